@@ -0,0 +1,44 @@
+"""Recent `items add` / `money add` field values, so `--like N` can start from an earlier entry."""
+
+import json
+import os
+from typing import Any, Dict, List
+
+HISTORY_FILE = "cli_history.json"
+HISTORY_SIZE = 20
+
+
+def history_path(settings_path: str) -> str:
+    return os.path.join(os.path.dirname(settings_path), HISTORY_FILE)
+
+
+def _load(path: str) -> Dict[str, List[Dict[str, Any]]]:
+    try:
+        with open(path, "r", encoding="utf-8") as f:
+            data = json.load(f)
+    except (OSError, ValueError):
+        # A missing or mangled history only costs the recall feature, never the command itself.
+        return {}
+    return data if isinstance(data, dict) else {}
+
+
+def recent(path: str, command: str) -> List[Dict[str, Any]]:
+    """Saved values for command, most recent first."""
+    return [entry for entry in _load(path).get(command, []) if isinstance(entry, dict)]
+
+
+def recall(path: str, command: str, number: int) -> Dict[str, Any]:
+    """The number-th most recent values for command (1 = last); ValueError when there is no such entry."""
+    entries = recent(path, command)
+    if not 1 <= number <= len(entries):
+        raise ValueError(f"No '{command}' history entry {number}; {len(entries)} saved")
+    return dict(entries[number - 1])
+
+
+def remember(path: str, command: str, values: Dict[str, Any]) -> None:
+    data = _load(path)
+    entries = [entry for entry in data.get(command, []) if entry != values]
+    data[command] = ([values] + entries)[:HISTORY_SIZE]
+    os.makedirs(os.path.dirname(path), exist_ok=True)
+    with open(path, "w", encoding="utf-8") as f:
+        json.dump(data, f, indent=2)
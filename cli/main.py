@@ -0,0 +1,1811 @@
+import argparse
+import contextlib
+import json
+import os
+import sys
+from datetime import datetime, time, timedelta
+from typing import List, Optional, Tuple
+
+from cli.history import history_path, recall, recent, remember
+from cli.server import serve
+from cli.table import render_table, score_color, use_color
+from core.archive import archive_path, restore_archived, split_for_archive
+from core.backup import create_backup, enforce_retention, preview_retention
+from core.config_check import check_config_dir, default_config_dir
+from core.config_manager import (
+    DEFAULT_BOOK,
+    DEFAULT_ITEM_RATINGS,
+    ConfigManager,
+    describe_money_template,
+    ensure_paths,
+    ensure_startup_files,
+)
+from core.csv_storage import (
+    READ_LOCK_MODES,
+    export_columns,
+    is_document,
+    iter_jsonl,
+    read_header,
+    read_items,
+    read_money,
+    set_lock_timeout,
+    set_read_lock,
+    validate_import,
+    write_items,
+    write_csv_columns,
+    write_jsonl,
+    write_money,
+)
+from core.dependencies import dependency_error, purchase_plan
+from core.duplicates import duplicate_groups, merge_group
+from core.errors import AmbiguousIdError, RecordNotFoundError
+from core.filters import parse_filter
+from core.forecast import cashflow_projection
+from core.models import (
+    DECISIONS,
+    UNCATEGORIZED,
+    UNSCORED_MARK,
+    ItemRecord,
+    MoneyRecord,
+    format_score,
+    new_id,
+    parse_date,
+)
+from core.reports import (
+    SCORE_FIELDS,
+    balance_as_of,
+    category_totals,
+    decisions_csv,
+    decisions_markdown,
+    markdown_report,
+    monthly_balances,
+    purchase_log,
+    purchases_csv,
+    purchases_markdown,
+    reconciliation,
+)
+from core.merge import CONFLICT_STRATEGIES, find_conflicts, merge_records, resolve_conflicts
+from core.relocation import changed_data_paths, relocate_data_file, remember_data_paths
+from core.schema import schema_drift
+from core.search import rank_items, rank_money
+from core.splits import replace_with_split, split_entry
+from core.suggest import pick_suggestion, suggestion_candidates
+from scoring.scoring import DRIVER_PHRASES, overall_scores, score_drivers, score_item
+from scoring.sensitivity import weight_sensitivity
+
+
+def load_config(create_missing: bool = True, book: Optional[str] = None) -> ConfigManager:
+    """The config, working in `book` for this run when given (ValueError if there is no such book)."""
+    config = ConfigManager()
+    if book:
+        config.use_book(book)
+    if create_missing:
+        ensure_paths(config.paths)
+        # Checked before the startup files exist, so a freshly created empty file is not taken for data.
+        config.moved_data_paths = changed_data_paths(config.settings, config.settings_path)
+        if not config.moved_data_paths:
+            remember_data_paths(config.settings, config.settings_path)
+            ensure_startup_files(config)
+    set_lock_timeout(config.settings["storage"]["lock_timeout_seconds"])
+    return config
+
+
+def _items_path(config: ConfigManager) -> str:
+    return config.data_files()[0]
+
+
+def _money_path(config: ConfigManager) -> str:
+    return config.data_files()[1]
+
+
+def _read_items(config: ConfigManager) -> List[ItemRecord]:
+    """Items from the data file; with storage.rescore_on_load, rescored from the current weights (in memory only)."""
+    items = read_items(_items_path(config))
+    if config.settings["storage"].get("rescore_on_load", False):
+        for item, score in zip(items, overall_scores(items, config.weights)):
+            item.overall_score = score
+    return items
+
+
+def _save_items(config: ConfigManager, items: List[ItemRecord]) -> None:
+    path = _items_path(config)
+    write_items(path, items)
+    if config.settings["ui"].get("autosave", True):
+        create_backup(path, config.paths["backup_dir"], config.settings["backup"])
+
+
+def _save_money(config: ConfigManager, entries: List[MoneyRecord]) -> None:
+    path = _money_path(config)
+    write_money(path, entries)
+    if config.settings["ui"].get("autosave", True):
+        create_backup(path, config.paths["backup_dir"], config.settings["backup"])
+
+
+def _find_by_id(records: List, id_or_prefix: str, noun: str):
+    """The record whose id equals id_or_prefix, or else the only one whose id starts with it (like git short hashes)."""
+    wanted = id_or_prefix.strip().lower()
+    matches = []
+    for record in records:
+        if record.id.lower() == wanted:
+            return record
+        if wanted and record.id.lower().startswith(wanted):
+            matches.append(record)
+    if not matches:
+        raise RecordNotFoundError(f"No {noun} with id {id_or_prefix}")
+    if len(matches) > 1:
+        candidates = sorted(record.id for record in matches)
+        shown = "\n  ".join(candidates[:10]) + ("\n  ..." if len(candidates) > 10 else "")
+        raise AmbiguousIdError(f"Id prefix '{id_or_prefix}' matches {len(matches)} {noun}s:\n  {shown}", candidates)
+    return matches[0]
+
+
+def _find_item(items: List[ItemRecord], item_id: str) -> ItemRecord:
+    return _find_by_id(items, item_id, "item")
+
+
+def _find_money(entries: List[MoneyRecord], entry_id: str) -> MoneyRecord:
+    return _find_by_id(entries, entry_id, "money entry")
+
+
+def items_note(args: argparse.Namespace, config: ConfigManager) -> None:
+    items = _read_items(config)
+    record = _find_item(items, args.id)
+    note = record.add_note(args.text)
+    _save_items(config, items)
+    print(f"Note added to '{record.product}': {note}")
+
+
+RECURRENCE_CHOICES = ["none", "once", "weekly", "biweekly", "monthly", "quarterly", "yearly"]
+
+
+def _print_score_breakdown(result, weights: dict) -> None:
+    labels = dict(SCORE_FIELDS)
+    for key, score in result.field_scores.items():
+        if key == "overall":
+            continue
+        label = labels.get(key, key.replace("_", " ").title())
+        print(f"  {label:<18} {score:5.2f}  (weight {float(weights.get(key, 1.0)):g})")
+    print(f"  {'Overall':<18} {result.overall:5.2f}")
+
+
+# Fields `items add` / `money add` remember for --like; dates are always given per entry.
+ITEM_ADD_DEFAULTS = {
+    "product": "",
+    "cost": 0.0,
+    "description": "",
+    "location": "",
+    "reference": "",
+    # Overridden by ui.item_defaults; see _item_add_defaults.
+    **DEFAULT_ITEM_RATINGS,
+    "justification": "",
+    "recurrence": "none",
+    "expected_uses": None,
+    "depends_on": "",
+    "effort": None,
+    "image_path": "",
+}
+MONEY_ADD_DEFAULTS = {
+    "source": "",
+    "entry_type": "expense",
+    "amount": 0.0,
+    "notes": "",
+    "category": "",
+    "link": "",
+}
+
+
+def _item_add_defaults(config: ConfigManager) -> dict:
+    return {**ITEM_ADD_DEFAULTS, **config.item_defaults()}
+
+
+def _add_values(args: argparse.Namespace, config: ConfigManager, command: str, defaults: dict) -> dict:
+    """Defaults, overlaid with history entry --like N, overlaid with the flags actually given."""
+    values = dict(defaults)
+    if args.like:
+        recalled = recall(history_path(config.settings_path), command, args.like)
+        values.update((key, value) for key, value in recalled.items() if key in defaults)
+    values.update((key, getattr(args, key)) for key in defaults if getattr(args, key) is not None)
+    if args.like:
+        print(f"Based on '{command}' history entry {args.like}:")
+        for key, value in values.items():
+            print(f"  {key:<14} {'' if value is None else value}")
+    return values
+
+
+def add_history(args: argparse.Namespace, config: ConfigManager) -> None:
+    entries = recent(history_path(config.settings_path), args.history_command)
+    if not entries:
+        print(f"No '{args.history_command}' history yet.")
+        return
+    for number, entry in enumerate(entries, 1):
+        shown = ", ".join(f"{key}={value}" for key, value in entry.items() if value not in ("", None))
+        print(f"{number:>3}. {shown}")
+
+
+def _item_from_values(args: argparse.Namespace, values: dict, location: str) -> ItemRecord:
+    return ItemRecord(
+        id=new_id(),
+        date=args.date or datetime.now(),
+        product=values["product"],
+        description=values["description"],
+        location=location,
+        reference=values["reference"],
+        cost=values["cost"],
+        urgency=values["urgency"],
+        value=values["value"],
+        want=values["want"],
+        price_comp=values["price_comp"],
+        effect=values["effect"],
+        justification=values["justification"],
+        recurrence=values["recurrence"],
+        expected_uses=values["expected_uses"],
+        target_date=args.target_date,
+        depends_on=values["depends_on"],
+        effort=values["effort"],
+        image_path=values["image_path"],
+    )
+
+
+def items_add(args: argparse.Namespace, config: ConfigManager) -> None:
+    values = _add_values(args, config, "items add", _item_add_defaults(config))
+    if not values["product"]:
+        raise ValueError("A product name is required (or start from an earlier one with --like N)")
+    if config.cost_exceeds_warning(values["cost"]) and not args.force:
+        raise ValueError(f"Cost {values['cost']:,.2f} is above cost_warn_threshold; re-run with --force")
+    items = _read_items(config)
+    if values["depends_on"]:
+        values["depends_on"] = _find_item(items, values["depends_on"]).id
+    record = _item_from_values(args, values, config.remember_location(values["location"]))
+    result = score_item(record, config.weights)
+    record.overall_score = result.overall
+    problem = dependency_error(items + [record], record.id, record.depends_on)
+    if problem:
+        raise ValueError(problem)
+    items.append(record)
+    _save_items(config, items)
+    remember(history_path(config.settings_path), "items add", values)
+    print("Item added.")
+    if args.verbose >= 1:
+        _print_score_breakdown(result, config.weights.get("weights", {}))
+    if args.verbose >= 2:
+        print(json.dumps(record.to_row(), ensure_ascii=False, indent=2))
+
+
+def items_score_preview(args: argparse.Namespace, config: ConfigManager) -> None:
+    """Score an item built from the `items add` flags without saving it, the location list or history."""
+    values = _add_values(args, config, "items add", _item_add_defaults(config))
+    record = _item_from_values(args, values, values["location"])
+    result = score_item(record, config.weights)
+    print(f"Score preview for '{record.product or 'unnamed item'}' (not saved):")
+    _print_score_breakdown(result, config.weights.get("weights", {}))
+    scores = [item.overall_score for item in _read_items(config) if item.overall_score is not None]
+    if scores:
+        rank = 1 + sum(1 for score in scores if score > result.overall)
+        print(f"Would rank #{rank} of {len(scores) + 1} scored items.")
+    if config.cost_exceeds_warning(values["cost"]):
+        print(f"Note: cost {values['cost']:,.2f} is above cost_warn_threshold; `items add` would need --force.")
+
+
+def _print_records(
+    args: argparse.Namespace, records: list, headers: List[str], rows: List[List[str]], **table
+) -> None:
+    if args.format == "json":
+        print(json.dumps([record.to_row() for record in records], ensure_ascii=False, indent=2))
+    elif args.format == "plain":
+        for row in rows:
+            print(" | ".join(row))
+    else:
+        print(render_table(headers, rows, **table))
+
+
+def _score_in_range(item: ItemRecord, args: argparse.Namespace) -> bool:
+    if args.min_score is None and args.max_score is None:
+        return True
+    if item.overall_score is None:
+        return args.include_unscored
+    if args.min_score is not None and item.overall_score < args.min_score:
+        return False
+    return args.max_score is None or item.overall_score <= args.max_score
+
+
+# Short names for the edit-time columns, e.g. `--sort updated --desc` for the most recently edited first.
+SORT_ALIASES = {"created": "created_at", "updated": "updated_at"}
+ITEM_SORT_FIELDS = [h for h in ItemRecord.headers() if h != "history"] + list(SORT_ALIASES)
+
+
+def _sort_value(record, field: str):
+    value = getattr(record, field)
+    # Unset values sort last either way; text compares case-insensitively.
+    if value is None or value == "":
+        return (1, "")
+    return (0, value.lower() if isinstance(value, str) else value)
+
+
+def _recurrence_matches(item: ItemRecord, args: argparse.Namespace) -> bool:
+    if args.recurring and not item.is_recurring():
+        return False
+    return not args.recurrence or item.recurrence_kind() == args.recurrence
+
+
+def _select_items(args: argparse.Namespace, config: ConfigManager, default_sort: Optional[str] = None) -> list:
+    """Items matching --filter and the other selection options, ordered by --sort (or default_sort, descending)."""
+    matches = parse_filter(args.filter or "")
+    items = [
+        item
+        for item in _read_items(config)
+        if matches(item)
+        and _score_in_range(item, args)
+        and _in_date_range(item, args)
+        and _recurrence_matches(item, args)
+    ]
+    field = SORT_ALIASES.get(args.sort, args.sort) or default_sort
+    if field:
+        descending = args.desc if args.sort else True
+        present = [item for item in items if _sort_value(item, field)[0] == 0]
+        missing = [item for item in items if _sort_value(item, field)[0] == 1]
+        present.sort(key=lambda item: _sort_value(item, field), reverse=descending)
+        items = present + missing
+    return items
+
+
+def _add_item_selection_arguments(parser: argparse.ArgumentParser) -> None:
+    parser.add_argument("--filter", help="Only items matching an expression, e.g. 'cost>100 and recurrence=monthly'")
+    parser.add_argument("--min-score", type=float, help="Only items whose overall score is at least this")
+    parser.add_argument("--max-score", type=float, help="Only items whose overall score is at most this")
+    parser.add_argument(
+        "--include-unscored", action="store_true", help="Keep items that have no stored score when filtering by score"
+    )
+    parser.add_argument("--since", type=_parse_date_arg, help="Only items dated on or after YYYY-MM-DD")
+    parser.add_argument("--until", type=_parse_date_arg, help="Only items dated on or before YYYY-MM-DD")
+    recurrence = parser.add_mutually_exclusive_group()
+    recurrence.add_argument("--recurrence", choices=RECURRENCE_CHOICES, help="Only items with this recurrence")
+    recurrence.add_argument("--recurring", action="store_true", help="Only items that repeat (not none or once)")
+    parser.add_argument("--sort", choices=ITEM_SORT_FIELDS, metavar="FIELD", help="Order by this item field")
+    parser.add_argument("--desc", action="store_true", help="With --sort, order from highest to lowest")
+
+
+def items_list(args: argparse.Namespace, config: ConfigManager) -> None:
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    date_fmt = config.settings["ui"]["date_format"]
+    items = _select_items(args, config, default_sort="overall_score")
+    rows = [
+        [
+            item.id,
+            " ".join(item.product.splitlines()),
+            item.date.strftime(date_fmt),
+            f"{symbol}{item.cost:,.2f}",
+            item.recurrence,
+            format_score(item.overall_score),
+        ]
+        for item in items
+    ]
+    overall_col = 5
+    theme = config.get_theme()
+
+    def colorize(col: int, text: str) -> Optional[str]:
+        return score_color(float(text), theme) if col == overall_col and text != UNSCORED_MARK else None
+
+    _print_records(
+        args,
+        items,
+        ["Id", "Product", "Date", "Cost", "Recurrence", "Overall"],
+        rows,
+        numeric=(3, overall_col),
+        colorize=colorize if use_color() else None,
+    )
+
+
+def money_list(args: argparse.Namespace, config: ConfigManager) -> None:
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    date_fmt = config.settings["ui"]["date_format"]
+    all_entries = read_money(_money_path(config))
+    entries = sorted(all_entries, key=lambda e: getattr(e, SORT_ALIASES.get(args.sort, "date")), reverse=True)
+    if args.uncleared:
+        entries = [entry for entry in entries if not entry.cleared]
+    rows = [
+        [
+            entry.id,
+            entry.date.strftime(date_fmt),
+            entry.entry_type,
+            entry.source_or_destination,
+            f"{symbol}{entry.amount:,.2f}",
+            entry.category,
+            entry.linked_item_id,
+            "yes" if entry.cleared else "",
+        ]
+        for entry in entries
+    ]
+    headers = ["Id", "Date", "Type", "Source/Destination", "Amount", "Category", "Linked Item", "Cleared"]
+    _print_records(args, entries, headers, rows, numeric=(4,))
+    if args.format == "table":
+        # Over every entry to date, whatever is listed: the cleared balance is what the statement should show.
+        cleared, actual, uncleared = reconciliation(all_entries, datetime.now())
+        noun = "entry" if uncleared == 1 else "entries"
+        print(
+            f"\nCleared balance: {symbol}{cleared:,.2f}  Actual balance: {symbol}{actual:,.2f}"
+            f"  Difference: {symbol}{actual - cleared:,.2f} in {uncleared} uncleared {noun}"
+        )
+
+
+def _with_time(date_fmt: str) -> str:
+    # Edit times need the time of day even when the configured date format leaves it out.
+    return date_fmt if "%H" in date_fmt or "%I" in date_fmt else f"{date_fmt} %H:%M"
+
+
+def items_show(args: argparse.Namespace, config: ConfigManager) -> None:
+    record = _find_item(_read_items(config), args.id)
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    date_fmt = config.settings["ui"]["date_format"]
+    stamp_fmt = _with_time(date_fmt)
+    print(f"{record.product} ({record.id})")
+    print(f"  Date:        {record.date.strftime(date_fmt)}")
+    print(f"  Cost:        {symbol}{record.cost:,.2f}")
+    print(f"  Location:    {record.location}")
+    print(f"  Recurrence:  {record.recurrence}")
+    print(f"  Effort:      {record.effort or 'not rated'}")
+    print(f"  Overall:     {format_score(record.overall_score)}")
+    print(f"  Edited:      {record.updated_at.strftime(stamp_fmt)} (added {record.created_at.strftime(stamp_fmt)})")
+    url = record.reference_url()
+    if url:
+        print(f"  URL:         {url}")
+    elif record.reference:
+        print(f"  Reference:   {record.reference}")
+    if record.description:
+        print(f"  Description: {record.description}")
+    if record.justification:
+        print(f"  Why:         {record.justification}")
+    if record.image_path:
+        missing = "" if os.path.isfile(record.image_path) else " (not found)"
+        print(f"  Image:       {record.image_path}{missing}")
+    if record.depends_on:
+        items = _read_items(config)
+        prereq = next((item for item in items if item.id == record.depends_on), None)
+        print(f"  Depends on:  {prereq.product if prereq else 'missing item'} ({record.depends_on})")
+    for note in record.history:
+        print(f"  Note:        {note}")
+
+
+def items_depend(args: argparse.Namespace, config: ConfigManager) -> None:
+    items = _read_items(config)
+    record = _find_item(items, args.id)
+    prereq = _find_item(items, args.prerequisite_id)
+    problem = dependency_error(items, record.id, prereq.id)
+    if problem:
+        raise ValueError(problem)
+    record.depends_on = prereq.id
+    record.touch()
+    _save_items(config, items)
+    print(f"'{record.product}' now depends on '{prereq.product}'.")
+
+
+def items_undepend(args: argparse.Namespace, config: ConfigManager) -> None:
+    items = _read_items(config)
+    record = _find_item(items, args.id)
+    if not record.depends_on:
+        print(f"'{record.product}' has no prerequisite.")
+        return
+    record.depends_on = ""
+    record.touch()
+    _save_items(config, items)
+    print(f"Cleared the prerequisite of '{record.product}'.")
+
+
+def items_decide(args: argparse.Namespace, config: ConfigManager) -> None:
+    items = _read_items(config)
+    record = _find_item(items, args.id)
+    decision = "" if args.decision == "none" else args.decision
+    record.decision = decision
+    record.touch()
+    _save_items(config, items)
+    print(f"'{record.product}': {decision or 'decision cleared'}.")
+
+
+def items_plan(args: argparse.Namespace, config: ConfigManager) -> None:
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    plan = purchase_plan(_read_items(config))
+    names = {item.id: item.product for item in plan}
+    for position, item in enumerate(plan, 1):
+        after = f"  (after {names[item.depends_on]})" if item.depends_on in names else ""
+        print(f"{position:>3}. {item.product}  {symbol}{item.cost:,.2f}  {format_score(item.overall_score)}{after}")
+
+
+def items_score(args: argparse.Namespace, config: ConfigManager) -> None:
+    # The stored scores, not _read_items: rescore_on_load would fill every missing score in memory first.
+    items = read_items(_items_path(config))
+    targets = items if args.all else [item for item in items if item.overall_score is None]
+    for item in targets:
+        item.overall_score = score_item(item, config.weights).overall
+    if targets:
+        _save_items(config, items)
+    print(f"Scored {len(targets)} {'item(s)' if args.all else 'unscored item(s)'}.")
+
+
+def items_verify_scores(args: argparse.Namespace, config: ConfigManager) -> None:
+    """Compare each stored overall score with a fresh one; --fix rewrites the ones that differ."""
+    items = read_items(_items_path(config))
+    mismatched = []
+    for item in items:
+        fresh = score_item(item, config.weights).overall
+        if item.overall_score is None or abs(item.overall_score - fresh) > args.tolerance:
+            mismatched.append((item, fresh))
+    if not mismatched:
+        print(f"All {len(items)} stored scores match the current weights (tolerance {args.tolerance:g}).")
+        return
+    rows = [
+        [
+            item.id,
+            item.product,
+            format_score(item.overall_score),
+            f"{fresh:.2f}",
+            f"{fresh - item.overall_score:+.2f}" if item.overall_score is not None else "missing",
+        ]
+        for item, fresh in mismatched
+    ]
+    print(render_table(["Id", "Product", "Stored", "Computed", "Difference"], rows, numeric=(2, 3, 4)))
+    if not args.fix:
+        raise ValueError(f"{len(mismatched)} of {len(items)} stored score(s) differ; re-run with --fix to correct them")
+    _backup_items(config)
+    for item, fresh in mismatched:
+        item.overall_score = fresh
+    _save_items(config, items)
+    print(f"Fixed {len(mismatched)} score(s).")
+
+
+def items_search(args: argparse.Namespace, config: ConfigManager) -> None:
+    matches = rank_items(_read_items(config), args.query)
+    if not matches:
+        print(f"No items match '{args.query}'.")
+        return
+    for score, item in matches[: args.limit]:
+        print(f"{score:.2f}  {item.product}  {item.id}")
+
+
+def money_search(args: argparse.Namespace, config: ConfigManager) -> None:
+    matches = rank_money(_select_money(args, config), args.query)
+    if not matches:
+        print(f"No money entries match '{args.query}'.")
+        return
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    date_fmt = config.settings["ui"]["date_format"]
+    rows = [
+        [
+            f"{score:.2f}",
+            entry.id,
+            entry.date.strftime(date_fmt),
+            entry.entry_type,
+            entry.source_or_destination,
+            f"{symbol}{entry.amount:,.2f}",
+            entry.notes,
+        ]
+        for score, entry in matches[: args.limit]
+    ]
+    print(render_table(["Match", "Id", "Date", "Type", "Source/Destination", "Amount", "Notes"], rows, numeric=(0, 5)))
+
+
+def _driver_text(result, config: ConfigManager) -> str:
+    strongest, weakest = score_drivers(result, config.weights)
+    strong = DRIVER_PHRASES.get(strongest, (strongest, ""))[0] if strongest else "nothing stands out"
+    weak = DRIVER_PHRASES.get(weakest, ("", weakest))[1] if weakest else "nothing holds it back"
+    return f"strong: {strong}; weak: {weak}"
+
+
+def items_top(args: argparse.Namespace, config: ConfigManager) -> None:
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    scored = [(score_item(item, config.weights), item) for item in _read_items(config)]
+    scored.sort(key=lambda pair: (-pair[0].overall, pair[1].product.lower()))
+    if not scored:
+        print("No items yet.")
+        return
+    for position, (result, item) in enumerate(scored[: args.n], 1):
+        print(f"{position:>3}. {item.product}  {symbol}{item.cost:,.2f}  {result.overall:.2f}")
+        print(f"     {_driver_text(result, config)}")
+
+
+def items_suggest(args: argparse.Namespace, config: ConfigManager) -> None:
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    money = read_money(_money_path(config))
+    budget = args.budget if args.budget is not None else balance_as_of(money, datetime.now())
+    candidates = suggestion_candidates(_read_items(config), money, config.weights, budget)
+    picked = pick_suggestion(candidates, args.seed)
+    if picked is None:
+        print(f"Nothing unbought costs {symbol}{budget:,.2f} or less.")
+        return
+    item, result = picked
+    print(f"How about {item.product}? {symbol}{item.cost:,.2f}, score {result.overall:.2f}  ({item.id})")
+    print(f"  {_driver_text(result, config)}")
+    if item.justification:
+        print(f"  why: {item.justification}")
+    print(f"  Picked from {len(candidates)} item(s) within {symbol}{budget:,.2f}; higher scores are likelier.")
+
+
+def _confirm(prompt: str) -> bool:
+    try:
+        return input(f"{prompt} [y/N] ").strip().lower() in ("y", "yes")
+    except EOFError:
+        return False
+
+
+def items_merge_duplicates(args: argparse.Namespace, config: ConfigManager) -> None:
+    items = _read_items(config)
+    money = read_money(_money_path(config))
+    tolerance = args.cost_tolerance / 100 if args.cost_tolerance is not None else None
+    groups = duplicate_groups(items, tolerance)
+    if not groups:
+        print("No duplicate items found.")
+        return
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    backed_up = False
+    merged = 0
+    for group in groups:
+        print(f"\n{group[0].product} ({len(group)} items):")
+        for item in group:
+            spent = sum(entry.amount for entry in money if entry.linked_item_id == item.id)
+            print(f"  {item.id}  {item.date:%Y-%m-%d}  {symbol}{item.cost:,.2f}  linked {symbol}{spent:,.2f}")
+        if not args.yes and not _confirm(f"Merge into {group[0].id}?"):
+            continue
+        if not backed_up:
+            # One snapshot of both files before the first change, even with autosave backups off.
+            for path in dict.fromkeys((_items_path(config), _money_path(config))):
+                if os.path.exists(path):
+                    create_backup(path, config.paths["backup_dir"], config.settings["backup"])
+            backed_up = True
+        keep, relinked = merge_group(group, items, money)
+        keep.overall_score = score_item(keep, config.weights).overall
+        merged += len(group) - 1
+        print(f"  Merged into {keep.id}; relinked {len(relinked)} money entr{'y' if len(relinked) == 1 else 'ies'}.")
+    if merged:
+        write_items(_items_path(config), items)
+        write_money(_money_path(config), money)
+    print(f"\nConsolidated {merged} duplicate item(s).")
+
+
+BULK_EDIT_LOCKED_FIELDS = {"id", "history", "overall_score", "created_at", "updated_at"}
+
+
+def _parse_assignment(text: str) -> Tuple[str, str]:
+    if "=" not in text:
+        raise argparse.ArgumentTypeError(f"expected field=value, got '{text}'")
+    field, value = (part.strip() for part in text.split("=", 1))
+    if field not in ItemRecord.headers() or field in BULK_EDIT_LOCKED_FIELDS:
+        raise argparse.ArgumentTypeError(f"'{field}' is not an editable item field")
+    return field, value
+
+
+def items_bulk_edit(args: argparse.Namespace, config: ConfigManager) -> None:
+    items = _read_items(config)
+    matches = parse_filter(args.filter)
+    assignments = dict(args.set)
+    if "location" in assignments:
+        # Same spelling rules as items add, so "online" and "Online" do not drift apart.
+        assignments["location"] = config.remember_location(assignments["location"])
+    modified = 0
+    for idx, item in enumerate(items):
+        if not matches(item):
+            continue
+        # Round-trip through the CSV row format so values are parsed exactly like a file load.
+        updated = ItemRecord.from_row({**item.to_row(), **assignments})
+        if updated == item:
+            continue
+        if updated.cost != item.cost and config.cost_exceeds_warning(updated.cost) and not args.force:
+            raise ValueError(
+                f"Cost {updated.cost:,.2f} for '{item.product}' is above cost_warn_threshold; re-run with --force"
+            )
+        updated.overall_score = score_item(updated, config.weights).overall
+        updated.touch()
+        items[idx] = updated
+        modified += 1
+    if "depends_on" in assignments:
+        for item in items:
+            problem = dependency_error(items, item.id, item.depends_on)
+            if problem:
+                raise ValueError(f"{item.product}: {problem}")
+    if modified:
+        _save_items(config, items)
+    print(f"Modified {modified} item(s).")
+
+
+def _export(records: list, args: argparse.Namespace, record_cls, csv_writer) -> None:
+    path, fmt = args.path, args.format
+    columns = export_columns(record_cls, args.columns.split(",")) if args.columns else None
+    if fmt == "jsonl" and path == "-":
+        sys.stdout.writelines(iter_jsonl(records, columns))
+        return
+    if path == "-":
+        raise ValueError("Only --format jsonl can be written to stdout")
+    if fmt == "jsonl":
+        write_jsonl(path, records, columns)
+    elif columns:
+        write_csv_columns(path, records, columns)
+    else:
+        csv_writer(path, records)
+    print(f"Exported {len(records)} record(s) to {path}", file=sys.stderr)
+
+
+def _import(report, existing: list, args: argparse.Namespace) -> Optional[list]:
+    """Print the validation report and conflicts, and return the records to save, or None for a dry run."""
+    for line in report.lines():
+        print(line)
+    if args.partial and report.columns:
+        print(f"Columns updated: {', '.join(report.columns)}; other fields keep their current values")
+    if args.check:
+        return None
+    if not report.ok and not args.skip_invalid:
+        raise ValueError("Import has errors; fix the file or pass --skip-invalid to import only valid rows")
+    if args.replace:
+        return list(report.records)
+    conflicts = find_conflicts(existing, report.records)
+    resolve_conflicts(conflicts, args.on_conflict)
+    if conflicts:
+        mine = sum(1 for conflict in conflicts if conflict.keep == "mine")
+        theirs = len(conflicts) - mine
+        print(f"Conflicts: {len(conflicts)} id(s) differ; kept yours for {mine}, took the import's for {theirs}")
+        for conflict in conflicts:
+            print(f"  {conflict.describe()}")
+    return merge_records(existing, report.records, conflicts)
+
+
+def items_import(args: argparse.Namespace, config: ConfigManager) -> None:
+    existing = _read_items(config)
+    report = validate_import(args.path, ItemRecord, merge_into=existing if args.partial else None)
+    items = _import(report, existing, args)
+    if items is None:
+        return
+    for item in report.records:
+        item.overall_score = score_item(item, config.weights).overall
+    _save_items(config, items)
+    print(f"Imported {len(report.records)} item(s).")
+
+
+def money_import(args: argparse.Namespace, config: ConfigManager) -> None:
+    item_ids = [item.id for item in _read_items(config)]
+    existing = read_money(_money_path(config))
+    report = validate_import(args.path, MoneyRecord, item_ids, merge_into=existing if args.partial else None)
+    entries = _import(report, existing, args)
+    if entries is None:
+        return
+    _save_money(config, entries)
+    print(f"Imported {len(report.records)} money entr{'y' if len(report.records) == 1 else 'ies'}.")
+
+
+def items_export(args: argparse.Namespace, config: ConfigManager) -> None:
+    _export(_select_items(args, config), args, ItemRecord, write_items)
+
+
+def _in_date_range(record, args: argparse.Namespace) -> bool:
+    # --until is inclusive of the whole day.
+    if args.since and record.date < args.since:
+        return False
+    return not args.until or record.date.date() <= args.until.date()
+
+
+def _select_money(args: argparse.Namespace, config: ConfigManager) -> list:
+    """Entries matching --type, --category and the date range, ordered by --sort when given."""
+    entries = [entry for entry in read_money(_money_path(config)) if _in_date_range(entry, args)]
+    if args.type:
+        entries = [entry for entry in entries if entry.entry_type.lower() == args.type]
+    if args.category:
+        entries = [entry for entry in entries if entry.category_label().lower() == args.category.lower()]
+    if getattr(args, "sort", None):
+        entries.sort(key=lambda entry: _sort_value(entry, args.sort), reverse=args.desc)
+    return entries
+
+
+def _add_money_selection_arguments(parser: argparse.ArgumentParser) -> None:
+    parser.add_argument("--type", choices=["income", "expense"], help="Only this kind of entry")
+    parser.add_argument("--category", help=f"Only entries in this category ('{UNCATEGORIZED}' for none)")
+    parser.add_argument("--since", type=_parse_date_arg, help="Only entries on or after YYYY-MM-DD")
+    parser.add_argument("--until", type=_parse_date_arg, help="Only entries on or before YYYY-MM-DD")
+
+
+def money_export(args: argparse.Namespace, config: ConfigManager) -> None:
+    _export(_select_money(args, config), args, MoneyRecord, write_money)
+
+
+def items_count(args: argparse.Namespace, config: ConfigManager) -> None:
+    print(len(_select_items(args, config)))
+
+
+def money_count(args: argparse.Namespace, config: ConfigManager) -> None:
+    print(len(_select_money(args, config)))
+
+
+def _money_template(config: ConfigManager, name: str) -> dict:
+    templates = config.money_templates()
+    if name not in templates:
+        known = ", ".join(templates) or "none saved yet"
+        raise ValueError(f"No money template named '{name}' (known: {known})")
+    return templates[name]
+
+
+def money_templates(args: argparse.Namespace, config: ConfigManager) -> None:
+    templates = config.money_templates()
+    if not templates:
+        print("No money templates saved; add them in the app or under ui.money_templates in settings.json.")
+        return
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    for name, template in templates.items():
+        print(f"{name}: {describe_money_template(template, symbol)}")
+
+
+def money_add(args: argparse.Namespace, config: ConfigManager) -> None:
+    # A template replaces the defaults, so --like and flags still override it.
+    template = _money_template(config, args.template) if args.template else {}
+    if args.template and "amount" not in template and args.amount is None:
+        raise ValueError(f"Template '{args.template}' has no amount; pass --amount")
+    values = _add_values(args, config, "money add", {**MONEY_ADD_DEFAULTS, **template})
+    if not values["source"]:
+        raise ValueError("A source or destination is required (or start from an earlier one with --like N)")
+    if values["link"]:
+        values["link"] = _find_item(_read_items(config), values["link"]).id
+    entry = MoneyRecord(
+        id=new_id(),
+        date=args.date or datetime.now(),
+        entry_type=values["entry_type"],
+        source_or_destination=values["source"],
+        amount=values["amount"],
+        notes=values["notes"],
+        linked_item_id=values["link"],
+        category=values["category"],
+    )
+    entries = read_money(_money_path(config))
+    entries.append(entry)
+    _save_money(config, entries)
+    remember(history_path(config.settings_path), "money add", values)
+    print("Money entry added.")
+    if args.verbose >= 2:
+        print(json.dumps(entry.to_row(), ensure_ascii=False, indent=2))
+
+
+def _split_part(text: str) -> Tuple[float, str]:
+    amount, _, category = text.partition(":")
+    try:
+        return float(amount), category
+    except ValueError:
+        raise argparse.ArgumentTypeError(f"expected AMOUNT or AMOUNT:CATEGORY, got '{text}'") from None
+
+
+def money_split(args: argparse.Namespace, config: ConfigManager) -> None:
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    entries = read_money(_money_path(config))
+    entry = _find_money(entries, args.id)
+    parts = split_entry(entry, [amount for amount, _ in args.parts], [category for _, category in args.parts])
+    _save_money(config, replace_with_split(entries, entry, parts))
+    print(f"Split {symbol}{entry.amount:,.2f} ({entry.source_or_destination}) into {len(parts)} entries:")
+    for part in parts:
+        print(f"  {part.id}  {symbol}{part.amount:,.2f}  {part.category_label()}")
+
+
+def money_link(args: argparse.Namespace, config: ConfigManager) -> None:
+    item = _find_item(_read_items(config), args.item_id)
+    entries = read_money(_money_path(config))
+    entry = _find_money(entries, args.money_id)
+    entry.linked_item_id = item.id
+    entry.touch()
+    _save_money(config, entries)
+    print(f"Linked money entry {entry.id} to '{item.product}'.")
+
+
+def money_unlink(args: argparse.Namespace, config: ConfigManager) -> None:
+    entries = read_money(_money_path(config))
+    entry = _find_money(entries, args.money_id)
+    if not entry.linked_item_id:
+        print(f"Money entry {entry.id} is not linked to an item.")
+        return
+    entry.linked_item_id = ""
+    entry.touch()
+    _save_money(config, entries)
+    print(f"Unlinked money entry {entry.id}.")
+
+
+def money_clear(args: argparse.Namespace, config: ConfigManager) -> None:
+    _set_cleared(args, config, True)
+
+
+def money_unclear(args: argparse.Namespace, config: ConfigManager) -> None:
+    _set_cleared(args, config, False)
+
+
+def _set_cleared(args: argparse.Namespace, config: ConfigManager, cleared: bool) -> None:
+    entries = read_money(_money_path(config))
+    entry = _find_money(entries, args.money_id)
+    state = "cleared" if cleared else "uncleared"
+    if entry.cleared == cleared:
+        print(f"Money entry {entry.id} is already {state}.")
+        return
+    entry.cleared = cleared
+    entry.touch()
+    _save_money(config, entries)
+    print(f"Marked money entry {entry.id} as {state}.")
+
+
+def _backup_items(config: ConfigManager) -> None:
+    path = _items_path(config)
+    if os.path.exists(path):
+        create_backup(path, config.paths["backup_dir"], config.settings["backup"])
+
+
+def _backup_money(config: ConfigManager) -> None:
+    path = _money_path(config)
+    if os.path.exists(path):
+        create_backup(path, config.paths["backup_dir"], config.settings["backup"])
+
+
+def money_archive(args: argparse.Namespace, config: ConfigManager) -> None:
+    if args.before:
+        cutoff = args.before
+    else:
+        days = config.settings["storage"].get("archive_after_days", 365)
+        cutoff = datetime.combine((datetime.now() - timedelta(days=days)).date(), datetime.min.time())
+    money_path = _money_path(config)
+    archive = archive_path(money_path, cutoff)
+    kept, archived = split_for_archive(read_money(money_path), cutoff, archive, args.opening_balance)
+    if not archived:
+        print(f"No money entries before {cutoff:%Y-%m-%d}.")
+        return
+    print(f"{len(archived)} entr{'y' if len(archived) == 1 else 'ies'} before {cutoff:%Y-%m-%d} -> {archive}")
+    if args.dry_run:
+        return
+    _backup_money(config)
+    # Archiving twice with the same cutoff adds to the existing archive instead of replacing it.
+    existing = read_money(archive)
+    known = {entry.id for entry in archived}
+    write_money(archive, [entry for entry in existing if entry.id not in known] + archived)
+    write_money(money_path, kept)
+    print(f"Archived {len(archived)} entr{'y' if len(archived) == 1 else 'ies'}; {len(kept)} remain in {money_path}.")
+
+
+def money_unarchive(args: argparse.Namespace, config: ConfigManager) -> None:
+    if not os.path.exists(args.archive):
+        raise FileNotFoundError(f"No archive at {args.archive}")
+    archived = read_money(args.archive)
+    entries = read_money(_money_path(config))
+    restored, stand_in = restore_archived(entries, archived, args.archive)
+    _backup_money(config)
+    _save_money(config, restored)
+    added = len(restored) - (len(entries) - (1 if stand_in else 0))
+    dropped = " and removed its opening-balance entry" if stand_in else ""
+    print(f"Restored {added} entries from {args.archive}{dropped}.")
+
+
+def report(args: argparse.Namespace, config: ConfigManager) -> None:
+    ui = config.settings["ui"]
+    if args.kind == "purchases":
+        purchases = purchase_log(_read_items(config), read_money(_money_path(config)))
+        if args.format == "csv":
+            sys.stdout.write(purchases_csv(purchases, start_day=config.fiscal_month_start_day()))
+        else:
+            sys.stdout.write(
+                purchases_markdown(purchases, ui.get("currency_symbol", "$"), start_day=config.fiscal_month_start_day())
+            )
+        return
+    if args.kind == "decisions":
+        items = _read_items(config)
+        if args.format == "csv":
+            sys.stdout.write(decisions_csv(items))
+        else:
+            sys.stdout.write(decisions_markdown(items, ui.get("currency_symbol", "$")))
+        return
+    if args.format != "md":
+        raise ValueError("The summary report is only available as --format md")
+    print(
+        markdown_report(
+            _read_items(config),
+            read_money(_money_path(config)),
+            config.weights,
+            currency_symbol=ui.get("currency_symbol", "$"),
+            date_format=ui.get("date_format", "%Y-%m-%d %H:%M"),
+        )
+    )
+
+
+def money_report(args: argparse.Namespace, config: ConfigManager) -> None:
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    entries = read_money(_money_path(config))
+    if args.by_category:
+        totals = sorted(category_totals(entries).items(), key=lambda pair: pair[1][1], reverse=True)
+        width = max([len("Category")] + [len(name) for name, _ in totals])
+        print(f"{'Category':<{width}} {'Spent':>12} {'Received':>12}")
+        for name, (income, expenses) in totals:
+            print(f"{name:<{width}} {f'{symbol}{expenses:,.2f}':>12} {f'{symbol}{income:,.2f}':>12}")
+        return
+    income = sum(e.signed_amount() for e in entries if e.signed_amount() > 0)
+    expenses = -sum(e.signed_amount() for e in entries if e.signed_amount() < 0)
+    print(f"Income:   {symbol}{income:,.2f}")
+    print(f"Expenses: {symbol}{expenses:,.2f}")
+    print(f"Balance:  {symbol}{income - expenses:,.2f}")
+
+
+def money_balance(args: argparse.Namespace, config: ConfigManager) -> None:
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    chosen = args.balance_as_of or args.as_of
+    # A given date counts through the end of that day.
+    as_of = datetime.combine(chosen.date(), time.max) if chosen else datetime.now()
+    entries = read_money(_money_path(config))
+    if args.months:
+        rows = [
+            [month.month, f"{symbol}{month.net:,.2f}", f"{symbol}{month.balance:,.2f}"]
+            for month in monthly_balances(entries, as_of, args.months, config.fiscal_month_start_day())
+        ]
+        print(render_table(["Month", "Net", "Balance"], rows, numeric=(1, 2)))
+    print(f"Balance as of {as_of:%Y-%m-%d}: {symbol}{balance_as_of(entries, as_of):,.2f}")
+
+
+def doctor(args: argparse.Namespace, config: ConfigManager) -> None:
+    print(f"Settings: {config.settings_path}")
+    print(f"Weights:  {config.weights_path}")
+    for message in config.load_messages:
+        print(f"  warning: {message}")
+    problems = 0
+    item_ids = None
+    for label, path, record_cls in [
+        ("Items", _items_path(config), ItemRecord),
+        ("Money", _money_path(config), MoneyRecord),
+    ]:
+        if not os.path.exists(path):
+            print(f"{label}: {path} does not exist")
+            continue
+        report = validate_import(path, record_cls, item_ids)
+        print(f"{label}: " + "\n".join(report.lines()))
+        problems += len(report.errors) + len(report.duplicate_ids)
+        if record_cls is ItemRecord:
+            item_ids = [item.id for item in report.records]
+    if problems:
+        raise ValueError(
+            f"{problems} problem(s) found; fix the files above, or start the app with --safe to open it without them"
+        )
+    print("No problems found.")
+
+
+def schema_check(args: argparse.Namespace, config: ConfigManager) -> None:
+    """Compare each CSV's header row with the expected columns without reading any rows."""
+    targets = [("items", _items_path(config), ItemRecord), ("money", _money_path(config), MoneyRecord)]
+    broken = 0
+    for label, path, record_cls in targets:
+        if args.target not in ("all", label):
+            continue
+        if is_document(path):
+            print(f"{label}: {path} is a JSON document; only CSV files have a header row to check")
+            continue
+        if not os.path.exists(path):
+            print(f"{label}: {path} does not exist")
+            continue
+        headers = read_header(path)
+        if headers is None:
+            print(f"{label}: {path} is empty")
+            continue
+        drift = schema_drift(headers, record_cls)
+        print(f"{label}: {path}")
+        for line in drift.lines():
+            print(f"  {line}")
+        broken += not drift.ok()
+    if broken:
+        raise ValueError(f"{broken} file(s) are missing required columns and will not load")
+
+
+def book_list(args: argparse.Namespace, config: ConfigManager) -> None:
+    saved = config.settings["books"]["current"]
+    for name in config.book_names():
+        flags = (("in use", name == config.book), ("opens by default", name == saved))
+        marks = [label for label, flag in flags if flag]
+        items_path = config.book_paths(name)["items_csv"]
+        print(f"{name:<20} {os.path.dirname(items_path)}" + (f"  ({', '.join(marks)})" if marks else ""))
+
+
+def book_create(args: argparse.Namespace, config: ConfigManager) -> None:
+    config.create_book(args.name)
+    print(f"Created book '{args.name}'. Use it with --book {args.name}, or 'book use {args.name}' to keep it open.")
+
+
+def book_use(args: argparse.Namespace, config: ConfigManager) -> None:
+    config.set_current_book(args.name)
+    print(f"'{args.name}' is now the book the app and CLI open by default.")
+
+
+def config_validate(args: argparse.Namespace, config: Optional[ConfigManager]) -> None:
+    """Check every config file and report all problems; runs before (and instead of) loading the config."""
+    config_dir = args.dir or default_config_dir()
+    checks = check_config_dir(config_dir)
+    for check in checks:
+        print(f"{check.label}: {check.path}")
+        for line in check.lines():
+            print(f"  {line}")
+    invalid = [check.label for check in checks if not check.ok()]
+    if invalid:
+        raise ValueError(f"Invalid config in {config_dir}: {', '.join(invalid)}")
+    print(f"\nAll config files in {config_dir} are valid.")
+
+
+def serve_json(args: argparse.Namespace, config: ConfigManager) -> None:
+    weights = config.weights if config.settings["storage"].get("rescore_on_load", False) else None
+    server = serve(_items_path(config), _money_path(config), args.host, args.port, weights)
+    host, port = server.server_address[:2]
+    print(f"Serving /items, /money and /summary read-only at http://{host}:{port} (Ctrl+C to stop)", file=sys.stderr)
+    try:
+        server.serve_forever()
+    except KeyboardInterrupt:
+        pass
+    finally:
+        server.server_close()
+
+
+def relocate(args: argparse.Namespace, config: ConfigManager) -> None:
+    if not config.moved_data_paths:
+        print("No data paths changed since the last run; nothing to relocate.")
+        return
+    for key, old, new in config.moved_data_paths:
+        if args.start_empty:
+            print(f"{key}: starting empty at {new}; {old} is left where it is")
+            continue
+        backup = relocate_data_file(
+            old, new, config.book_paths(DEFAULT_BOOK)["backup_dir"], config.settings["backup"], move=args.move
+        )
+        print(f"{key}: {'moved' if args.move else 'copied'} {old} -> {new} (backup: {backup})")
+    remember_data_paths(config.settings, config.settings_path)
+
+
+def backup_simulate(args: argparse.Namespace, config: ConfigManager) -> None:
+    backup_dir = config.paths["backup_dir"]
+    policy = config.settings["backup"]
+    cap = policy.get("max_total_bytes")
+    print(
+        f"Policy: keep {policy.get('keep_recent', 3)} recent + {policy.get('keep_historical', 3)} historical"
+        + (f", at most {int(cap):,} bytes per file" if cap is not None else "")
+        + f" in {backup_dir}"
+    )
+    # dict.fromkeys drops the repeat when items and money share the combined data.json.
+    for path in dict.fromkeys((_items_path(config), _money_path(config))):
+        keep, delete = preview_retention(path, backup_dir, policy)
+        print(f"\n{os.path.basename(path)}: {len(keep)} kept, {len(delete)} would be pruned")
+        for backup in sorted(keep + delete, key=os.path.getmtime, reverse=True):
+            action = "keep " if backup in keep else "prune"
+            print(f"  {action}  {os.path.basename(backup)}")
+
+
+def backup_prune(args: argparse.Namespace, config: ConfigManager) -> None:
+    """Apply the retention settings to the existing backups without making a new one."""
+    backup_dir = config.paths["backup_dir"]
+    policy = config.settings["backup"]
+    targets = {"items": [_items_path(config)], "money": [_money_path(config)]}
+    paths = targets.get(args.target) or targets["items"] + targets["money"]
+    for path in dict.fromkeys(paths):
+        name = os.path.basename(path)
+        _, planned = preview_retention(path, backup_dir, policy)
+        removed = enforce_retention(path, backup_dir, policy)
+        print(f"{name}: removed {len(removed)} backup(s)")
+        for backup in removed:
+            print(f"  removed  {os.path.basename(backup)}")
+        for backup in planned:
+            if backup not in removed:
+                print(f"  could not remove  {os.path.basename(backup)}")
+
+
+def _parse_date_arg(text: str):
+    try:
+        return parse_date(text)
+    except ValueError as exc:
+        raise argparse.ArgumentTypeError(str(exc)) from exc
+
+
+def _add_item_arguments(parser: argparse.ArgumentParser) -> None:
+    # Field defaults live in ITEM_ADD_DEFAULTS so --like can tell given flags from omitted ones.
+    parser.add_argument("product", nargs="?", help="Product name")
+    parser.add_argument("--cost", type=float, help="Cost (default: 0)")
+    parser.add_argument("--date", type=_parse_date_arg, help="Purchase/added date, YYYY-MM-DD (default: now)")
+    parser.add_argument("--description")
+    parser.add_argument("--location")
+    parser.add_argument("--reference", help="URL or other reference")
+    for name in ["urgency", "value", "want", "price-comp", "effect"]:
+        key = name.replace("-", "_")
+        parser.add_argument(
+            f"--{name}",
+            type=int,
+            choices=range(1, 6),
+            metavar="1-5",
+            help=f"(default: ui.item_defaults.{key}, initially {DEFAULT_ITEM_RATINGS[key]})",
+        )
+    parser.add_argument("--justification")
+    parser.add_argument("--recurrence", choices=RECURRENCE_CHOICES, help="(default: none)")
+    parser.add_argument("--expected-uses", type=int, help="Expected number of uses, for cost per use")
+    parser.add_argument("--target-date", type=_parse_date_arg, help="Buy-by/watch-until date, YYYY-MM-DD")
+    parser.add_argument("--depends-on", metavar="ITEM_ID", help="Item that should be bought first")
+    parser.add_argument(
+        "--effort", type=int, choices=range(1, 6), metavar="1-5", help="Effort to buy or set up (default: not rated)"
+    )
+    parser.add_argument("--image-path", metavar="PATH", help="Picture of the item; only the path is stored")
+    _add_like_argument(parser, "items")
+
+
+def _add_like_argument(parser: argparse.ArgumentParser, group: str) -> None:
+    parser.add_argument(
+        "--like",
+        type=int,
+        metavar="N",
+        help=f"Start from the N-th most recent entry in '{group} history' (1 = last); given flags still win",
+    )
+
+
+def _add_list_arguments(parser: argparse.ArgumentParser) -> None:
+    parser.add_argument(
+        "--format",
+        choices=["table", "plain", "json"],
+        default="table",
+        help="Aligned table, pipe-delimited lines, or JSON (default: %(default)s)",
+    )
+
+
+def _add_import_arguments(parser: argparse.ArgumentParser) -> None:
+    parser.add_argument("path", help="CSV file to import")
+    parser.add_argument("--check", action="store_true", help="Only validate the file and print the report")
+    parser.add_argument("--replace", action="store_true", help="Replace existing records instead of merging by id")
+    parser.add_argument("--skip-invalid", action="store_true", help="Import the valid rows even if others fail")
+    parser.add_argument(
+        "--partial",
+        action="store_true",
+        help="Accept a file with only some columns (e.g. from export --columns): matching ids keep their other "
+        "fields, new rows get defaults and need a date",
+    )
+    parser.add_argument(
+        "--on-conflict",
+        # prompt needs the app's side-by-side view.
+        choices=[strategy for strategy in CONFLICT_STRATEGIES if strategy != "prompt"],
+        default="keep-theirs",
+        help="When an imported id exists with different values: take the import's version, keep yours, or keep "
+        "the one with the later date (default: %(default)s)",
+    )
+
+
+def _add_export_arguments(parser: argparse.ArgumentParser) -> None:
+    parser.add_argument("path", help="Destination file ('-' streams JSON Lines to stdout)")
+    parser.add_argument(
+        "--format",
+        choices=["csv", "jsonl"],
+        default="csv",
+        help="csv, or jsonl for one JSON record per line (default: %(default)s)",
+    )
+    parser.add_argument(
+        "--columns",
+        metavar="LIST",
+        help="Comma-separated fields to include, e.g. 'product,cost,urgency' (id is always included)",
+    )
+
+
+def forecast_cashflow(args: argparse.Namespace, config: ConfigManager) -> None:
+    symbol = config.settings["ui"].get("currency_symbol", "$")
+    projection = cashflow_projection(
+        _read_items(config),
+        read_money(_money_path(config)),
+        args.months,
+        config.weights.get("as_of"),
+        config.fiscal_month_start_day(),
+    )
+    print(f"{'Month':<8} {'Income':>12} {'Expenses':>12} {'Net':>12} {'Balance':>12}")
+    for month in projection:
+        amounts = [f"{symbol}{value:,.2f}" for value in (month.income, month.expenses, month.net, month.balance)]
+        print(f"{month.month:<8} " + " ".join(f"{amount:>12}" for amount in amounts))
+    print("\nRecurring items count as expenses; future-dated money entries are included as scheduled.")
+
+
+def weights_sensitivity(args: argparse.Namespace, config: ConfigManager) -> None:
+    items = _read_items(config)
+    if len(items) < 2:
+        print("Need at least two items to compare rankings.")
+        return
+    results = weight_sensitivity(items, config.weights, args.delta)
+    pct = f"{args.delta * 100:g}%"
+    rows = [
+        [
+            result.key,
+            f"{result.weight:g}",
+            f"{result.rho_down:.3f}",
+            f"{result.rho_up:.3f}",
+            f"{result.moved_down}/{result.moved_up}",
+        ]
+        for result in results
+    ]
+    print(render_table(["Weight", "Value", f"Rho -{pct}", f"Rho +{pct}", "Items moved -/+"], rows, numeric=(1, 2, 3, 4)))
+    print(f"\nRho is the rank correlation with the current order across {len(items)} items (1.000 = unchanged).")
+    disabled = [key for key, value in config.weights.get("weights", {}).items() if float(value) <= 0]
+    if disabled:
+        print(f"Skipped zero weights: {', '.join(disabled)}")
+
+
+def parse_args(argv: Optional[List[str]] = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Finance Planner command line")
+    parser.add_argument(
+        "--as-of",
+        type=_parse_date_arg,
+        help="Score as if today were this date (YYYY-MM-DD); overrides as_of_date in weights.txt",
+    )
+    parser.add_argument(
+        "-v",
+        "--verbose",
+        action="count",
+        default=0,
+        help="Print more detail: -v shows score breakdowns, -vv also echoes stored records as JSON",
+    )
+    parser.add_argument(
+        "--read-lock",
+        choices=READ_LOCK_MODES,
+        help="How read-only commands lock the data files: wait for the lock, try briefly and then read without it, "
+        "or skip it (default: storage.cli_read_lock, normally wait)",
+    )
+    parser.add_argument(
+        "-o",
+        "--output",
+        metavar="PATH",
+        help="Write the command's output to this file instead of stdout (without color); parent folders are created",
+    )
+    parser.add_argument(
+        "--book",
+        metavar="NAME",
+        help=f"Work in this book for this run only (default: books.current, normally {DEFAULT_BOOK})",
+    )
+    parser.add_argument(
+        "--theme",
+        metavar="NAME",
+        help="Color output with this theme for this run only, e.g. high-contrast (default: themes.default)",
+    )
+    groups = parser.add_subparsers(dest="group", required=True)
+
+    items_parser = groups.add_parser("items", help="Work with purchase items")
+    items_cmds = items_parser.add_subparsers(dest="command", required=True)
+
+    add_parser = items_cmds.add_parser("add", help="Add an item and score it")
+    _add_item_arguments(add_parser)
+    add_parser.add_argument("--force", action="store_true", help="Allow a cost above cost_warn_threshold")
+    add_parser.set_defaults(handler=items_add)
+
+    preview_parser = items_cmds.add_parser(
+        "score-preview", help="Show what an item would score with the current weights, without adding it"
+    )
+    _add_item_arguments(preview_parser)
+    preview_parser.set_defaults(handler=items_score_preview)
+
+    items_history_parser = items_cmds.add_parser("history", help="List recent 'items add' values for --like")
+    items_history_parser.set_defaults(handler=add_history, history_command="items add")
+
+    note_parser = items_cmds.add_parser("note", help="Append a timestamped note to an item's history")
+    note_parser.add_argument("id", help="Item id or unique prefix")
+    note_parser.add_argument("text", help="Note text")
+    note_parser.set_defaults(handler=items_note)
+
+    items_list_parser = items_cmds.add_parser("list", help="List items, highest overall score first")
+    _add_list_arguments(items_list_parser)
+    _add_item_selection_arguments(items_list_parser)
+    items_list_parser.set_defaults(handler=items_list)
+
+    show_parser = items_cmds.add_parser("show", help="Print one item's details")
+    show_parser.add_argument("id", help="Item id or unique prefix")
+    show_parser.set_defaults(handler=items_show)
+
+    depend_parser = items_cmds.add_parser("depend", help="Mark an item as needing another item bought first")
+    depend_parser.add_argument("id", help="Item id or unique prefix")
+    depend_parser.add_argument("prerequisite_id", help="Id (or unique prefix) of the item to buy first")
+    depend_parser.set_defaults(handler=items_depend)
+
+    undepend_parser = items_cmds.add_parser("undepend", help="Clear an item's prerequisite")
+    undepend_parser.add_argument("id", help="Item id or unique prefix")
+    undepend_parser.set_defaults(handler=items_undepend)
+
+    decide_parser = items_cmds.add_parser("decide", help="Record whether you will buy, skip or defer an item")
+    decide_parser.add_argument("id", help="Item id or unique prefix")
+    decide_parser.add_argument("decision", choices=list(DECISIONS) + ["none"], help="none clears the decision")
+    decide_parser.set_defaults(handler=items_decide)
+
+    plan_parser = items_cmds.add_parser(
+        "plan", help="List items in buying order: prerequisites first, then by overall score"
+    )
+    plan_parser.set_defaults(handler=items_plan)
+
+    score_parser = items_cmds.add_parser("score", help="Score items that have no overall score yet")
+    score_parser.add_argument("--all", action="store_true", help="Rescore every item with the current weights")
+    score_parser.set_defaults(handler=items_score)
+
+    verify_parser = items_cmds.add_parser(
+        "verify-scores", help="Check stored overall scores against the current weights and item fields"
+    )
+    verify_parser.add_argument(
+        "--tolerance", type=float, default=0.01, help="Largest difference to accept (default: %(default)s)"
+    )
+    verify_parser.add_argument("--fix", action="store_true", help="Back up the items file, then store the computed scores")
+    verify_parser.set_defaults(handler=items_verify_scores)
+
+    top_parser = items_cmds.add_parser(
+        "top", help="Show the highest-scoring items with what lifts and drags each score"
+    )
+    top_parser.add_argument("n", nargs="?", type=int, default=5, help="How many items to show (default: %(default)s)")
+    top_parser.set_defaults(handler=items_top)
+
+    suggest_parser = items_cmds.add_parser(
+        "suggest", help="Pick an unbought item you can afford at random, higher scores being likelier"
+    )
+    suggest_parser.add_argument(
+        "--budget", type=float, help="Spend at most this much (default: the balance of entries to date)"
+    )
+    suggest_parser.add_argument("--seed", type=int, help="Seed the draw so the same data gives the same pick")
+    suggest_parser.set_defaults(handler=items_suggest)
+
+    search_parser = items_cmds.add_parser("search", help="Rank items by how closely they match a query")
+    search_parser.add_argument("query", help="Text to look for in product and description")
+    search_parser.add_argument("--limit", type=int, default=10, help="Maximum matches to show (default: %(default)s)")
+    search_parser.set_defaults(handler=items_search)
+
+    dupes_parser = items_cmds.add_parser(
+        "merge-duplicates", help="Find items with the same product name and merge each group into its oldest item"
+    )
+    dupes_parser.add_argument(
+        "--cost-tolerance",
+        type=float,
+        metavar="PERCENT",
+        help="Only group items whose costs are within this percentage of each other",
+    )
+    dupes_parser.add_argument("--yes", action="store_true", help="Merge every group without asking")
+    dupes_parser.set_defaults(handler=items_merge_duplicates)
+
+    bulk_parser = items_cmds.add_parser("bulk-edit", help="Set fields on every item that matches a filter")
+    bulk_parser.add_argument(
+        "--filter",
+        required=True,
+        help="Filter expression, e.g. 'cost>100 and recurrence=monthly'; a plain word matches the product name",
+    )
+    bulk_parser.add_argument(
+        "--set",
+        action="append",
+        required=True,
+        type=_parse_assignment,
+        metavar="FIELD=VALUE",
+        help="Field to change, e.g. location=Online (repeatable)",
+    )
+    bulk_parser.add_argument("--force", action="store_true", help="Allow costs above cost_warn_threshold")
+    bulk_parser.set_defaults(handler=items_bulk_edit)
+
+    items_import_parser = items_cmds.add_parser("import", help="Validate and import items from CSV")
+    _add_import_arguments(items_import_parser)
+    items_import_parser.set_defaults(handler=items_import)
+
+    items_export_parser = items_cmds.add_parser("export", help="Export items to CSV or JSON Lines")
+    _add_export_arguments(items_export_parser)
+    _add_item_selection_arguments(items_export_parser)
+    items_export_parser.set_defaults(handler=items_export)
+
+    items_count_parser = items_cmds.add_parser("count", help="Print how many items match, and nothing else")
+    _add_item_selection_arguments(items_count_parser)
+    items_count_parser.set_defaults(handler=items_count)
+
+    money_parser = groups.add_parser("money", help="Work with income and expense entries")
+    money_cmds = money_parser.add_subparsers(dest="command", required=True)
+
+    money_add_parser = money_cmds.add_parser("add", help="Add an income or expense entry")
+    money_add_parser.add_argument("source", nargs="?", help="Source (income) or destination (expense)")
+    money_add_parser.add_argument("--amount", type=float, help="Amount, always positive (default: 0)")
+    money_add_parser.add_argument("--type", dest="entry_type", choices=["income", "expense"], help="(default: expense)")
+    money_add_parser.add_argument("--date", type=_parse_date_arg, help="Entry date, YYYY-MM-DD (default: now)")
+    money_add_parser.add_argument("--notes")
+    money_add_parser.add_argument("--category")
+    money_add_parser.add_argument("--link", metavar="ITEM_ID", help="Item this entry pays for")
+    money_add_parser.add_argument(
+        "--template", metavar="NAME", help="Start from a saved template (ui.money_templates); flags override it"
+    )
+    _add_like_argument(money_add_parser, "money")
+    money_add_parser.set_defaults(handler=money_add)
+
+    split_parser = money_cmds.add_parser(
+        "split", help="Split an entry into parts (e.g. by category) whose amounts add up to the original"
+    )
+    split_parser.add_argument("id", help="Money entry id or unique prefix")
+    split_parser.add_argument(
+        "parts",
+        nargs="+",
+        type=_split_part,
+        metavar="AMOUNT[:CATEGORY]",
+        help="Amount of each part, optionally with its category, e.g. 50:Groceries 30.50:Household",
+    )
+    split_parser.set_defaults(handler=money_split)
+
+    money_history_parser = money_cmds.add_parser("history", help="List recent 'money add' values for --like")
+    money_history_parser.set_defaults(handler=add_history, history_command="money add")
+
+    money_templates_parser = money_cmds.add_parser("templates", help="List saved templates for 'money add --template'")
+    money_templates_parser.set_defaults(handler=money_templates)
+
+    money_list_parser = money_cmds.add_parser("list", help="List money entries, newest first")
+    _add_list_arguments(money_list_parser)
+    money_list_parser.add_argument(
+        "--uncleared", action="store_true", help="Only entries not yet marked cleared against a statement"
+    )
+    money_list_parser.add_argument(
+        "--sort",
+        choices=["date", *SORT_ALIASES],
+        default="date",
+        help="Newest first by entry date, by when entries were added, or by when they were last edited "
+        "(default: %(default)s)",
+    )
+    money_list_parser.set_defaults(handler=money_list)
+
+    money_search_parser = money_cmds.add_parser(
+        "search", help="Rank money entries by how closely source/destination or notes match a query"
+    )
+    money_search_parser.add_argument("query", help="Text to look for in source/destination and notes")
+    money_search_parser.add_argument(
+        "--limit", type=int, default=10, help="Maximum matches to show (default: %(default)s)"
+    )
+    _add_money_selection_arguments(money_search_parser)
+    money_search_parser.set_defaults(handler=money_search)
+
+    link_parser = money_cmds.add_parser("link", help="Link a money entry to an existing item")
+    link_parser.add_argument("money_id", help="Money entry id or unique prefix")
+    link_parser.add_argument("item_id", help="Item id or unique prefix to link")
+    link_parser.set_defaults(handler=money_link)
+
+    unlink_parser = money_cmds.add_parser("unlink", help="Clear a money entry's linked item")
+    unlink_parser.add_argument("money_id", help="Money entry id or unique prefix")
+    unlink_parser.set_defaults(handler=money_unlink)
+
+    clear_parser = money_cmds.add_parser("clear", help="Mark a money entry as cleared (seen on a bank statement)")
+    clear_parser.add_argument("money_id", help="Money entry id or unique prefix")
+    clear_parser.set_defaults(handler=money_clear)
+
+    unclear_parser = money_cmds.add_parser("unclear", help="Mark a money entry as not cleared")
+    unclear_parser.add_argument("money_id", help="Money entry id or unique prefix")
+    unclear_parser.set_defaults(handler=money_unclear)
+
+    archive_parser = money_cmds.add_parser(
+        "archive", help="Move old money entries into a dated archive CSV next to the money file"
+    )
+    archive_parser.add_argument(
+        "--before",
+        type=_parse_date_arg,
+        help="Archive entries dated before this day, YYYY-MM-DD (default: storage.archive_after_days ago)",
+    )
+    archive_parser.add_argument(
+        "--opening-balance",
+        action="store_true",
+        help="Replace the archived entries with one entry carrying their net balance",
+    )
+    archive_parser.add_argument("--dry-run", action="store_true", help="Only report what would be archived")
+    archive_parser.set_defaults(handler=money_archive)
+
+    unarchive_parser = money_cmds.add_parser(
+        "unarchive", help="Merge an archive CSV back into the money file and drop its opening-balance entry"
+    )
+    unarchive_parser.add_argument("archive", help="Path to a money_archive_<date>.csv file")
+    unarchive_parser.set_defaults(handler=money_unarchive)
+
+    balance_parser = money_cmds.add_parser("balance", help="Print the balance of all entries up to a date")
+    balance_parser.add_argument(
+        "--as-of",
+        dest="balance_as_of",
+        type=_parse_date_arg,
+        help="Include entries up to the end of this day, YYYY-MM-DD (default: now)",
+    )
+    balance_parser.add_argument(
+        "--months", type=int, metavar="N", help="Also show net flow and closing balance for the last N months"
+    )
+    balance_parser.set_defaults(handler=money_balance)
+
+    money_report_parser = money_cmds.add_parser("report", help="Summarize income and expenses")
+    money_report_parser.add_argument(
+        "--by-category", action="store_true", help="Sum spending and income per category"
+    )
+    money_report_parser.set_defaults(handler=money_report)
+
+    money_import_parser = money_cmds.add_parser("import", help="Validate and import money entries from CSV")
+    _add_import_arguments(money_import_parser)
+    money_import_parser.set_defaults(handler=money_import)
+
+    money_export_parser = money_cmds.add_parser("export", help="Export money entries to CSV or JSON Lines")
+    _add_export_arguments(money_export_parser)
+    _add_money_selection_arguments(money_export_parser)
+    money_export_parser.add_argument(
+        "--sort", choices=MoneyRecord.headers(), metavar="FIELD", help="Order by this money field"
+    )
+    money_export_parser.add_argument("--desc", action="store_true", help="With --sort, order from highest to lowest")
+    money_export_parser.set_defaults(handler=money_export)
+
+    money_count_parser = money_cmds.add_parser("count", help="Print how many money entries match, and nothing else")
+    _add_money_selection_arguments(money_count_parser)
+    money_count_parser.set_defaults(handler=money_count)
+
+    report_parser = groups.add_parser(
+        "report", help="Print a scored summary of items and money, or a log of purchased items"
+    )
+    report_parser.add_argument(
+        "kind",
+        nargs="?",
+        choices=["summary", "purchases", "decisions"],
+        default="summary",
+        help="summary (default); purchases: items with linked expenses, grouped by month; decisions: items "
+        "and cost per buy/skip/defer decision, and decisions that go against the score",
+    )
+    report_parser.add_argument(
+        "--format",
+        choices=["md", "csv"],
+        default="md",
+        help="Output format; csv is for purchases and decisions (default: %(default)s)",
+    )
+    report_parser.set_defaults(handler=report)
+
+    forecast_parser = groups.add_parser("forecast", help="Project future balances")
+    forecast_cmds = forecast_parser.add_subparsers(dest="command", required=True)
+
+    cashflow_parser = forecast_cmds.add_parser(
+        "cashflow", help="Monthly cash-flow projection from recurring items and scheduled money entries"
+    )
+    cashflow_parser.add_argument("--months", type=int, default=12, help="Months to project (default: %(default)s)")
+    cashflow_parser.set_defaults(handler=forecast_cashflow)
+
+    book_parser = groups.add_parser("book", help="Keep separate items and money in named books")
+    book_cmds = book_parser.add_subparsers(dest="command", required=True)
+    book_list_parser = book_cmds.add_parser("list", help="List the books and where their data lives")
+    book_list_parser.set_defaults(handler=book_list)
+    book_create_parser = book_cmds.add_parser("create", help="Create an empty book")
+    book_create_parser.add_argument("name", help="Letters, digits, '-' and '_'")
+    book_create_parser.set_defaults(handler=book_create)
+    book_use_parser = book_cmds.add_parser("use", help="Open this book by default in the app and the CLI")
+    book_use_parser.add_argument("name")
+    book_use_parser.set_defaults(handler=book_use)
+
+    config_parser = groups.add_parser("config", help="Check the config files")
+    config_cmds = config_parser.add_subparsers(dest="command", required=True)
+    validate_parser = config_cmds.add_parser(
+        "validate", help="Check settings.json, weights.txt and themes.json and report every problem found"
+    )
+    validate_parser.add_argument(
+        "--dir", metavar="PATH", help="Config folder to check, e.g. a repository's config/ (default: the app's)"
+    )
+    validate_parser.set_defaults(handler=config_validate)
+
+    doctor_parser = groups.add_parser("doctor", help="Check config and data files without changing them")
+    doctor_parser.add_argument(
+        "--safe", action="store_true", help="Do not create missing config or data files while checking"
+    )
+    doctor_parser.set_defaults(handler=doctor)
+
+    schema_parser = groups.add_parser(
+        "schema", help="Check the CSV header rows for missing, extra or reordered columns without reading the rows"
+    )
+    schema_parser.add_argument(
+        "--target", choices=["items", "money", "all"], default="all", help="Which file to check (default: all)"
+    )
+    schema_parser.set_defaults(handler=schema_check)
+
+    serve_parser = groups.add_parser("serve", help="Serve items, money and a summary as read-only JSON over HTTP")
+    serve_parser.add_argument("--port", type=int, default=8765, help="Port to listen on (default: %(default)s)")
+    serve_parser.add_argument(
+        "--host",
+        default="127.0.0.1",
+        help="Address to bind (default: %(default)s, this machine only); the data has no authentication",
+    )
+    serve_parser.set_defaults(handler=serve_json)
+
+    weights_parser = groups.add_parser("weights", help="Analyze scoring weights")
+    weights_cmds = weights_parser.add_subparsers(dest="command", required=True)
+
+    sensitivity_parser = weights_cmds.add_parser(
+        "sensitivity", help="Show how much the item ranking changes when each weight is nudged"
+    )
+    sensitivity_parser.add_argument(
+        "--delta",
+        type=float,
+        default=0.1,
+        help="Fraction to nudge each weight down and up by (default: %(default)s)",
+    )
+    sensitivity_parser.set_defaults(handler=weights_sensitivity)
+
+    relocate_parser = groups.add_parser(
+        "relocate", help="Bring the data over after items_csv/money_csv/data_json were pointed somewhere new"
+    )
+    relocate_mode = relocate_parser.add_mutually_exclusive_group()
+    relocate_mode.add_argument("--move", action="store_true", help="Move the old files instead of copying them")
+    relocate_mode.add_argument(
+        "--start-empty", action="store_true", help="Keep the new, empty files and stop warning about the old ones"
+    )
+    relocate_parser.set_defaults(handler=relocate)
+
+    backup_parser = groups.add_parser("backup", help="Inspect and manage backups")
+    backup_cmds = backup_parser.add_subparsers(dest="command", required=True)
+
+    simulate_parser = backup_cmds.add_parser(
+        "simulate", help="Show which existing backups the current retention settings would keep or prune"
+    )
+    simulate_parser.set_defaults(handler=backup_simulate)
+
+    prune_parser = backup_cmds.add_parser(
+        "prune", help="Delete the existing backups the current retention settings would prune, without a new backup"
+    )
+    prune_parser.add_argument(
+        "--target", choices=["items", "money", "all"], default="all", help="Which file's backups (default: all)"
+    )
+    prune_parser.set_defaults(handler=backup_prune)
+
+    return parser.parse_args(argv)
+
+
+@contextlib.contextmanager
+def _redirected_output(path: Optional[str]):
+    if not path:
+        yield
+        return
+    parent = os.path.dirname(os.path.abspath(path))
+    os.makedirs(parent, exist_ok=True)
+    # A file is not a terminal, so use_color() turns color off for everything printed inside.
+    with open(path, "w", encoding="utf-8", newline="") as stream, contextlib.redirect_stdout(stream):
+        yield
+    print(f"Wrote output to {path}", file=sys.stderr)
+
+
+# Commands that never write the data files, and so may read them without the lock (see --read-lock).
+READ_ONLY_HANDLERS = {
+    add_history,
+    backup_prune,
+    backup_simulate,
+    doctor,
+    forecast_cashflow,
+    items_count,
+    items_export,
+    items_list,
+    items_plan,
+    items_score_preview,
+    items_search,
+    items_show,
+    items_suggest,
+    items_top,
+    money_balance,
+    money_count,
+    money_export,
+    money_list,
+    money_report,
+    money_search,
+    money_templates,
+    report,
+    schema_check,
+    serve_json,
+    weights_sensitivity,
+}
+
+
+def main(argv: Optional[List[str]] = None) -> int:
+    args = parse_args(argv)
+    if args.handler is config_validate:
+        # Loading would stop at a broken settings.json, or quietly repair what validate should report.
+        return _run_handler(args, None)
+    try:
+        config = load_config(create_missing=not getattr(args, "safe", False), book=args.book)
+    except ValueError as exc:
+        print(f"Error: {exc}", file=sys.stderr)
+        return 1
+    if config.moved_data_paths and args.handler is not relocate:
+        # Running anyway would start writing the new, empty location and leave the old data behind for good.
+        for key, old, new in config.moved_data_paths:
+            print(f"Error: {key} now points to {new}, but the data is still in {old}", file=sys.stderr)
+        print(
+            "Run 'relocate' to copy it there (--move to move it), "
+            "or 'relocate --start-empty' to use the new location as is.",
+            file=sys.stderr,
+        )
+        return 1
+    if args.handler in READ_ONLY_HANDLERS:
+        set_read_lock(args.read_lock or config.settings["storage"]["cli_read_lock"])
+    if args.as_of:
+        config.weights["as_of"] = args.as_of
+    if args.theme:
+        try:
+            config.override_theme(args.theme)
+        except ValueError as exc:
+            print(f"Error: {exc}", file=sys.stderr)
+            return 1
+    return _run_handler(args, config)
+
+
+def _run_handler(args: argparse.Namespace, config: Optional[ConfigManager]) -> int:
+    try:
+        with _redirected_output(args.output):
+            args.handler(args, config)
+    except (LookupError, ValueError, OSError) as exc:
+        print(f"Error: {exc}", file=sys.stderr)
+        return 1
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main(sys.argv[1:]))
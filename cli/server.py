@@ -0,0 +1,88 @@
+"""Read-only JSON over HTTP for dashboards and scripts on the same machine.
+
+Every request re-reads the data files (under their usual locks), so responses always match what
+the GUI last saved.
+"""
+
+import json
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import Callable, Dict, List, Optional, Tuple
+
+from core.csv_storage import read_items, read_money
+from core.errors import DataFileLockedError, DataFileParseError
+from core.models import ItemRecord
+from scoring.scoring import overall_scores
+
+
+def summary(items: List, money: List) -> Dict[str, object]:
+    scored = [item.overall_score for item in items if item.overall_score is not None]
+    flows = [entry.signed_amount() for entry in money]
+    return {
+        "items": {
+            "count": len(items),
+            "total_cost": round(sum(item.cost for item in items), 2),
+            "scored": len(scored),
+            "average_score": round(sum(scored) / len(scored), 2) if scored else None,
+        },
+        "money": {
+            "count": len(money),
+            "income": round(sum(flow for flow in flows if flow > 0), 2),
+            "expenses": round(-sum(flow for flow in flows if flow < 0), 2),
+            "balance": round(sum(flows), 2),
+        },
+    }
+
+
+def make_handler(items_path: str, money_path: str, weights: Optional[Dict] = None) -> type:
+    """Request handler for the data files; with weights, item scores are recomputed on every read."""
+
+    def items() -> List[ItemRecord]:
+        records = read_items(items_path)
+        if weights is not None:
+            for item, score in zip(records, overall_scores(records, weights)):
+                item.overall_score = score
+        return records
+
+    routes: Dict[str, Callable[[], object]] = {
+        "/items": lambda: [item.to_row() for item in items()],
+        "/money": lambda: [entry.to_row() for entry in read_money(money_path)],
+        "/summary": lambda: summary(items(), read_money(money_path)),
+    }
+
+    class Handler(BaseHTTPRequestHandler):
+        server_version = "FinancePlanner"
+
+        def do_GET(self) -> None:
+            status, payload = self._respond()
+            body = json.dumps(payload, ensure_ascii=False, indent=2).encode("utf-8")
+            self.send_response(status)
+            self.send_header("Content-Type", "application/json; charset=utf-8")
+            self.send_header("Content-Length", str(len(body)))
+            self.end_headers()
+            self.wfile.write(body)
+
+        def _respond(self) -> Tuple[int, object]:
+            path = self.path.split("?", 1)[0].rstrip("/") or "/"
+            if path == "/":
+                return 200, {"endpoints": sorted(routes)}
+            route = routes.get(path)
+            if route is None:
+                return 404, {"error": f"unknown path {path}", "endpoints": sorted(routes)}
+            try:
+                return 200, route()
+            except DataFileLockedError as exc:
+                return 503, {"error": str(exc)}
+            except DataFileParseError as exc:
+                return 500, {"error": str(exc)}
+
+        def log_message(self, format: str, *args) -> None:
+            # Keep stdout quiet; the console only reports startup and errors.
+            pass
+
+    return Handler
+
+
+def serve(
+    items_path: str, money_path: str, host: str, port: int, weights: Optional[Dict] = None
+) -> ThreadingHTTPServer:
+    return ThreadingHTTPServer((host, port), make_handler(items_path, money_path, weights))
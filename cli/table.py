@@ -0,0 +1,47 @@
+import os
+import sys
+from typing import Callable, List, Optional, Sequence
+
+from core.config_manager import score_color as theme_score_color
+
+_RESET = "\033[0m"
+
+
+def use_color(stream=None) -> bool:
+    stream = stream or sys.stdout
+    return hasattr(stream, "isatty") and stream.isatty() and "NO_COLOR" not in os.environ
+
+
+def score_color(score: float, theme: Optional[dict] = None) -> str:
+    """ANSI 24-bit color for an overall score on the theme's bad-to-good ramp (same as the GUI grid)."""
+    hex_color = theme_score_color(theme or {}, score).lstrip("#")
+    red, green, blue = (int(hex_color[i : i + 2], 16) for i in (0, 2, 4))
+    return f"\033[38;2;{red};{green};{blue}m"
+
+
+def render_table(
+    headers: Sequence[str],
+    rows: Sequence[Sequence[str]],
+    numeric: Sequence[int] = (),
+    colorize: Optional[Callable[[int, str], Optional[str]]] = None,
+) -> str:
+    """Lay rows out in aligned columns; `numeric` columns are right-aligned.
+
+    `colorize(column, text)` may return an ANSI color for a cell. Colors are applied after
+    padding so escape codes do not disturb the alignment.
+    """
+    widths = [len(h) for h in headers]
+    for row in rows:
+        for idx, cell in enumerate(row):
+            widths[idx] = max(widths[idx], len(cell))
+
+    def fmt(row: Sequence[str], paint: bool) -> str:
+        cells: List[str] = []
+        for idx, cell in enumerate(row):
+            padded = cell.rjust(widths[idx]) if idx in numeric else cell.ljust(widths[idx])
+            color = colorize(idx, cell) if paint and colorize else None
+            cells.append(f"{color}{padded}{_RESET}" if color else padded)
+        return "  ".join(cells).rstrip()
+
+    rule = "  ".join("-" * width for width in widths)
+    return "\n".join([fmt(headers, False), rule] + [fmt(row, True) for row in rows])
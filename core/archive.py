@@ -0,0 +1,58 @@
+import os
+from datetime import datetime
+from typing import List, Optional, Tuple
+
+from core.models import MoneyRecord
+
+OPENING_BALANCE_PREFIX = "opening-balance-"
+
+
+def archive_path(money_path: str, cutoff: datetime) -> str:
+    """Where entries dated before cutoff are archived: money_archive_<YYYYMMDD>.csv next to the money file."""
+    folder = os.path.dirname(os.path.abspath(money_path))
+    return os.path.join(folder, f"money_archive_{cutoff:%Y%m%d}.csv")
+
+
+def opening_balance_id(archive: str) -> str:
+    stem = os.path.splitext(os.path.basename(archive))[0]
+    return OPENING_BALANCE_PREFIX + stem.rsplit("_", 1)[-1]
+
+
+def split_for_archive(
+    entries: List[MoneyRecord], cutoff: datetime, archive: str, opening_balance: bool = False
+) -> Tuple[List[MoneyRecord], List[MoneyRecord]]:
+    """Split entries into (kept, archived) by day; archived entries are those dated before cutoff.
+
+    With opening_balance, the kept list starts with one entry dated on the cutoff that carries the
+    archived entries' net balance, so running balances and totals stay the same.
+    """
+    day = cutoff.date()
+    archived = [entry for entry in entries if entry.date.date() < day]
+    kept = [entry for entry in entries if entry.date.date() >= day]
+    if opening_balance and archived:
+        kept.insert(0, _opening_entry(archived, cutoff, archive))
+    return kept, archived
+
+
+def _opening_entry(archived: List[MoneyRecord], cutoff: datetime, archive: str) -> MoneyRecord:
+    balance = sum(entry.signed_amount() for entry in archived)
+    return MoneyRecord(
+        id=opening_balance_id(archive),
+        date=cutoff,
+        entry_type="income" if balance >= 0 else "expense",
+        source_or_destination="Opening balance",
+        amount=round(abs(balance), 2),
+        notes=f"Net of {len(archived)} entries archived in {os.path.basename(archive)}",
+    )
+
+
+def restore_archived(
+    entries: List[MoneyRecord], archived: List[MoneyRecord], archive: str
+) -> Tuple[List[MoneyRecord], Optional[MoneyRecord]]:
+    """Merge archived entries back and drop the opening-balance entry that stood in for them."""
+    stand_in = opening_balance_id(archive)
+    removed = next((entry for entry in entries if entry.id == stand_in), None)
+    restored = {entry.id: entry for entry in entries if entry.id != stand_in}
+    for entry in archived:
+        restored.setdefault(entry.id, entry)
+    return list(restored.values()), removed
@@ -1,12 +1,17 @@
 import os
 import shutil
 from datetime import datetime
-from typing import Dict, List
+from typing import Dict, List, Optional, Tuple
 
+from core.errors import BackupSourceMissingError
 
-def create_backup(source_path: str, backup_dir: str, policy: Dict[str, int]) -> str:
+
+def create_backup(source_path: str, backup_dir: str, policy: Dict[str, int]) -> Optional[str]:
+    """Copy source_path into backup_dir; returns None without writing when backups are disabled."""
+    if not policy.get("enabled", True):
+        return None
     if not os.path.exists(source_path):
-        raise FileNotFoundError(f"Cannot back up missing file: {source_path}")
+        raise BackupSourceMissingError(f"Cannot back up missing file: {source_path}")
     os.makedirs(backup_dir, exist_ok=True)
     timestamp = datetime.now().strftime("%Y%m%d%H%M%S")
     base = os.path.basename(source_path)
@@ -17,32 +22,99 @@ def create_backup(source_path: str, backup_dir: str, policy: Dict[str, int]) ->
     return backup_path
 
 
-def enforce_retention(filename: str, backup_dir: str, policy: Dict[str, int]) -> None:
+def startup_backup(paths: List[str], backup_dir: str, policy: Dict[str, int]) -> List[str]:
+    """Back up each file once per day when `on_startup` is set; returns the backups created."""
+    if not policy.get("on_startup", False) or not policy.get("enabled", True):
+        return []
+    today = datetime.now().date()
+    created = []
+    for path in paths:
+        if not os.path.exists(path):
+            continue
+        # Frequent restarts should not pile up copies; any backup from today already counts.
+        if any(datetime.fromtimestamp(os.path.getmtime(b)).date() == today for b in list_backups(path, backup_dir)):
+            continue
+        backup_path = create_backup(path, backup_dir, policy)
+        if backup_path:
+            created.append(backup_path)
+    return created
+
+
+def enforce_retention(filename: str, backup_dir: str, policy: Dict[str, int]) -> List[str]:
+    _, to_delete = preview_retention(filename, backup_dir, policy)
+    removed = []
+    for path in to_delete:
+        try:
+            os.remove(path)
+            removed.append(path)
+        except OSError:
+            pass
+    return removed
+
+
+def preview_retention(filename: str, backup_dir: str, policy: Dict[str, int]) -> Tuple[List[str], List[str]]:
+    """Split existing backups of filename into (keep, delete) under policy without touching any files.
+
+    Count rules pick the recent and historical backups to keep; `max_total_bytes`, when set, then
+    drops the oldest kept historical backups until their combined size fits.
+    """
     keep_recent = int(policy.get("keep_recent", 3))
     keep_historical = int(policy.get("keep_historical", 3))
-    prefix = os.path.splitext(filename)[0]
+    backups_sorted = sorted(list_backups(filename, backup_dir), key=os.path.getmtime, reverse=True)
+    recent = backups_sorted[:keep_recent]
+    remainder = backups_sorted[keep_recent:]
+    if len(backups_sorted) <= keep_recent + keep_historical:
+        keep = list(backups_sorted)
+    else:
+        historical = _select_historical(remainder, keep_historical)
+        to_keep = set(recent + historical)
+        keep = [path for path in backups_sorted if path in to_keep]
+
+    max_total_bytes = policy.get("max_total_bytes")
+    if max_total_bytes is not None:
+        # Trim the oldest non-recent backups until under the cap; the recent set is never deleted.
+        total = sum(os.path.getsize(path) for path in keep)
+        for path in reversed(list(keep)):
+            if total <= int(max_total_bytes):
+                break
+            if path in recent:
+                continue
+            keep.remove(path)
+            total -= os.path.getsize(path)
+    delete = [path for path in backups_sorted if path not in keep]
+    return keep, delete
 
-    backups = [
+
+def latest_backup(filename: str, backup_dir: str) -> Optional[str]:
+    backups = list_backups(filename, backup_dir)
+    return max(backups, key=os.path.getmtime) if backups else None
+
+
+def restore_backup(backup_path: str, target_path: str) -> Optional[str]:
+    """Copy backup_path over target_path, first moving the current file aside; returns where it went."""
+    set_aside = None
+    if os.path.exists(target_path):
+        set_aside = f"{target_path}.damaged-{datetime.now().strftime('%Y%m%d%H%M%S')}"
+        os.replace(target_path, set_aside)
+    shutil.copy2(backup_path, target_path)
+    return set_aside
+
+
+def list_backups(filename: str, backup_dir: str) -> List[str]:
+    if not os.path.isdir(backup_dir):
+        return []
+    prefix = os.path.splitext(os.path.basename(filename))[0]
+    return [
         os.path.join(backup_dir, f)
         for f in os.listdir(backup_dir)
         if f.startswith(prefix + "_")
     ]
-    if len(backups) <= keep_recent + keep_historical:
-        return
-
-    backups_sorted = sorted(backups, key=os.path.getmtime, reverse=True)
-    recent = backups_sorted[:keep_recent]
-    remainder = backups_sorted[keep_recent:]
 
-    historical = _select_historical(remainder, keep_historical)
-    to_keep = set(recent + historical)
 
-    for path in backups_sorted:
-        if path not in to_keep:
-            try:
-                os.remove(path)
-            except OSError:
-                pass
+def backup_usage(filename: str, backup_dir: str) -> Tuple[int, int]:
+    """Return (count, total size in bytes) of the existing backups of filename."""
+    paths = list_backups(filename, backup_dir)
+    return len(paths), sum(os.path.getsize(path) for path in paths)
 
 
 def _select_historical(paths: List[str], count: int) -> List[str]:
@@ -0,0 +1,233 @@
+"""Checking the config files (settings.json, weights.txt, themes.json) without loading the app.
+
+ConfigManager fills in defaults and repairs what it can so the app always starts; these checks
+only read the files and report every problem they find, so a broken setup can be caught before
+relying on it (or in CI). Nothing is created or written.
+"""
+
+import json
+import os
+import re
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, Optional, Tuple
+
+from core.config_manager import (
+    BOOK_NAME_PATTERN,
+    DEFAULT_ITEM_RATINGS,
+    FALLBACK_DATA_DIR,
+    STORAGE_FORMATS,
+    ConfigManager,
+    money_template_problem,
+)
+from core.csv_storage import READ_LOCK_MODES
+from core.errors import InvalidWeightsError
+
+CONFIG_FILES = ("settings.json", "weights.txt", "themes.json")
+THEME_COLOR_KEYS = (
+    "background",
+    "foreground",
+    "accent",
+    "muted",
+    "score_bad",
+    "score_good",
+    "income",
+    "expense",
+    "negative",
+    "warning",
+)
+TABLE_COLOR_KEYS = ("header_bg", "header_fg", "row_bg", "alt_row_bg")
+_HEX_COLOR = re.compile(r"#([0-9a-fA-F]{3}|[0-9a-fA-F]{6})")
+
+
+@dataclass
+class FileCheck:
+    label: str
+    path: str
+    problems: List[str] = field(default_factory=list)
+    # Worth knowing but harmless, e.g. a file the app will create with defaults.
+    notes: List[str] = field(default_factory=list)
+
+    def ok(self) -> bool:
+        return not self.problems
+
+    def lines(self) -> List[str]:
+        return self.problems + [f"note: {note}" for note in self.notes] or ["ok"]
+
+
+def default_config_dir() -> str:
+    """The folder the app reads its config from, found without creating anything."""
+    preferred = ConfigManager._user_data_root()
+    if preferred and (not os.path.exists(preferred) or os.access(preferred, os.W_OK)):
+        return os.path.join(preferred, "config")
+    return os.path.join(os.path.abspath(FALLBACK_DATA_DIR), "config")
+
+
+def check_config_dir(config_dir: str) -> List[FileCheck]:
+    """One FileCheck per config file in config_dir; settings.json is also checked against the themes."""
+    settings_path, weights_path, themes_path = (os.path.join(config_dir, name) for name in CONFIG_FILES)
+    themes_check, themes = check_themes(themes_path)
+    return [check_settings(settings_path, themes), check_weights(weights_path), themes_check]
+
+
+def _read_json(check: FileCheck) -> Optional[Any]:
+    if not os.path.exists(check.path):
+        check.notes.append("not found; the app creates it with the defaults")
+        return None
+    try:
+        with open(check.path, "r", encoding="utf-8") as f:
+            return json.load(f)
+    except json.JSONDecodeError as exc:
+        check.problems.append(f"not valid JSON: {exc.msg} at line {exc.lineno}, column {exc.colno}")
+    except (OSError, UnicodeDecodeError) as exc:
+        check.problems.append(f"could not be read: {exc}")
+    return None
+
+
+def _whole_number(value: Any, low: int, high: Optional[int] = None) -> bool:
+    if isinstance(value, bool) or not isinstance(value, int):
+        return False
+    return value >= low and (high is None or value <= high)
+
+
+def check_settings(path: str, themes: Optional[Dict[str, Any]] = None) -> FileCheck:
+    """settings.json: its JSON, section types, and the values the app would otherwise replace or skip.
+
+    themes are the themes file's contents, to check that themes.default names one of them.
+    """
+    check = FileCheck("settings", path)
+    settings = _read_json(check)
+    if settings is None:
+        return check
+    if not isinstance(settings, dict):
+        check.problems.append("must be a JSON object of sections")
+        return check
+    sections = {}
+    for name in ("paths", "backup", "storage", "themes", "ui", "books"):
+        section = settings.get(name, {})
+        if isinstance(section, dict):
+            sections[name] = section
+        else:
+            check.problems.append(f"{name} must be an object")
+            sections[name] = {}
+    for key, value in sections["paths"].items():
+        if not isinstance(value, str):
+            check.problems.append(f"paths.{key} must be text (a file or folder path)")
+
+    backup = sections["backup"]
+    for key in ("keep_recent", "keep_historical"):
+        if key in backup and not _whole_number(backup[key], 0):
+            check.problems.append(f"backup.{key} must be a whole number of 0 or more")
+    max_bytes = backup.get("max_total_bytes")
+    if max_bytes is not None and not _whole_number(max_bytes, 1):
+        check.problems.append("backup.max_total_bytes must be a positive whole number, or null for no limit")
+
+    storage = sections["storage"]
+    if "format" in storage and storage["format"] not in STORAGE_FORMATS:
+        check.problems.append(f"storage.format must be one of {', '.join(STORAGE_FORMATS)}")
+    if "cli_read_lock" in storage and storage["cli_read_lock"] not in READ_LOCK_MODES:
+        check.problems.append(f"storage.cli_read_lock must be one of {', '.join(READ_LOCK_MODES)}")
+    timeout = storage.get("lock_timeout_seconds", 0)
+    if isinstance(timeout, bool) or not isinstance(timeout, (int, float)) or timeout < 0:
+        check.problems.append("storage.lock_timeout_seconds must be a number of 0 or more")
+
+    current_book = sections["books"].get("current")
+    if current_book is not None and not (isinstance(current_book, str) and BOOK_NAME_PATTERN.fullmatch(current_book)):
+        check.problems.append("books.current must be a book name (letters, digits, '-' and '_')")
+
+    default_theme = sections["themes"].get("default")
+    known_themes = set(ConfigManager._default_themes()) | set(themes or {})
+    if default_theme is not None and default_theme not in known_themes:
+        check.problems.append(
+            f"themes.default '{default_theme}' is not a theme; choose from {', '.join(sorted(known_themes))}"
+        )
+
+    ui = sections["ui"]
+    if "fiscal_month_start_day" in ui and not _whole_number(ui["fiscal_month_start_day"], 1, 31):
+        check.problems.append("ui.fiscal_month_start_day must be a whole number from 1 to 31")
+    item_defaults = ui.get("item_defaults", {})
+    if not isinstance(item_defaults, dict):
+        check.problems.append("ui.item_defaults must be an object of ratings")
+    else:
+        for key, value in item_defaults.items():
+            if key not in DEFAULT_ITEM_RATINGS:
+                check.problems.append(f"ui.item_defaults.{key} is not a rating; use {', '.join(DEFAULT_ITEM_RATINGS)}")
+            elif not _whole_number(value, 1, 5):
+                check.problems.append(f"ui.item_defaults.{key} must be a whole number from 1 to 5")
+    templates = ui.get("money_templates", {})
+    if not isinstance(templates, dict):
+        check.problems.append("ui.money_templates must be an object of named templates")
+    else:
+        for name, template in templates.items():
+            problem = money_template_problem(template)
+            if problem:
+                check.problems.append(f"ui.money_templates.{name}: {problem}")
+    return check
+
+
+def check_weights(path: str) -> FileCheck:
+    """weights.txt: every line the app would ignore or fall back on, then whether the values make sense."""
+    check = FileCheck("weights", path)
+    if not os.path.exists(path):
+        check.notes.append("not found; the app creates it with the defaults")
+        return check
+    try:
+        with open(path, "r", encoding="utf-8") as f:
+            lines = f.readlines()
+    except (OSError, UnicodeDecodeError) as exc:
+        check.problems.append(f"could not be read: {exc}")
+        return check
+    try:
+        weights, warnings = ConfigManager._parse_weights_lines(lines, ConfigManager._default_weights())
+    except InvalidWeightsError as exc:
+        # Band problems stop the parse, so the other checks have nothing to look at.
+        check.problems.append(str(exc))
+        return check
+    check.problems.extend(warnings)
+
+    values = weights["weights"]
+    for key, value in values.items():
+        if value < 0:
+            check.problems.append(f"weight_{key} is negative ({value:g}); use 0 to turn it off")
+    if not any(value > 0 for value in values.values()):
+        check.problems.append("every weight is 0, so every item would score 0")
+    days = weights["date_scoring"]
+    if days["recent_days"] < 0 or days["mid_days"] < 0:
+        check.problems.append("date_recent_days and date_mid_days cannot be negative")
+    elif days["recent_days"] > days["mid_days"]:
+        check.problems.append(
+            f"date_recent_days ({days['recent_days']}) is more than date_mid_days ({days['mid_days']}), "
+            "so the middle age score is never used"
+        )
+    if days["target_ramp_days"] <= 0:
+        check.problems.append("target_ramp_days must be at least 1")
+    for band in weights["cost_bands"]:
+        if not 1 <= band["score"] <= 5:
+            check.problems.append(f"cost band with maximum {band['max']} scores {band['score']:g}; use 1 to 5")
+    if not 1 <= weights["urgency_override"] <= 5:
+        check.problems.append(f"urgency_override ({weights['urgency_override']}) is not an urgency from 1 to 5")
+    return check
+
+
+def check_themes(path: str) -> Tuple[FileCheck, Optional[Dict[str, Any]]]:
+    """themes.json as (FileCheck, the themes it defines or None); every color must be #rgb or #rrggbb."""
+    check = FileCheck("themes", path)
+    themes = _read_json(check)
+    if themes is None:
+        return check, None
+    if not isinstance(themes, dict):
+        check.problems.append("must be a JSON object of named themes")
+        return check, None
+    for name, theme in themes.items():
+        if not isinstance(theme, dict):
+            check.problems.append(f"{name} must be an object of colors")
+            continue
+        colors = [(key, theme[key]) for key in THEME_COLOR_KEYS if key in theme]
+        table = theme.get("table", {})
+        if isinstance(table, dict):
+            colors += [(f"table.{key}", table[key]) for key in TABLE_COLOR_KEYS if key in table]
+        else:
+            check.problems.append(f"{name}.table must be an object of colors")
+        for key, value in colors:
+            if not isinstance(value, str) or not _HEX_COLOR.fullmatch(value.strip()):
+                check.problems.append(f"{name}.{key}: {value!r} is not a hex color like #1f2937")
+    return check, themes
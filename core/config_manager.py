@@ -7,7 +7,33 @@ import sys
 from copy import deepcopy
 from typing import Any, Dict, List, Optional, Tuple
 
-from core.models import ItemRecord, MoneyRecord
+from core.csv_storage import READ_LOCK_MODES, migrate_to_document
+from core.errors import InvalidWeightsError
+from core.models import ItemRecord, MoneyRecord, parse_date
+
+
+# Used relative to the working directory when the platform data directory is unavailable.
+FALLBACK_DATA_DIR = "finance_planner_data"
+# The book kept at paths.*, where the data lived before there were books; others get a folder under books/.
+DEFAULT_BOOK = "default"
+BOOKS_DIR = "books"
+# Book names double as folder names.
+BOOK_NAME_PATTERN = re.compile(r"[A-Za-z0-9][A-Za-z0-9_-]{0,39}")
+# storage.format: one CSV per record kind, or items and money together in paths.data_json.
+STORAGE_FORMATS = ("csv", "json")
+# Starting 1-5 ratings for new items in the item form and `items add`; ui.item_defaults overrides them.
+DEFAULT_ITEM_RATINGS = {"urgency": 1, "value": 1, "want": 3, "price_comp": 1, "effect": 1}
+# Fields a ui.money_templates entry may set, named like the `money add` values; amount may be left out.
+MONEY_TEMPLATE_FIELDS = ("source", "entry_type", "amount", "notes", "category", "link")
+# Ends of the overall-score color ramp for themes that do not set score_bad / score_good.
+DEFAULT_SCORE_BAD = "#dc2626"
+DEFAULT_SCORE_GOOD = "#16a34a"
+# Text colors for income and expense rows in the money grid.
+DEFAULT_INCOME_COLOR = "#15803d"
+DEFAULT_EXPENSE_COLOR = "#c2410c"
+# Money grid text for negative balances and missing items, and for future income.
+DEFAULT_NEGATIVE_COLOR = "#dc2626"
+DEFAULT_WARNING_COLOR = "#d97706"
 
 
 class ConfigManager:
@@ -22,8 +48,14 @@ class ConfigManager:
     ) -> None:
         self.bundle_dir = getattr(sys, "_MEIPASS", os.getcwd())
         self.base_dir = os.path.abspath(base_dir or self.bundle_dir)
-        self.user_root = self._user_data_root()
         self.load_messages: List[str] = []
+        # (key, old, new) data paths that moved since the last run; filled in by the entry points at startup.
+        self.moved_data_paths: List[Tuple[str, str, str]] = []
+        # Theme chosen for this run only (--theme); never written to settings.json.
+        self.theme_override: Optional[str] = None
+        # The book whose data is read and written; books.current unless use_book picks another for this run.
+        self.book = DEFAULT_BOOK
+        self.user_root = self._resolve_user_root()
         self.settings_path = self._user_path(settings_path)
         self.weights_path = self._user_path(weights_path)
         self.themes_path = self._user_path(themes_path)
@@ -46,13 +78,34 @@ class ConfigManager:
         self._apply_defaults()
 
     @staticmethod
-    def _user_data_root() -> str:
+    def _user_data_root() -> Optional[str]:
         if os.name == "nt":
             base = os.environ.get("APPDATA") or os.path.expanduser("~\\AppData\\Roaming")
         else:
             base = os.environ.get("XDG_DATA_HOME") or os.path.expanduser("~/.local/share")
+        # expanduser leaves "~" in place when there is no resolvable home directory.
+        if base.startswith("~"):
+            return None
         return os.path.join(base, "finance_planner")
 
+    def _resolve_user_root(self) -> str:
+        """The platform data directory, or ./finance_planner_data when it cannot be used (e.g. containers)."""
+        preferred = self._user_data_root()
+        if preferred is None:
+            reason = "no home directory could be determined"
+        else:
+            try:
+                os.makedirs(preferred, exist_ok=True)
+                if os.access(preferred, os.W_OK):
+                    return preferred
+                reason = f"{preferred} is not writable"
+            except OSError as exc:
+                reason = f"{preferred} could not be created: {exc.strerror or exc}"
+        fallback = os.path.abspath(FALLBACK_DATA_DIR)
+        self.load_messages.append(f"Platform data directory unavailable ({reason}); using {fallback} instead.")
+        print(f"Warning: {self.load_messages[-1]}", file=sys.stderr)
+        return fallback
+
     def _user_path(self, relative: str) -> str:
         if os.path.isabs(relative):
             return relative
@@ -95,13 +148,16 @@ class ConfigManager:
             if created:
                 warnings.append(f"Weights file not found. A default template was created at {path}.")
             return weights, warnings
-        except Exception as exc:  # pragma: no cover - defensive
+        except Exception as exc:
+            # Including InvalidWeightsError: bands that cannot be scored in order would misrank every item.
             warnings.append(f"Failed to load weights from {path}: {exc}. Using defaults.")
             return dict(default), warnings
 
-    def _parse_weights_lines(self, lines: List[str], default: Dict[str, Any]) -> Tuple[Dict[str, Any], List[str]]:
+    @staticmethod
+    def _parse_weights_lines(lines: List[str], default: Dict[str, Any]) -> Tuple[Dict[str, Any], List[str]]:
         config = deepcopy(default)
         warnings: List[str] = []
+        defined_bands = 0
         weight_keys = {
             "weight_date": "date",
             "weight_cost": "cost",
@@ -110,6 +166,9 @@ class ConfigManager:
             "weight_want": "want",
             "weight_price_comp": "price_comp",
             "weight_effect": "effect",
+            "weight_cost_per_use": "cost_per_use",
+            "weight_target_date": "target_date",
+            "weight_effort": "effort",
         }
         for idx, raw in enumerate(lines, start=1):
             line = raw.strip()
@@ -137,10 +196,17 @@ class ConfigManager:
                 except ValueError:
                     warnings.append(f"Line {idx}: invalid integer for date_mid_days; using default.")
                 continue
+            if key == "target_ramp_days":
+                try:
+                    config.setdefault("date_scoring", {})["target_ramp_days"] = int(value)
+                except ValueError:
+                    warnings.append(f"Line {idx}: invalid integer for target_ramp_days; using default.")
+                continue
             cost_band_match = re.match(r"cost_band(\d+)_(max|score)$", key)
             if cost_band_match:
                 band_num = int(cost_band_match.group(1))
                 band_field = cost_band_match.group(2)
+                defined_bands = max(defined_bands, band_num)
                 while len(config.setdefault("cost_bands", [])) < band_num:
                     config["cost_bands"].append({"max": None, "score": 1})
                 band = config["cost_bands"][band_num - 1]
@@ -188,7 +254,27 @@ class ConfigManager:
                 except ValueError:
                     warnings.append(f"Line {idx}: invalid integer for urgency_override; using default.")
                 continue
+            if key == "as_of_date":
+                if value.lower() in {"", "none", "today"}:
+                    config["as_of"] = None
+                else:
+                    try:
+                        config["as_of"] = parse_date(value)
+                    except ValueError:
+                        warnings.append(f"Line {idx}: invalid date for as_of_date; scoring against today.")
+                continue
+            if key == "annualize_recurring_cost":
+                parsed = _parse_bool(value)
+                if parsed is None:
+                    warnings.append(f"Line {idx}: invalid true/false for annualize_recurring_cost; using default.")
+                else:
+                    config["annualize_recurring_cost"] = parsed
+                continue
             warnings.append(f"Line {idx}: unknown key '{key}'; ignored.")
+        if defined_bands:
+            # A file that lists its own bands replaces the default ones rather than overlaying them.
+            config["cost_bands"] = config["cost_bands"][:defined_bands]
+        config["cost_bands"] = normalize_cost_bands(config["cost_bands"])
         return config, warnings
 
     def _weights_template(self, config: Dict[str, Any]) -> str:
@@ -197,7 +283,8 @@ class ConfigManager:
         bands = config.get("cost_bands", [])
         lines = [
             "# Purchase scoring weights",
-            "# Edit values and restart the app to apply changes.",
+            "# Edit values and restart the app to apply changes, or use the Weights tab.",
+            "# Weights are relative: the overall is their weighted average, so it stays on the 1-5 scale.",
             "",
             f"weight_date={weights.get('date', 1.0)}",
             f"weight_cost={weights.get('cost', 1.0)}",
@@ -206,9 +293,17 @@ class ConfigManager:
             f"weight_want={weights.get('want', 1.0)}",
             f"weight_price_comp={weights.get('price_comp', 1.0)}",
             f"weight_effect={weights.get('effect', 1.0)}",
+            "# Cost per expected use (items without an estimate score neutral); 0 disables it",
+            f"weight_cost_per_use={weights.get('cost_per_use', 0.0)}",
+            "# Closeness to an item's target date (items without one score neutral); 0 disables it",
+            f"weight_target_date={weights.get('target_date', 0.0)}",
+            "# Effort to buy or set up; more effort lowers the score (unrated items score neutral); 0 disables it",
+            f"weight_effort={weights.get('effort', 0.0)}",
             "",
             f"date_recent_days={date_scoring.get('recent_days', 7)}",
             f"date_mid_days={date_scoring.get('mid_days', 30)}",
+            "# Days before a target date over which its score ramps from 1 up to 5",
+            f"target_ramp_days={date_scoring.get('target_ramp_days', 30)}",
             "",
             "# Cost bands: ascending maximum (use 'none' for no upper bound)",
         ]
@@ -219,6 +314,13 @@ class ConfigManager:
             lines.append(f"cost_band{idx}_score={band.get('score', 1)}")
         lines.append("")
         lines.append(f"urgency_override={config.get('urgency_override', 5)}")
+        lines.append("")
+        lines.append("# Score recurring items on their yearly cost (e.g. monthly cost x 12) instead of one payment")
+        lines.append(f"annualize_recurring_cost={str(config.get('annualize_recurring_cost', False)).lower()}")
+        lines.append("")
+        lines.append("# Pin the 'today' used for age scoring (YYYY-MM-DD) to reproduce past scores; 'none' = today")
+        as_of = config.get("as_of")
+        lines.append(f"as_of_date={as_of.strftime('%Y-%m-%d') if as_of else 'none'}")
         return "\n".join(str(line) for line in lines)
 
     @staticmethod
@@ -227,17 +329,46 @@ class ConfigManager:
             "paths": {
                 "items_csv": "",
                 "money_csv": "",
+                "data_json": "",
                 "backup_dir": "",
             },
             "backup": {
+                "enabled": True,
                 "keep_recent": 3,
                 "keep_historical": 3,
+                "max_total_bytes": None,
+                "on_startup": False,
+            },
+            "storage": {
+                "lock_timeout_seconds": 5,
+                "archive_after_days": 365,
+                "format": "csv",
+                "cli_read_lock": "wait",
+                "rescore_on_load": False,
             },
             "themes": {"default": "light"},
+            "books": {"current": DEFAULT_BOOK},
             "ui": {
                 "date_format": "%Y-%m-%d %H:%M",
                 "currency_symbol": "$",
                 "autosave": True,
+                "autosave_interval_seconds": 5,
+                "items_columns": ["product", "date", "cost", "urgency", "want", "overall"],
+                "locations": ["Online", "Brick and Mortar", "Second-hand", "Gift"],
+                "cost_warn_threshold": None,
+                "money_categories": ["Housing", "Groceries", "Utilities", "Transport", "Health", "Entertainment", "Salary"],
+                "remember_sort": True,
+                "default_items_sort": {"column": "overall", "direction": "desc"},
+                "default_money_sort": {"column": 0, "direction": "desc"},
+                "min_window_size": [720, 480],
+                "compact_width": 1100,
+                "show_save_status": True,
+                "font_scale": 1.0,
+                "row_height": 0,
+                "compact_mode": False,
+                "fiscal_month_start_day": 1,
+                "item_defaults": dict(DEFAULT_ITEM_RATINGS),
+                "money_templates": {},
             },
         }
 
@@ -252,8 +383,11 @@ class ConfigManager:
                 "want": 1.0,
                 "price_comp": 1.0,
                 "effect": 1.0,
+                "cost_per_use": 0.0,
+                "target_date": 0.0,
+                "effort": 0.0,
             },
-            "date_scoring": {"recent_days": 7, "mid_days": 30},
+            "date_scoring": {"recent_days": 7, "mid_days": 30, "target_ramp_days": 30},
             "cost_bands": [
                 {"max": 50, "score": 5},
                 {"max": 150, "score": 4},
@@ -261,12 +395,21 @@ class ConfigManager:
                 {"max": 800, "score": 2},
                 {"max": None, "score": 1},
             ],
+            "cost_per_use_bands": [
+                {"max": 1, "score": 5},
+                {"max": 5, "score": 4},
+                {"max": 20, "score": 3},
+                {"max": 50, "score": 2},
+                {"max": None, "score": 1},
+            ],
             "urgency_override": 5,
+            "annualize_recurring_cost": False,
+            "as_of": None,
         }
 
     @staticmethod
     def _default_themes() -> Dict[str, Any]:
-        # Minimal fallback; the bundled themes.json has richer content.
+        # Fallback when themes.json is missing; high-contrast is also added to themes files that lack it.
         return {
             "light": {
                 "background": "#f7f9fb",
@@ -279,6 +422,25 @@ class ConfigManager:
                     "row_bg": "#ffffff",
                     "alt_row_bg": "#f1f5f9",
                 },
+            },
+            "high-contrast": {
+                # Every text color is at least 7:1 (WCAG AAA) against both row backgrounds.
+                "background": "#000000",
+                "foreground": "#ffffff",
+                "accent": "#ffff00",
+                "muted": "#d0d0d0",
+                "score_bad": "#ff8a8a",
+                "score_good": "#5cff5c",
+                "income": "#5cff5c",
+                "expense": "#ffa94d",
+                "negative": "#ff8a8a",
+                "warning": "#ffd24d",
+                "table": {
+                    "header_bg": "#ffffff",
+                    "header_fg": "#000000",
+                    "row_bg": "#000000",
+                    "alt_row_bg": "#1a1a1a",
+                },
             }
         }
 
@@ -297,13 +459,19 @@ class ConfigManager:
         if not paths.get("money_csv"):
             paths["money_csv"] = os.path.join(self.user_root, "data", "money.csv")
             changed = True
+        if not paths.get("data_json"):
+            paths["data_json"] = os.path.join(self.user_root, "data", "data.json")
+            changed = True
         if not paths.get("backup_dir"):
             paths["backup_dir"] = os.path.join(self.user_root, "backups")
             changed = True
 
         backup_defaults = {
+            "enabled": True,
             "keep_recent": 3,
             "keep_historical": 3,
+            "max_total_bytes": None,
+            "on_startup": False,
         }
         if "backup" not in self.settings:
             self.settings["backup"] = dict(backup_defaults)
@@ -314,6 +482,23 @@ class ConfigManager:
                     self.settings["backup"][key] = value
                     changed = True
 
+        storage = self.settings.setdefault("storage", {})
+        if "lock_timeout_seconds" not in storage:
+            storage["lock_timeout_seconds"] = 5
+            changed = True
+        if "archive_after_days" not in storage:
+            storage["archive_after_days"] = 365
+            changed = True
+        if storage.get("format") not in STORAGE_FORMATS:
+            storage["format"] = "csv"
+            changed = True
+        if storage.get("cli_read_lock") not in READ_LOCK_MODES:
+            storage["cli_read_lock"] = "wait"
+            changed = True
+        if "rescore_on_load" not in storage:
+            storage["rescore_on_load"] = False
+            changed = True
+
         if "themes" not in self.settings:
             self.settings["themes"] = {"default": "light"}
             changed = True
@@ -321,11 +506,43 @@ class ConfigManager:
             if "default" not in self.settings["themes"]:
                 self.settings["themes"]["default"] = "light"
                 changed = True
+        books = self.settings.get("books")
+        if not isinstance(books, dict):
+            books = self.settings["books"] = {"current": DEFAULT_BOOK}
+            changed = True
+        current = books.get("current")
+        if current != DEFAULT_BOOK and current not in self.book_names():
+            if current is not None:
+                self.load_messages.append(f"Book '{current}' was not found; opening the {DEFAULT_BOOK} book.")
+            books["current"] = DEFAULT_BOOK
+            changed = True
+        self.book = books["current"]
+        if isinstance(self.themes, dict):
+            # Themes files copied before a built-in theme existed still offer it.
+            for name, theme in self._default_themes().items():
+                self.themes.setdefault(name, theme)
 
         ui_defaults = {
             "date_format": "%Y-%m-%d %H:%M",
             "currency_symbol": "$",
             "autosave": True,
+            "autosave_interval_seconds": 5,
+            "items_columns": ["product", "date", "cost", "urgency", "want", "overall"],
+            "locations": ["Online", "Brick and Mortar", "Second-hand", "Gift"],
+            "cost_warn_threshold": None,
+            "money_categories": ["Housing", "Groceries", "Utilities", "Transport", "Health", "Entertainment", "Salary"],
+            "remember_sort": True,
+            "default_items_sort": {"column": "overall", "direction": "desc"},
+            "default_money_sort": {"column": 0, "direction": "desc"},
+            "min_window_size": [720, 480],
+            "compact_width": 1100,
+            "show_save_status": True,
+            "font_scale": 1.0,
+            "row_height": 0,
+            "compact_mode": False,
+            "fiscal_month_start_day": 1,
+            "item_defaults": dict(DEFAULT_ITEM_RATINGS),
+            "money_templates": {},
         }
         if "ui" not in self.settings:
             self.settings["ui"] = dict(ui_defaults)
@@ -335,6 +552,31 @@ class ConfigManager:
                 if key not in self.settings["ui"]:
                     self.settings["ui"][key] = value
                     changed = True
+        start_day = self.settings["ui"]["fiscal_month_start_day"]
+        if isinstance(start_day, bool) or not isinstance(start_day, int) or not 1 <= start_day <= 31:
+            self.settings["ui"]["fiscal_month_start_day"] = 1
+            changed = True
+        item_defaults = self.settings["ui"]["item_defaults"]
+        if not isinstance(item_defaults, dict):
+            item_defaults = self.settings["ui"]["item_defaults"] = {}
+        for key, default in DEFAULT_ITEM_RATINGS.items():
+            value = item_defaults.get(key)
+            if isinstance(value, bool) or not isinstance(value, int) or not 1 <= value <= 5:
+                if key in item_defaults:
+                    self.load_messages.append(
+                        f"ui.item_defaults.{key} must be a whole number from 1 to 5; using {default}."
+                    )
+                item_defaults[key] = default
+                changed = True
+        templates = self.settings["ui"]["money_templates"]
+        # Invalid templates stay in the file for the user to fix; money_templates() leaves them out.
+        if not isinstance(templates, dict):
+            self.load_messages.append("ui.money_templates must be an object of named templates; ignoring it.")
+        else:
+            for name, template in templates.items():
+                problem = money_template_problem(template)
+                if problem:
+                    self.load_messages.append(f"ui.money_templates.{name}: {problem}; template ignored.")
 
         self.weights.setdefault(
             "weights",
@@ -346,9 +588,17 @@ class ConfigManager:
                 "want": 1.0,
                 "price_comp": 1.0,
                 "effect": 1.0,
+                "cost_per_use": 0.0,
+                "target_date": 0.0,
+                "effort": 0.0,
             },
         )
+        self.weights["weights"].setdefault("cost_per_use", 0.0)
+        self.weights["weights"].setdefault("target_date", 0.0)
+        self.weights["weights"].setdefault("effort", 0.0)
+        self.weights.setdefault("cost_per_use_bands", deepcopy(self._default_weights()["cost_per_use_bands"]))
         self.weights.setdefault("date_scoring", {"recent_days": 7, "mid_days": 30})
+        self.weights["date_scoring"].setdefault("target_ramp_days", 30)
         self.weights.setdefault(
             "cost_bands",
             [
@@ -360,6 +610,8 @@ class ConfigManager:
             ],
         )
         self.weights.setdefault("urgency_override", 5)
+        self.weights.setdefault("annualize_recurring_cost", False)
+        self.weights.setdefault("as_of", None)
         # ensure every theme has table defaults to avoid KeyError when packed
         for name, theme in list(self.themes.items()):
             theme.setdefault("table", {})
@@ -372,18 +624,98 @@ class ConfigManager:
         if changed:
             self.save_settings()
 
+    def book_names(self) -> List[str]:
+        """The default book, then every book folder under books/, sorted."""
+        root = os.path.join(self.user_root, BOOKS_DIR)
+        found = sorted(
+            name
+            for name in (os.listdir(root) if os.path.isdir(root) else [])
+            if name != DEFAULT_BOOK and BOOK_NAME_PATTERN.fullmatch(name) and os.path.isdir(os.path.join(root, name))
+        )
+        return [DEFAULT_BOOK] + found
+
+    def book_paths(self, name: str) -> Dict[str, str]:
+        """paths.* for the default book; a book's own data and backup locations under books/<name> otherwise."""
+        if name == DEFAULT_BOOK:
+            return dict(self.settings["paths"])
+        root = os.path.join(self.user_root, BOOKS_DIR, name)
+        return {
+            "items_csv": os.path.join(root, "data", "items.csv"),
+            "money_csv": os.path.join(root, "data", "money.csv"),
+            "data_json": os.path.join(root, "data", "data.json"),
+            "backup_dir": os.path.join(root, "backups"),
+        }
+
+    @property
+    def paths(self) -> Dict[str, str]:
+        """The data and backup locations of the book in use."""
+        return self.book_paths(self.book)
+
+    def data_files(self) -> Tuple[str, str]:
+        """The (items, money) paths to read and write: both CSVs, or the combined data.json twice."""
+        paths = self.paths
+        if self.settings.get("storage", {}).get("format") == "json":
+            return paths["data_json"], paths["data_json"]
+        return paths["items_csv"], paths["money_csv"]
+
+    def use_book(self, name: str) -> None:
+        """Work in an existing book for this run; ValueError naming the books when it does not exist."""
+        if name not in self.book_names():
+            raise ValueError(f"No book named '{name}'; books: {', '.join(self.book_names())}")
+        self.book = name
+
+    def create_book(self, name: str) -> None:
+        """Make an empty book folder; its data files are created when the book is first opened."""
+        if not BOOK_NAME_PATTERN.fullmatch(name):
+            raise ValueError("Book names use letters, digits, '-' and '_' (up to 40), starting with a letter or digit")
+        if name in self.book_names():
+            raise ValueError(f"A book named '{name}' already exists")
+        os.makedirs(os.path.join(self.user_root, BOOKS_DIR, name))
+
+    def set_current_book(self, name: str) -> None:
+        """Switch to a book and open it on later runs too."""
+        self.use_book(name)
+        self.settings["books"]["current"] = name
+        self.save_settings()
+
     def save_settings(self) -> None:
         os.makedirs(os.path.dirname(self.settings_path), exist_ok=True)
         with open(self.settings_path, "w", encoding="utf-8") as f:
             json.dump(self.settings, f, indent=2)
 
+    def weights_text(self) -> str:
+        """The current weights in weights.txt form, e.g. to travel with an exported bundle."""
+        return self._weights_template(self.weights)
+
+    def parse_weights_text(self, text: str) -> Tuple[Dict[str, Any], List[str]]:
+        """Weights from weights.txt-formatted text (such as a bundle's), with a warning per skipped line."""
+        return self._parse_weights_lines(text.splitlines(), self._default_weights())
+
+    def save_weights(self, weights: Dict[str, Any]) -> None:
+        """Validate and write weights back to weights.txt, replacing the in-memory copy."""
+        weights["cost_bands"] = normalize_cost_bands(weights.get("cost_bands", []))
+        os.makedirs(os.path.dirname(self.weights_path), exist_ok=True)
+        with open(self.weights_path, "w", encoding="utf-8") as f:
+            f.write(self._weights_template(weights))
+        self.weights = weights
+
     def save_themes(self) -> None:
         os.makedirs(os.path.dirname(self.themes_path), exist_ok=True)
         with open(self.themes_path, "w", encoding="utf-8") as f:
             json.dump(self.themes, f, indent=2)
 
+    def active_theme_name(self) -> str:
+        """The --theme override for this run, else themes.default."""
+        return self.theme_override or self.settings.get("themes", {}).get("default", "light")
+
+    def override_theme(self, name: str) -> None:
+        """Use a theme for this run without saving it; ValueError naming the known themes if it does not exist."""
+        if name not in self.themes:
+            raise ValueError(f"Unknown theme '{name}'; choose from {', '.join(sorted(self.themes))}")
+        self.theme_override = name
+
     def get_theme(self, name: Optional[str] = None) -> Dict[str, Any]:
-        theme_name = name or self.settings.get("themes", {}).get("default", "light")
+        theme_name = name or self.active_theme_name()
         base = self.themes.get("light", {})
         selected = self.themes.get(theme_name, base)
         # ensure required keys exist
@@ -392,6 +724,12 @@ class ConfigManager:
             "foreground": selected.get("foreground", base.get("foreground", "#000000")),
             "accent": selected.get("accent", base.get("accent", "#2563eb")),
             "muted": selected.get("muted", base.get("muted", "#94a3b8")),
+            "score_bad": selected.get("score_bad", base.get("score_bad", DEFAULT_SCORE_BAD)),
+            "score_good": selected.get("score_good", base.get("score_good", DEFAULT_SCORE_GOOD)),
+            "income": selected.get("income", base.get("income", DEFAULT_INCOME_COLOR)),
+            "expense": selected.get("expense", base.get("expense", DEFAULT_EXPENSE_COLOR)),
+            "negative": selected.get("negative", base.get("negative", DEFAULT_NEGATIVE_COLOR)),
+            "warning": selected.get("warning", base.get("warning", DEFAULT_WARNING_COLOR)),
         }
         table = selected.get("table", {}) or {}
         base_table = base.get("table", {}) or {}
@@ -403,16 +741,164 @@ class ConfigManager:
         }
         return theme
 
+    def locations(self) -> List[str]:
+        return list(self.settings.get("ui", {}).get("locations", []))
+
+    def fiscal_month_start_day(self) -> int:
+        """Day of the month that budget months start on; 1 means calendar months."""
+        return int(self.settings.get("ui", {}).get("fiscal_month_start_day", 1))
+
+    def item_defaults(self) -> Dict[str, int]:
+        """Starting ratings for a new item (urgency, value, want, price_comp, effect), each 1-5."""
+        configured = self.settings.get("ui", {}).get("item_defaults", {})
+        return {key: int(configured.get(key, default)) for key, default in DEFAULT_ITEM_RATINGS.items()}
+
+    def money_categories(self) -> List[str]:
+        return list(self.settings.get("ui", {}).get("money_categories", []))
+
+    def money_templates(self) -> Dict[str, Dict[str, Any]]:
+        """The valid named money entry templates, by name."""
+        templates = self.settings.get("ui", {}).get("money_templates", {})
+        if not isinstance(templates, dict):
+            return {}
+        return {name: dict(t) for name, t in sorted(templates.items()) if not money_template_problem(t)}
+
+    def save_money_template(self, name: str, template: Dict[str, Any]) -> None:
+        """Add or replace a template; ValueError when name or contents are invalid."""
+        name = " ".join(name.split())
+        if not name:
+            raise ValueError("A template needs a name")
+        problem = money_template_problem(template)
+        if problem:
+            raise ValueError(problem)
+        templates = self.settings.setdefault("ui", {}).get("money_templates")
+        if not isinstance(templates, dict):
+            templates = self.settings["ui"]["money_templates"] = {}
+        templates[name] = dict(template)
+        self.save_settings()
+
+    def delete_money_template(self, name: str) -> None:
+        self.settings.get("ui", {}).get("money_templates", {}).pop(name, None)
+        self.save_settings()
+
+    def remember_location(self, value: str) -> str:
+        """Return the known spelling of value (case-insensitive), adding new locations to the list."""
+        cleaned = " ".join(value.split())
+        if not cleaned:
+            return ""
+        known = self.settings.setdefault("ui", {}).setdefault("locations", [])
+        for existing in known:
+            if existing.lower() == cleaned.lower():
+                return existing
+        known.append(cleaned)
+        self.save_settings()
+        return cleaned
+
+    def cost_exceeds_warning(self, cost: float) -> bool:
+        """True when a cost threshold is configured and cost is above it."""
+        threshold = self.settings.get("ui", {}).get("cost_warn_threshold")
+        return threshold is not None and cost > float(threshold)
+
     def set_default_theme(self, name: str) -> None:
         self.settings.setdefault("themes", {})
         self.settings["themes"]["default"] = name
         self.save_settings()
 
 
-def ensure_paths(settings: Dict[str, Any]) -> None:
+def cost_band_error(bands: List[Dict[str, Any]]) -> Optional[str]:
+    """Describe why bands cannot be scored in order, or return None when they are valid."""
+    previous: Optional[float] = None
+    for idx, band in enumerate(bands, start=1):
+        max_val = band.get("max")
+        if max_val is None:
+            if idx != len(bands):
+                return f"Cost band {idx} has no maximum but is not the last band."
+            continue
+        if previous is not None and float(max_val) <= previous:
+            return f"Cost band {idx} maximum ({max_val}) must be greater than the band before it ({previous})."
+        previous = float(max_val)
+    return None
+
+
+def score_color(theme: Dict[str, Any], score: float, low: float = 1.0, high: float = 5.0) -> str:
+    """Hex color for an overall score, blended linearly from the theme's score_bad (low) to score_good (high)."""
+
+    def rgb(value: str) -> Tuple[int, int, int]:
+        value = value.lstrip("#")
+        if len(value) == 3:
+            value = "".join(ch * 2 for ch in value)
+        return int(value[0:2], 16), int(value[2:4], 16), int(value[4:6], 16)
+
+    try:
+        bad = rgb(str(theme.get("score_bad", DEFAULT_SCORE_BAD)))
+        good = rgb(str(theme.get("score_good", DEFAULT_SCORE_GOOD)))
+    except ValueError:
+        bad, good = rgb(DEFAULT_SCORE_BAD), rgb(DEFAULT_SCORE_GOOD)
+    t = min(max((score - low) / (high - low), 0.0), 1.0)
+    return "#" + "".join(f"{round(b + (g - b) * t):02x}" for b, g in zip(bad, good))
+
+
+def money_template_problem(template: Any) -> Optional[str]:
+    """Why a money template is unusable, or None when it is fine."""
+    if not isinstance(template, dict):
+        return "must be an object of fields"
+    unknown = sorted(set(template) - set(MONEY_TEMPLATE_FIELDS))
+    if unknown:
+        return f"unknown field(s) {', '.join(unknown)}; use {', '.join(MONEY_TEMPLATE_FIELDS)}"
+    if not isinstance(template.get("source"), str) or not template["source"].strip():
+        return "source must be non-empty text"
+    if template.get("entry_type", "expense") not in ("income", "expense"):
+        return "entry_type must be income or expense"
+    if "amount" in template:
+        amount = template["amount"]
+        if isinstance(amount, bool) or not isinstance(amount, (int, float)) or amount < 0:
+            return "amount must be a number of 0 or more, or left out to ask for it each time"
+    for key in ("notes", "category", "link"):
+        if not isinstance(template.get(key, ""), str):
+            return f"{key} must be text"
+    return None
+
+
+def describe_money_template(template: Dict[str, Any], currency_symbol: str = "$") -> str:
+    """One line for listing a valid template, e.g. `expense, Landlord, $1,200.00, category=Housing`."""
+    amount = f"{currency_symbol}{template['amount']:,.2f}" if "amount" in template else "amount asked each time"
+    shown = [template.get("entry_type", "expense"), template["source"], amount]
+    shown += [f"{key}={template[key]}" for key in ("category", "notes", "link") if template.get(key)]
+    return ", ".join(shown)
+
+
+def normalize_cost_bands(bands: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """Sort bands by maximum with the unbounded band last; raise InvalidWeightsError when they cannot cover every cost.
+
+    Scoring walks the bands in order and stops at the first one a cost fits, so they must ascend
+    and end with exactly one band without a maximum.
+    """
+    for idx, band in enumerate(bands, start=1):
+        max_val = band.get("max")
+        if max_val is not None and (isinstance(max_val, bool) or not isinstance(max_val, (int, float))):
+            raise InvalidWeightsError(f"Cost band {idx} maximum ({max_val!r}) is not a number.")
+    ordered = sorted(bands, key=lambda band: (band.get("max") is None, float(band.get("max") or 0)))
+    unbounded = sum(1 for band in ordered if band.get("max") is None)
+    if unbounded != 1:
+        raise InvalidWeightsError(f"Cost bands need exactly one band without a maximum; found {unbounded}.")
+    error = cost_band_error(ordered)
+    if error:
+        raise InvalidWeightsError(error)
+    return ordered
+
+
+def _parse_bool(value: str) -> Optional[bool]:
+    lowered = value.strip().lower()
+    if lowered in {"true", "yes", "on", "1"}:
+        return True
+    if lowered in {"false", "no", "off", "0"}:
+        return False
+    return None
+
+
+def ensure_paths(paths: Dict[str, str]) -> None:
     """Ensure directories for data and backups exist."""
-    paths = settings.get("paths", {})
-    for key in ("items_csv", "money_csv", "backup_dir"):
+    for key in ("items_csv", "money_csv", "data_json", "backup_dir"):
         path = paths.get(key)
         if path:
             os.makedirs(os.path.dirname(path) if key != "backup_dir" else path, exist_ok=True)
@@ -424,9 +910,12 @@ def ensure_startup_files(config: "ConfigManager") -> None:
     _ensure_text_if_missing(config.weights_path, config._weights_template(config.weights))
     _ensure_json_if_missing(config.themes_path, config.themes)
 
-    paths = config.settings.get("paths", {})
-    _ensure_csv_if_missing(paths.get("items_csv"), ItemRecord.headers())
-    _ensure_csv_if_missing(paths.get("money_csv"), MoneyRecord.headers())
+    paths = config.paths
+    if config.settings.get("storage", {}).get("format") == "json":
+        migrate_to_document(paths["items_csv"], paths["money_csv"], paths["data_json"])
+    else:
+        _ensure_csv_if_missing(paths.get("items_csv"), ItemRecord.headers())
+        _ensure_csv_if_missing(paths.get("money_csv"), MoneyRecord.headers())
     backup_dir = paths.get("backup_dir")
     if backup_dir:
         os.makedirs(backup_dir, exist_ok=True)
@@ -1,12 +1,17 @@
 import csv
+import gzip
+import io
 import json
 import os
+import sys
 import time
-from contextlib import contextmanager
+from contextlib import ExitStack, contextmanager
+from dataclasses import dataclass, field
 from datetime import datetime, timezone
-from typing import Dict, Iterable, List, Tuple
+from typing import Dict, Iterable, Iterator, List, Optional, Tuple
 
-from core.models import DATE_FMT, ItemRecord, MoneyRecord
+from core.errors import DataFileLockedError, DataFileParseError
+from core.models import ItemRecord, MoneyRecord
 
 try:
     import fcntl  # type: ignore
@@ -18,37 +23,68 @@ try:
 except ImportError:  # pragma: no cover - non-Windows
     msvcrt = None
 
-_LOCK_RETRIES = 5
 _LOCK_DELAY = 0.1
+_lock_timeout = 5.0
+# How reads take the lock: "wait" up to the lock timeout, "try" briefly and then read unlocked with a
+# warning, or "none". Writes always wait for the lock.
+READ_LOCK_MODES = ("wait", "try", "none")
+TRY_LOCK_SECONDS = 0.5
+_read_lock = "wait"
+
+
+def set_lock_timeout(seconds: float) -> None:
+    global _lock_timeout
+    _lock_timeout = max(0.0, float(seconds))
+
+
+def set_read_lock(mode: str) -> None:
+    global _read_lock
+    if mode not in READ_LOCK_MODES:
+        raise ValueError(f"Unknown read lock mode '{mode}'; expected one of {', '.join(READ_LOCK_MODES)}")
+    _read_lock = mode
 
 
 @contextmanager
-def locked_file(path: str, mode: str):
-    os.makedirs(os.path.dirname(path), exist_ok=True)
-    fh = open(path, mode, newline="", encoding="utf-8")
+def locked_file(path: str, mode: str, timeout: Optional[float] = None):
+    os.makedirs(os.path.dirname(path) or ".", exist_ok=True)
+    # Truncating before the lock is held would clobber a file another process is writing.
+    truncate = mode == "w"
+    text_args = {} if "b" in mode else {"newline": "", "encoding": "utf-8"}
+    fh = open(path, "a" if truncate else mode, **text_args)
+    try:
+        _lock_file(fh, path, _lock_timeout if timeout is None else timeout)
+    except DataFileLockedError:
+        fh.close()
+        raise
     try:
-        _lock_file(fh)
+        if truncate:
+            fh.seek(0)
+            fh.truncate()
         yield fh
     finally:
         _unlock_file(fh)
         fh.close()
 
 
-def _lock_file(fh) -> None:
-    if fcntl:
-        for _ in range(_LOCK_RETRIES):
-            try:
+def _lock_file(fh, path: str, timeout: float) -> None:
+    if not fcntl and not msvcrt:
+        return
+    deadline = time.monotonic() + timeout
+    while True:
+        try:
+            if fcntl:
                 fcntl.flock(fh.fileno(), fcntl.LOCK_EX | fcntl.LOCK_NB)
-                return
-            except BlockingIOError:
-                time.sleep(_LOCK_DELAY)
-    elif msvcrt:
-        for _ in range(_LOCK_RETRIES):
-            try:
+            else:
                 msvcrt.locking(fh.fileno(), msvcrt.LK_NBLCK, 1)
-                return
-            except OSError:
-                time.sleep(_LOCK_DELAY)
+            return
+        except OSError:
+            if time.monotonic() >= deadline:
+                raise DataFileLockedError(
+                    path,
+                    f"{path} is locked by another process (waited {timeout:g}s). "
+                    "Close other Finance Planner windows and try again."
+                ) from None
+            time.sleep(_LOCK_DELAY)
 
 
 def _unlock_file(fh) -> None:
@@ -64,56 +100,150 @@ def _unlock_file(fh) -> None:
             pass
 
 
+DOCUMENT_KEYS = {ItemRecord: "items", MoneyRecord: "money"}
+
+
+def is_document(path: str) -> bool:
+    """Whether path is a combined items-and-money JSON document rather than one CSV per kind."""
+    return path.lower().endswith(".json")
+
+
 def read_items(path: str) -> List[ItemRecord]:
-    if not os.path.exists(path):
-        return []
-    with locked_file(path, "r") as fh:
-        reader = csv.DictReader(fh)
-        _validate_headers(path, reader.fieldnames, ItemRecord.headers())
-        return [_safe_record_from_row(ItemRecord.from_row, row, path, reader.line_num) for row in reader]
+    return _read_records(path, ItemRecord)
 
 
 def write_items(path: str, items: Iterable[ItemRecord]) -> None:
+    if is_document(path):
+        _write_document_section(path, "items", items)
+        return
     with locked_file(path, "w") as fh:
         writer = csv.DictWriter(fh, fieldnames=ItemRecord.headers())
         writer.writeheader()
         for item in items:
-            writer.writerow(item.to_row(DATE_FMT))
+            writer.writerow(item.to_row())
 
 
 def read_money(path: str) -> List[MoneyRecord]:
-    if not os.path.exists(path):
-        return []
-    with locked_file(path, "r") as fh:
-        reader = csv.DictReader(fh)
-        _validate_headers(path, reader.fieldnames, MoneyRecord.headers())
-        return [_safe_record_from_row(MoneyRecord.from_row, row, path, reader.line_num) for row in reader]
+    return _read_records(path, MoneyRecord)
 
 
 def write_money(path: str, entries: Iterable[MoneyRecord]) -> None:
+    if is_document(path):
+        _write_document_section(path, "money", entries)
+        return
     with locked_file(path, "w") as fh:
         writer = csv.DictWriter(fh, fieldnames=MoneyRecord.headers())
         writer.writeheader()
         for entry in entries:
-            writer.writerow(entry.to_row(DATE_FMT))
+            writer.writerow(entry.to_row())
+
 
+def export_columns(record_cls, names: Iterable[str]) -> List[str]:
+    """Validate a column whitelist and return it in file order, always starting with id."""
+    wanted = {name.strip() for name in names if name.strip()}
+    unknown = sorted(wanted - set(record_cls.headers()))
+    if unknown:
+        raise ValueError(f"Unknown column(s): {', '.join(unknown)}; choose from {', '.join(record_cls.headers())}")
+    return [header for header in record_cls.headers() if header == "id" or header in wanted]
 
-def write_bundle(path: str, items: Iterable[ItemRecord], money: Iterable[MoneyRecord]) -> None:
+
+def write_csv_columns(path: str, records: Iterable, columns: List[str]) -> None:
+    """Write records as CSV with only the given columns, e.g. to share a file without notes."""
+    with locked_file(path, "w") as fh:
+        writer = csv.DictWriter(fh, fieldnames=columns, extrasaction="ignore")
+        writer.writeheader()
+        for record in records:
+            writer.writerow(record.to_row())
+
+
+def iter_jsonl(records: Iterable, columns: Optional[List[str]] = None) -> Iterator[str]:
+    """Yield one JSON object per record, newline-terminated, using the same field encoding as the CSV."""
+    for record in records:
+        row = record.to_row()
+        if columns:
+            row = {column: row[column] for column in columns}
+        yield json.dumps(row, ensure_ascii=False) + "\n"
+
+
+def write_jsonl(path: str, records: Iterable, columns: Optional[List[str]] = None) -> None:
+    with locked_file(path, "w") as fh:
+        fh.writelines(iter_jsonl(records, columns))
+
+
+def _bundle_metadata() -> Dict[str, object]:
+    return {
+        "version": 1,
+        "generated_at": datetime.now(timezone.utc).isoformat(),
+    }
+
+
+def write_bundle(
+    path: str, items: Iterable[ItemRecord], money: Iterable[MoneyRecord], weights_text: Optional[str] = None
+) -> None:
+    """Write items and money as one JSON document; weights_text (weights.txt contents) adds a `config`
+    section so the recipient can reproduce the scores."""
     payload: Dict[str, object] = {
-        "metadata": {
-            "version": 1,
-            "generated_at": datetime.now(timezone.utc).isoformat(),
-        },
-        "items": [item.to_row(DATE_FMT) for item in items],
-        "money": [entry.to_row(DATE_FMT) for entry in money],
+        "metadata": _bundle_metadata(),
+        "items": [item.to_row() for item in items],
+        "money": [entry.to_row() for entry in money],
     }
+    if weights_text is not None:
+        payload["config"] = {"weights": weights_text.splitlines()}
     with locked_file(path, "w") as fh:
         json.dump(payload, fh, ensure_ascii=False, indent=2)
 
 
-def read_bundle(path: str) -> Tuple[List[ItemRecord], List[MoneyRecord], Dict[str, object]]:
+def _parse_document(path: str, content: str) -> Dict[str, object]:
+    if not content.strip():
+        return {}
+    try:
+        data = json.loads(content)
+    except ValueError as exc:
+        raise DataFileParseError(path, f"{path} is not valid JSON: {exc}") from exc
+    if not isinstance(data, dict):
+        raise DataFileParseError(path, f"{path}: expected a JSON object with 'items' and 'money' arrays")
+    return data
+
+
+def _document_rows(path: str, record_cls) -> List[Dict[str, str]]:
+    rows = _parse_document(path, read_text(path)).get(DOCUMENT_KEYS[record_cls], [])
+    if not isinstance(rows, list) or not all(isinstance(row, dict) for row in rows):
+        raise DataFileParseError(path, f"{path}: '{DOCUMENT_KEYS[record_cls]}' must be an array of objects")
+    return rows
+
+
+def _write_document_section(path: str, key: str, records: Iterable) -> None:
+    """Replace one section of the combined document under its lock, keeping the other as it is."""
+    with locked_file(path, "a+") as fh:
+        fh.seek(0)
+        data = _parse_document(path, fh.read())
+        payload: Dict[str, object] = {
+            "metadata": _bundle_metadata(),
+            "items": data.get("items", []),
+            "money": data.get("money", []),
+        }
+        payload[key] = [record.to_row() for record in records]
+        fh.seek(0)
+        fh.truncate()
+        json.dump(payload, fh, ensure_ascii=False, indent=2)
+
+
+def migrate_to_document(items_csv: str, money_csv: str, document: str) -> bool:
+    """Create the combined document from the CSV files on first use; False if it already exists.
+
+    The CSVs are left in place, so switching `storage.format` back to csv picks up where they were.
+    """
+    if os.path.exists(document):
+        return False
+    write_items(document, read_items(items_csv))
+    write_money(document, read_money(money_csv))
+    return True
+
+
+def read_bundle(path: str) -> Tuple[List[ItemRecord], List[MoneyRecord], Dict[str, object], Optional[str]]:
+    """(items, money, metadata, weights_text); weights_text is None when the bundle carries no weights."""
     if not os.path.exists(path):
-        return [], [], {}
+        return [], [], {}, None
     with locked_file(path, "r") as fh:
         data = json.load(fh)
     items_raw = data.get("items", [])
@@ -121,20 +251,228 @@ def read_bundle(path: str) -> Tuple[List[ItemRecord], List[MoneyRecord], Dict[st
     metadata = data.get("metadata", {})
     items = [_safe_record_from_row(ItemRecord.from_row, row, path) for row in items_raw]
     money = [_safe_record_from_row(MoneyRecord.from_row, row, path) for row in money_raw]
-    return items, money, metadata
+    config = data.get("config")
+    weights_lines = config.get("weights") if isinstance(config, dict) else None
+    if not isinstance(weights_lines, list) or not all(isinstance(line, str) for line in weights_lines):
+        return items, money, metadata, None
+    return items, money, metadata, "\n".join(weights_lines)
+
+
+_GZIP_MAGIC = b"\x1f\x8b"
+
+
+def read_text(path: str) -> str:
+    """Read a data file under its lock (as far as set_read_lock allows), transparently decompressing it if it is gzipped.
+
+    Detection uses the gzip magic bytes rather than the extension, so a `.csv.gz` path the app
+    has since saved to (saves are always plain CSV) still reads back.
+    """
+    with _read_locked(path) as raw:
+        data = raw.read()
+    if data[:2] == _GZIP_MAGIC:
+        try:
+            data = gzip.decompress(data)
+        except (OSError, EOFError) as exc:
+            raise DataFileParseError(path, f"{path} is not a readable gzip file: {exc}") from exc
+    return data.decode("utf-8")
+
+
+def read_header(path: str) -> Optional[List[str]]:
+    """The header row of a CSV (or gzipped CSV) file, without reading the rows; None for an empty file."""
+    with _read_locked(path) as raw:
+        gzipped = raw.read(2) == _GZIP_MAGIC
+        raw.seek(0)
+        try:
+            stream = (
+                gzip.open(raw, "rt", encoding="utf-8", newline="")
+                if gzipped
+                else io.TextIOWrapper(raw, encoding="utf-8", newline="")
+            )
+            return next(csv.reader(stream), None)
+        except (OSError, EOFError, UnicodeDecodeError, csv.Error) as exc:
+            raise DataFileParseError(path, f"{path}: cannot read the header row: {exc}", 1) from exc
+
+
+@contextmanager
+def _read_locked(path: str):
+    """A binary handle on path that holds its lock, as far as set_read_lock allows.
+
+    Read through this handle, not a fresh open(): on Windows the lock is a byte-range lock that
+    blocks reads from every other handle, including this process's own.
+    """
+    with ExitStack() as stack:
+        fh = None
+        if _read_lock != "none":
+            try:
+                fh = stack.enter_context(locked_file(path, "rb", TRY_LOCK_SECONDS if _read_lock == "try" else None))
+            except DataFileLockedError:
+                if _read_lock == "wait":
+                    raise
+                # Another process is mid-write, so what we read may be partial or about to change.
+                print(f"Warning: {path} is locked by another process; reading it without the lock", file=sys.stderr)
+        yield fh or stack.enter_context(open(path, "rb"))
+
+
+def _read_records(path: str, record_cls) -> list:
+    if not os.path.exists(path):
+        return []
+    if is_document(path):
+        rows = _document_rows(path, record_cls)
+        return [_safe_record_from_row(record_cls.from_row, row, path, index) for index, row in enumerate(rows, 1)]
+    content = read_text(path)
+    # A zero-byte or blank file has no header row; treat it like a missing file.
+    if not content.strip():
+        return []
+    reader = csv.DictReader(io.StringIO(content))
+    try:
+        _validate_headers(path, reader.fieldnames, record_cls.required_headers())
+        return [_safe_record_from_row(record_cls.from_row, row, path, reader.line_num) for row in reader]
+    except csv.Error as exc:
+        line = reader.line_num
+        raise DataFileParseError(path, f"{path} (line {line}) is not valid CSV: {exc}", line) from exc
+
+
+@dataclass
+class ImportReport:
+    """What an import would bring in, gathered without writing anything."""
+
+    path: str
+    columns: List[str] = field(default_factory=list)
+    row_count: int = 0
+    records: list = field(default_factory=list)
+    errors: List[str] = field(default_factory=list)
+    duplicate_ids: List[str] = field(default_factory=list)
+    dangling_links: List[str] = field(default_factory=list)
+
+    @property
+    def ok(self) -> bool:
+        return not self.errors and not self.duplicate_ids
+
+    def lines(self) -> List[str]:
+        lines = [f"{self.path}: {self.row_count} row(s), {len(self.records)} valid"]
+        lines += [f"  error: {error}" for error in self.errors]
+        lines += [f"  duplicate id: {record_id}" for record_id in self.duplicate_ids]
+        lines += [f"  links to unknown item: {link}" for link in self.dangling_links]
+        return lines
+
+
+def validate_import(
+    path: str, record_cls, known_item_ids: Optional[Iterable[str]] = None, merge_into: Optional[Iterable] = None
+) -> ImportReport:
+    """Parse every row of a CSV (or gzipped CSV) import, collecting problems per row instead of stopping at the first.
+
+    A combined JSON document (see is_document) is checked the same way, using its section for record_cls.
+
+    With merge_into (the existing records), the file may hold only some columns: a row whose id
+    matches an existing record keeps that record's other fields, and a new row gets the usual
+    defaults for the missing ones (only id and date are required).
+
+    Duplicate ids within the file are reported and only the first occurrence is kept. For money
+    imports, pass the ids of items that will exist afterwards to have unknown links reported.
+    """
+    try:
+        if is_document(path):
+            reader = _DocumentReader(_document_rows(path, record_cls))
+            return _validate_rows(path, reader, record_cls, known_item_ids, merge_into)
+        content = read_text(path)
+    except (OSError, DataFileParseError) as exc:
+        report = ImportReport(path)
+        report.errors.append(str(exc))
+        return report
+    return _validate_rows(path, csv.DictReader(io.StringIO(content)), record_cls, known_item_ids, merge_into)
+
+
+def validate_pasted_rows(text: str, record_cls, known_item_ids: Optional[Iterable[str]] = None) -> ImportReport:
+    """Like validate_import, for rows pasted from a spreadsheet or text editor.
+
+    Tab-separated text is read as TSV, anything else as CSV. Without a header row the values are
+    taken in the export's column order, so a single copied row works as well as a full sheet.
+    """
+    lines = text.strip("\r\n").splitlines()
+    delimiter = "\t" if lines and "\t" in lines[0] else ","
+    first = next(csv.reader(lines[:1], delimiter=delimiter), [])
+    fieldnames = None
+    if not set(record_cls.required_headers()) <= {cell.strip() for cell in first}:
+        fieldnames = record_cls.headers()
+    reader = csv.DictReader(lines, fieldnames=fieldnames, delimiter=delimiter, restval="")
+    return _validate_rows("clipboard", reader, record_cls, known_item_ids)
+
+
+class _DocumentReader:
+    """The parts of csv.DictReader that _validate_rows uses, over a document's rows (line_num counts entries)."""
+
+    def __init__(self, rows: List[Dict[str, str]]) -> None:
+        self.rows = rows
+        self.fieldnames = sorted({key for row in rows for key in row}) if rows else None
+        self.line_num = 0
+
+    def __iter__(self) -> Iterator[Dict[str, str]]:
+        for self.line_num, row in enumerate(self.rows, 1):
+            yield row
+
+
+def _validate_rows(
+    path: str,
+    reader: csv.DictReader,
+    record_cls,
+    known_item_ids: Optional[Iterable[str]],
+    merge_into: Optional[Iterable] = None,
+) -> ImportReport:
+    report = ImportReport(path)
+    existing = {record.id: record for record in merge_into} if merge_into is not None else None
+    try:
+        if reader.fieldnames is None:
+            return report
+        report.columns = [name for name in reader.fieldnames if name]
+        _validate_headers(path, reader.fieldnames, ["id"] if existing is not None else record_cls.required_headers())
+        seen = set()
+        for row in reader:
+            report.row_count += 1
+            try:
+                if existing is not None:
+                    row = _fill_missing_columns(row, existing)
+                record = record_cls.from_row(row)
+            except Exception as exc:
+                report.errors.append(f"line {reader.line_num}: {exc}")
+                continue
+            if record.id in seen:
+                report.duplicate_ids.append(record.id)
+                continue
+            seen.add(record.id)
+            report.records.append(record)
+    except (ValueError, csv.Error) as exc:
+        report.errors.append(f"line {reader.line_num}: {exc}" if isinstance(exc, csv.Error) else str(exc))
+        return report
+    if known_item_ids is not None:
+        known = set(known_item_ids)
+        report.dangling_links = [
+            record.linked_item_id
+            for record in report.records
+            if getattr(record, "linked_item_id", "") and record.linked_item_id not in known
+        ]
+    return report
+
+
+def _fill_missing_columns(row: Dict[str, str], existing: Dict[str, object]) -> Dict[str, str]:
+    current = existing.get((row.get("id") or "").strip())
+    if current is not None:
+        return {**current.to_row(), **{key: value for key, value in row.items() if key and value is not None}}
+    if not (row.get("date") or "").strip():
+        raise ValueError("new records need a date")
+    return row
 
 
 def _validate_headers(path: str, headers: List[str] | None, expected: List[str]) -> None:
     if headers is None:
-        raise ValueError(f"{path}: Missing header row")
+        raise DataFileParseError(path, f"{path}: Missing header row", 1)
     missing = [h for h in expected if h not in headers]
     if missing:
-        raise ValueError(f"{path}: Missing required columns: {', '.join(missing)}")
+        raise DataFileParseError(path, f"{path}: Missing required columns: {', '.join(missing)}", 1)
 
 
 def _safe_record_from_row(factory, row: Dict[str, str], path: str, line_num: int | None = None):
     try:
-        return factory(row, DATE_FMT)
+        return factory(row)
     except Exception as exc:
         location = f"{path} (line {line_num})" if line_num else path
-        raise ValueError(f"Failed to parse record in {location}: {exc}") from exc
+        raise DataFileParseError(path, f"Failed to parse record in {location}: {exc}", line_num) from exc
@@ -0,0 +1,56 @@
+from typing import Dict, Iterable, List, Optional
+
+from core.errors import DependencyCycleError
+from core.models import ItemRecord
+
+
+def dependency_error(items: Iterable[ItemRecord], item_id: str, depends_on: str) -> Optional[str]:
+    """Why item_id may not depend on depends_on, or None when the link is allowed."""
+    if not depends_on:
+        return None
+    by_id = {item.id: item for item in items}
+    if depends_on not in by_id:
+        return f"No item with id {depends_on}"
+    if depends_on == item_id:
+        return "An item cannot depend on itself"
+    # Walk the prerequisite chain; reaching item_id again would close a loop.
+    seen = set()
+    current = depends_on
+    while current in by_id and current not in seen:
+        if current == item_id:
+            return f"'{by_id[depends_on].product}' already depends on this item (directly or indirectly)"
+        seen.add(current)
+        current = by_id[current].depends_on
+    return None
+
+
+def purchase_plan(items: Iterable[ItemRecord]) -> List[ItemRecord]:
+    """Order items so each comes after its prerequisite; otherwise highest overall score first.
+
+    Prerequisites that no longer exist are ignored. Raises DependencyCycleError when dependencies form a cycle.
+    """
+    records = list(items)
+    by_id = {item.id: item for item in records}
+    children: Dict[str, List[ItemRecord]] = {}
+    ready = []
+    for item in records:
+        if item.depends_on in by_id and item.depends_on != item.id:
+            children.setdefault(item.depends_on, []).append(item)
+        else:
+            ready.append(item)
+
+    def rank(item: ItemRecord) -> float:
+        # Unscored items go after every scored one rather than ranking as a zero.
+        return -item.overall_score if item.overall_score is not None else float("inf")
+
+    ordered: List[ItemRecord] = []
+    ready.sort(key=rank)
+    while ready:
+        item = ready.pop(0)
+        ordered.append(item)
+        ready.extend(children.pop(item.id, []))
+        ready.sort(key=rank)
+    if children:
+        stuck = sorted(child.product for group in children.values() for child in group)
+        raise DependencyCycleError(f"Dependency cycle between: {', '.join(stuck)}")
+    return ordered
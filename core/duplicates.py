@@ -0,0 +1,74 @@
+import re
+from typing import Dict, Iterable, List, Optional, Tuple
+
+from core.models import ItemRecord, MoneyRecord
+
+
+def normalize_product(name: str) -> str:
+    """Lowercase, drop punctuation and collapse whitespace so 'Desk-Lamp ' and 'desk lamp' compare equal."""
+    return " ".join(re.sub(r"[^\w\s]", " ", name.lower()).split())
+
+
+def duplicate_groups(items: Iterable[ItemRecord], cost_tolerance: Optional[float] = None) -> List[List[ItemRecord]]:
+    """Items sharing a normalized product name, two or more per group, oldest first.
+
+    With cost_tolerance (a fraction, e.g. 0.1 for 10%), a name group is further split so each
+    cost lies within that fraction of the previous (cheaper) one.
+    """
+    by_name: Dict[str, List[ItemRecord]] = {}
+    for item in items:
+        key = normalize_product(item.product)
+        if key:
+            by_name.setdefault(key, []).append(item)
+
+    groups = []
+    for members in by_name.values():
+        clusters = [members]
+        if cost_tolerance is not None:
+            clusters = []
+            for item in sorted(members, key=lambda member: member.cost):
+                previous = clusters[-1][-1].cost if clusters else None
+                if previous is not None and item.cost - previous <= abs(previous) * cost_tolerance:
+                    clusters[-1].append(item)
+                else:
+                    clusters.append([item])
+        groups += [sorted(cluster, key=lambda member: member.date.timestamp()) for cluster in clusters if len(cluster) > 1]
+    groups.sort(key=lambda group: normalize_product(group[0].product))
+    return groups
+
+
+def merge_group(
+    group: List[ItemRecord], items: List[ItemRecord], money: List[MoneyRecord]
+) -> Tuple[ItemRecord, List[MoneyRecord]]:
+    """Fold every item in the group into its oldest member, in place.
+
+    The kept item takes the earliest date, the joined justifications and all notes; money linked
+    to the others and items that depended on them are pointed at it. The other items are removed
+    from items. Returns the kept item and the money entries that were relinked.
+    """
+    keep = min(group, key=lambda member: member.date.timestamp())
+    others = [member for member in group if member is not keep]
+    merged_ids = {member.id for member in others}
+
+    justifications = []
+    for member in [keep] + others:
+        text = member.justification.strip()
+        if text and text not in justifications:
+            justifications.append(text)
+    keep.justification = "; ".join(justifications)
+    for member in others:
+        keep.history.extend(note for note in member.history if note not in keep.history)
+    keep.add_note(f"Merged {len(others)} duplicate(s): {', '.join(sorted(merged_ids))}")
+
+    relinked = [entry for entry in money if entry.linked_item_id in merged_ids]
+    for entry in relinked:
+        entry.linked_item_id = keep.id
+        entry.touch()
+    for item in items:
+        if item.depends_on in merged_ids:
+            item.depends_on = keep.id
+            item.touch()
+    if keep.depends_on in merged_ids or keep.depends_on == keep.id:
+        keep.depends_on = ""
+    items[:] = [item for item in items if item.id not in merged_ids]
+    return keep, relinked
@@ -0,0 +1,53 @@
+"""Error types shared by the storage, backup, and planning layers.
+
+Each one also derives from the builtin exception it replaced, so existing `except ValueError`
+(or `TimeoutError`, `LookupError`, ...) handlers keep working while the UI can match on the type
+to offer a fitting recovery: retry on a lock, restore a backup on a parse failure.
+"""
+
+from typing import List, Optional
+
+
+class FinancePlannerError(Exception):
+    """Base class for the app's own errors."""
+
+
+class DataFileLockedError(FinancePlannerError, TimeoutError):
+    """Another process kept a data file locked past the configured timeout."""
+
+    def __init__(self, path: str, message: str) -> None:
+        super().__init__(message)
+        self.path = path
+
+
+class DataFileParseError(FinancePlannerError, ValueError):
+    """A data file exists but its contents could not be read as records."""
+
+    def __init__(self, path: str, message: str, line: Optional[int] = None) -> None:
+        super().__init__(message)
+        self.path = path
+        self.line = line
+
+
+class RecordNotFoundError(FinancePlannerError, LookupError):
+    """No item or money entry has the requested id."""
+
+
+class AmbiguousIdError(FinancePlannerError, LookupError):
+    """An id prefix matches more than one record."""
+
+    def __init__(self, message: str, candidates: List[str]) -> None:
+        super().__init__(message)
+        self.candidates = candidates
+
+
+class DependencyCycleError(FinancePlannerError, ValueError):
+    """Item prerequisites loop back on themselves."""
+
+
+class InvalidWeightsError(FinancePlannerError, ValueError):
+    """Weights that would score inconsistently, such as cost bands out of order."""
+
+
+class BackupSourceMissingError(FinancePlannerError, FileNotFoundError):
+    """The file to back up does not exist."""
@@ -0,0 +1,207 @@
+"""Filter expressions over items, e.g. `cost>100 and recurrence=monthly`.
+
+Comparisons are `field op value` with `=`, `!=`, `>`, `>=`, `<`, `<=`, `~` (contains) and `!~`.
+They combine with `and`, `or`, `not` and parentheses; `and` binds tighter than `or`, and adjacent
+terms without a keyword are and-ed. A bare word matches the product name as a substring. Text
+compares case-insensitively, dates compare by day (YYYY-MM-DD), and `field=""` tests for an empty
+value. Values containing spaces or operator characters can be quoted.
+"""
+
+import re
+from typing import Callable, List, Optional, Tuple
+
+from core.models import ItemRecord, parse_date
+
+Predicate = Callable[[ItemRecord], bool]
+
+NUMERIC_FIELDS = {
+    "cost",
+    "urgency",
+    "value",
+    "want",
+    "price_comp",
+    "effect",
+    "overall_score",
+    "expected_uses",
+    "effort",
+}
+DATE_FIELDS = {"date", "target_date", "created_at", "updated_at"}
+FIELD_ALIASES = {"overall": "overall_score", "score": "overall_score"}
+
+_TOKEN = re.compile(
+    r"""\s*(?:
+        (?P<paren>[()])
+      | (?P<op>>=|<=|!=|==|!~|=|>|<|~)
+      | (?P<quoted>"(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*')
+      | (?P<word>[^\s()=<>!~"']+)
+    )""",
+    re.VERBOSE,
+)
+_KEYWORDS = {"and", "or", "not"}
+
+
+class FilterError(ValueError):
+    """Raised for an expression that cannot be parsed; the message points at the offending text."""
+
+
+def filter_fields() -> List[str]:
+    return [h for h in ItemRecord.headers() if h != "history"] + sorted(FIELD_ALIASES)
+
+
+def _tokenize(text: str) -> List[Tuple[str, str, int]]:
+    tokens = []
+    pos = 0
+    text = text.rstrip()
+    while pos < len(text):
+        match = _TOKEN.match(text, pos)
+        if not match or match.end() == pos:
+            raise FilterError(f"unexpected character at position {pos + 1}: '{text[pos:].strip()[:10]}'")
+        kind = match.lastgroup or ""
+        value = match.group(kind)
+        start = match.start(kind) + 1
+        if kind == "quoted":
+            value = re.sub(r"\\(.)", r"\1", value[1:-1])
+        elif kind == "word" and value.lower() in _KEYWORDS:
+            kind, value = "keyword", value.lower()
+        tokens.append((kind, value, start))
+        pos = match.end()
+    return tokens
+
+
+class _Parser:
+    def __init__(self, text: str) -> None:
+        self.tokens = _tokenize(text)
+        self.index = 0
+
+    def peek(self) -> Optional[Tuple[str, str, int]]:
+        return self.tokens[self.index] if self.index < len(self.tokens) else None
+
+    def take(self) -> Tuple[str, str, int]:
+        token = self.peek()
+        if token is None:
+            raise FilterError("expression ends too early")
+        self.index += 1
+        return token
+
+    def parse(self) -> Predicate:
+        if not self.tokens:
+            return lambda item: True
+        predicate = self.parse_or()
+        token = self.peek()
+        if token:
+            raise FilterError(f"unexpected '{token[1]}' at position {token[2]}")
+        return predicate
+
+    def parse_or(self) -> Predicate:
+        parts = [self.parse_and()]
+        while self.peek() and self.peek()[:2] == ("keyword", "or"):
+            self.take()
+            parts.append(self.parse_and())
+        return parts[0] if len(parts) == 1 else (lambda item: any(p(item) for p in parts))
+
+    def parse_and(self) -> Predicate:
+        parts = [self.parse_not()]
+        while True:
+            token = self.peek()
+            if token is None or token[:2] in (("keyword", "or"), ("paren", ")")):
+                break
+            if token[:2] == ("keyword", "and"):
+                self.take()
+            parts.append(self.parse_not())
+        return parts[0] if len(parts) == 1 else (lambda item: all(p(item) for p in parts))
+
+    def parse_not(self) -> Predicate:
+        if self.peek() and self.peek()[:2] == ("keyword", "not"):
+            self.take()
+            inner = self.parse_not()
+            return lambda item: not inner(item)
+        return self.parse_atom()
+
+    def parse_atom(self) -> Predicate:
+        kind, value, position = self.take()
+        if (kind, value) == ("paren", "("):
+            inner = self.parse_or()
+            closing = self.peek()
+            if not closing or closing[:2] != ("paren", ")"):
+                raise FilterError(f"missing ')' for '(' at position {position}")
+            self.take()
+            return inner
+        if kind not in ("word", "quoted"):
+            raise FilterError(f"unexpected '{value}' at position {position}")
+        following = self.peek()
+        if kind == "word" and following and following[0] == "op":
+            self.take()
+            operand = self.take()
+            if operand[0] not in ("word", "quoted"):
+                raise FilterError(f"expected a value after '{following[1]}' at position {operand[2]}")
+            return _comparison(value, following[1], operand[1], position)
+        needle = value.lower()
+        return lambda item: needle in item.product.lower()
+
+
+def _comparison(field: str, op: str, raw: str, position: int) -> Predicate:
+    name = FIELD_ALIASES.get(field.lower(), field.lower())
+    if name not in ItemRecord.headers() or name == "history":
+        raise FilterError(f"unknown field '{field}' at position {position} (try: {', '.join(filter_fields())})")
+    op = "=" if op == "==" else op
+    if raw == "" and op in ("=", "!="):
+        wanted_empty = op == "="
+        return lambda item: (getattr(item, name) in (None, "")) == wanted_empty
+
+    if name in NUMERIC_FIELDS or name in DATE_FIELDS:
+        if op in ("~", "!~"):
+            raise FilterError(f"'{op}' only applies to text fields, not '{field}'")
+        if name in DATE_FIELDS:
+            try:
+                target = parse_date(raw).date()
+            except ValueError as exc:
+                raise FilterError(f"{exc} for '{field}'") from None
+
+            def read(item: ItemRecord):
+                value = getattr(item, name)
+                return value.date() if value is not None else None
+
+        else:
+            try:
+                target = float(raw)
+            except ValueError:
+                raise FilterError(f"'{field}' needs a number, got '{raw}'") from None
+
+            def read(item: ItemRecord):
+                value = getattr(item, name)
+                return float(value) if value is not None else None
+
+        compare = _ORDERED[op]
+
+        def matches(item: ItemRecord) -> bool:
+            value = read(item)
+            # Unset values (no score, no target date) only satisfy '!='.
+            if value is None:
+                return op == "!="
+            return compare(value, target)
+
+        return matches
+
+    if op in (">", ">=", "<", "<="):
+        raise FilterError(f"'{op}' needs a numeric or date field, not '{field}'")
+    needle = raw.lower()
+    if op in ("~", "!~"):
+        contains = op == "~"
+        return lambda item: (needle in str(getattr(item, name)).lower()) == contains
+    equal = op == "="
+    return lambda item: (str(getattr(item, name)).lower() == needle) == equal
+
+
+_ORDERED = {
+    "=": lambda a, b: a == b,
+    "!=": lambda a, b: a != b,
+    ">": lambda a, b: a > b,
+    ">=": lambda a, b: a >= b,
+    "<": lambda a, b: a < b,
+    "<=": lambda a, b: a <= b,
+}
+
+
+def parse_filter(text: str) -> Predicate:
+    """Compile an expression into a predicate; an empty expression matches every item."""
+    return _Parser(text).parse()
@@ -0,0 +1,69 @@
+from dataclasses import dataclass
+from datetime import datetime
+from typing import Dict, Iterable, Iterator, List, Optional
+
+from core.models import RECURRENCE_PER_YEAR, ItemRecord, MoneyRecord
+from core.periods import Period, period_label, period_of, period_start, shift_period
+from core.recurrence import occurrences
+
+
+@dataclass
+class MonthProjection:
+    month: str
+    income: float
+    expenses: float
+    net: float
+    balance: float
+
+
+def item_occurrences(item: ItemRecord, start: datetime, end: datetime) -> Iterator[datetime]:
+    """Dates in (start, end) on which a recurring item is paid, counting forward from its own date."""
+    recurrence = item.recurrence_kind()
+    if recurrence not in RECURRENCE_PER_YEAR:
+        return
+    for when in occurrences(item.date, recurrence):
+        if when >= end:
+            return
+        if when > start:
+            yield when
+
+
+def cashflow_projection(
+    items: Iterable[ItemRecord],
+    money: Iterable[MoneyRecord],
+    months: int,
+    start: Optional[datetime] = None,
+    start_day: int = 1,
+) -> List[MonthProjection]:
+    """Project monthly income, expenses, and running balance for the next `months` months.
+
+    Months start on start_day (see core.periods). Assumptions: the opening balance is the ledger
+    balance at `start`; every recurring item is an expense of its cost on each occurrence; money
+    entries dated after `start` are treated as scheduled and counted in their month. The first
+    month is the current one (from `start` on).
+    """
+    start = start or datetime.now()
+    current = period_of(start, start_day)
+    end = period_start(shift_period(current, months), start_day)
+    buckets: Dict[Period, List[float]] = {shift_period(current, offset): [0.0, 0.0] for offset in range(months)}
+
+    entries = list(money)
+    balance = sum(e.signed_amount() for e in entries if e.date <= start)
+    for entry in entries:
+        if start < entry.date < end:
+            flow = entry.signed_amount()
+            bucket = buckets[period_of(entry.date, start_day)]
+            if flow >= 0:
+                bucket[0] += flow
+            else:
+                bucket[1] -= flow
+    for item in items:
+        for when in item_occurrences(item, start, end):
+            buckets[period_of(when, start_day)][1] += item.cost
+
+    projection = []
+    for period, (income, expenses) in buckets.items():
+        net = income - expenses
+        balance += net
+        projection.append(MonthProjection(period_label(period), income, expenses, net, balance))
+    return projection
@@ -0,0 +1,80 @@
+"""Merging imported records into the existing ones by id, deciding what happens when both sides changed.
+
+An imported record whose id already exists with different field values is a conflict. By default
+the imported version replaces the existing one, as plain merge imports always did; the other
+strategies keep the existing version or the one with the later date, or (in the app) ask per record.
+"""
+
+from dataclasses import dataclass, field
+from typing import Dict, Iterable, List
+
+CONFLICT_STRATEGIES = ("keep-theirs", "keep-mine", "newest", "prompt")
+# Recomputed from the weights on import, so a different stored score is not a disagreement; edit
+# times differ whenever anything else does, so on their own they are not one either.
+_IGNORED_FIELDS = {"overall_score", "created_at", "updated_at"}
+
+
+@dataclass
+class Conflict:
+    mine: object
+    theirs: object
+    fields: List[str] = field(default_factory=list)
+    # "mine" or "theirs" once resolved, with a short reason for the report.
+    keep: str = ""
+    reason: str = ""
+
+    @property
+    def id(self) -> str:
+        return self.mine.id
+
+    def values(self) -> List[tuple]:
+        """(field, mine, theirs) for every field that differs, as the CSV would show them."""
+        mine, theirs = self.mine.to_row(), self.theirs.to_row()
+        return [(name, str(mine.get(name, "")), str(theirs.get(name, ""))) for name in self.fields]
+
+    def describe(self) -> str:
+        kept = "kept yours" if self.keep == "mine" else "took the import's"
+        return f"{self.id}: {kept} ({self.reason}); differs in {', '.join(self.fields)}"
+
+
+def find_conflicts(existing: Iterable, imported: Iterable) -> List[Conflict]:
+    """Imported records whose id exists with different values, in import order."""
+    by_id = {record.id: record for record in existing}
+    conflicts = []
+    for record in imported:
+        current = by_id.get(record.id)
+        if current is None:
+            continue
+        mine, theirs = current.to_row(), record.to_row()
+        fields = [name for name in mine if name not in _IGNORED_FIELDS and mine[name] != theirs.get(name)]
+        if fields:
+            conflicts.append(Conflict(current, record, fields))
+    return conflicts
+
+
+def resolve_conflicts(conflicts: Iterable[Conflict], strategy: str) -> None:
+    """Decide every conflict by keep-theirs, keep-mine or newest; prompt is left to the caller.
+
+    newest keeps whichever record has the later date and keeps the existing one on a tie.
+    """
+    for conflict in conflicts:
+        if strategy == "keep-mine":
+            conflict.keep, conflict.reason = "mine", strategy
+        elif strategy == "newest":
+            newer = conflict.theirs.date > conflict.mine.date
+            conflict.keep = "theirs" if newer else "mine"
+            conflict.reason = "newest: import is dated later" if newer else "newest: yours is not older"
+        elif strategy == "keep-theirs":
+            conflict.keep, conflict.reason = "theirs", strategy
+        else:
+            raise ValueError(f"Unknown conflict strategy '{strategy}'; use {', '.join(CONFLICT_STRATEGIES[:3])}")
+
+
+def merge_records(existing: Iterable, imported: Iterable, conflicts: Iterable[Conflict] = ()) -> list:
+    """existing with imported merged in by id; conflicts resolved to "mine" keep the existing record."""
+    kept: Dict[str, str] = {conflict.id: conflict.keep for conflict in conflicts}
+    merged = {record.id: record for record in existing}
+    for record in imported:
+        if kept.get(record.id) != "mine":
+            merged[record.id] = record
+    return list(merged.values())
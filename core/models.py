@@ -1,10 +1,83 @@
-from dataclasses import dataclass
-from datetime import datetime
-from typing import Dict, Optional
+import json
+import uuid
+from dataclasses import dataclass, field
+from datetime import datetime, timezone
+from typing import Dict, List, Optional
+from urllib.parse import urlparse
 
 
 DATE_FMT = "%Y-%m-%d %H:%M"
 
+def parse_date(text: str) -> datetime:
+    """Parse a user-supplied date given either as YYYY-MM-DD or in the storage format."""
+    for fmt in (DATE_FMT, "%Y-%m-%d"):
+        try:
+            return datetime.strptime(text.strip(), fmt)
+        except ValueError:
+            continue
+    raise ValueError(f"invalid date '{text}' (expected YYYY-MM-DD)")
+
+
+def format_stored_date(value: datetime) -> str:
+    """Serialize a local datetime with its UTC offset, e.g. 2025-01-31 09:30+01:00."""
+    return value.astimezone().isoformat(sep=" ", timespec="minutes")
+
+
+def parse_stored_date(text: str, date_format: Optional[str] = None) -> datetime:
+    """Parse a stored date into naive local time.
+
+    Values carrying an offset are converted to this machine's timezone, so the instant is preserved
+    when a file moves between timezones. Values without one (files written before offsets were
+    stored) are taken as local time and gain an offset on the next save.
+    """
+    if date_format:
+        return datetime.strptime(text, date_format)
+    try:
+        parsed = datetime.fromisoformat(text.strip())
+    except ValueError:
+        raise ValueError(f"invalid date '{text}'") from None
+    if parsed.tzinfo is not None:
+        parsed = parsed.astimezone().replace(tzinfo=None)
+    return parsed
+
+
+# Occurrences per year for each recurrence option offered in the item form.
+RECURRENCE_PER_YEAR = {
+    "weekly": 52,
+    "biweekly": 26,
+    "monthly": 12,
+    "quarterly": 4,
+    "yearly": 1,
+}
+
+UNCATEGORIZED = "Uncategorized"
+
+# What the user decided about an item, beyond its score; items without one are undecided.
+DECISIONS = ("buy", "skip", "defer")
+
+# Shown in place of an overall score for items that have not been scored yet (e.g. fresh imports).
+UNSCORED_MARK = "—"
+
+
+def format_score(score: Optional[float]) -> str:
+    return UNSCORED_MARK if score is None else f"{score:.2f}"
+
+# Fixed namespace for stable_id(); changing it would change every derived id.
+ID_NAMESPACE = uuid.UUID("6f1c0e5a-8d3b-5b7e-9a41-2c7d9e0f4b13")
+
+
+def new_id() -> str:
+    """A random id for records created interactively."""
+    return str(uuid.uuid4())
+
+
+def _utc_stamp(value: datetime) -> str:
+    return value.astimezone(timezone.utc).strftime(DATE_FMT)
+
+
+def _stable_uuid(kind: str, *parts: str) -> str:
+    return str(uuid.uuid5(ID_NAMESPACE, "\x1f".join((kind, *parts))))
+
 
 @dataclass
 class ItemRecord:
@@ -23,6 +96,40 @@ class ItemRecord:
     justification: str
     recurrence: str = ""
     overall_score: Optional[float] = None
+    history: List[str] = field(default_factory=list)
+    expected_uses: Optional[int] = None
+    target_date: Optional[datetime] = None
+    # Id of another item that should be bought first; empty when there is none.
+    depends_on: str = ""
+    # 1 (little) to 5 (lots) of effort to buy or set up; None when not rated.
+    effort: Optional[int] = None
+    # Path to a picture of the item; only the path is stored, never the image.
+    image_path: str = ""
+    # One of DECISIONS, or empty while undecided.
+    decision: str = ""
+    # When the record was added and last edited; set in __post_init__ when not given.
+    created_at: Optional[datetime] = None
+    updated_at: Optional[datetime] = None
+
+    # Columns added after the original schema; older files may omit them.
+    _OPTIONAL_HEADERS = (
+        "history",
+        "expected_uses",
+        "target_date",
+        "depends_on",
+        "effort",
+        "image_path",
+        "decision",
+        "created_at",
+        "updated_at",
+    )
+
+    def __post_init__(self) -> None:
+        # New records are stamped now; from_row passes the stored times, or the date for older files.
+        if self.created_at is None:
+            self.created_at = datetime.now()
+        if self.updated_at is None:
+            self.updated_at = self.created_at
 
     @classmethod
     def headers(cls) -> list[str]:
@@ -42,32 +149,62 @@ class ItemRecord:
             "justification",
             "recurrence",
             "overall_score",
+            "history",
+            "expected_uses",
+            "target_date",
+            "depends_on",
+            "effort",
+            "image_path",
+            "decision",
+            "created_at",
+            "updated_at",
         ]
 
     @classmethod
-    def from_row(cls, row: Dict[str, str], date_format: str = DATE_FMT) -> "ItemRecord":
-        return cls(
-            id=row["id"],
-            date=datetime.strptime(row["date"], date_format),
-            product=row.get("product", ""),
-            description=row.get("description", ""),
-            location=row.get("location", ""),
-            reference=row.get("reference", ""),
+    def required_headers(cls) -> list[str]:
+        return [h for h in cls.headers() if h not in cls._OPTIONAL_HEADERS]
+
+    @classmethod
+    def from_row(cls, row: Dict[str, str], date_format: Optional[str] = None) -> "ItemRecord":
+        record = cls(
+            id=row["id"].strip(),
+            date=parse_stored_date(row["date"], date_format),
+            product=row.get("product") or "",
+            description=row.get("description") or "",
+            location=row.get("location") or "",
+            reference=row.get("reference") or "",
             cost=float(row.get("cost", "0") or 0),
             urgency=int(row.get("urgency", "1") or 1),
             value=int(row.get("value", "1") or 1),
             want=int(row.get("want", "3") or 3),
             price_comp=int(row.get("price_comp", "1") or 1),
             effect=int(row.get("effect", "1") or 1),
-            justification=row.get("justification", ""),
-            recurrence=row.get("recurrence", ""),
+            justification=row.get("justification") or "",
+            recurrence=row.get("recurrence") or "",
             overall_score=float(row["overall_score"]) if row.get("overall_score") else None,
+            history=_decode_history(row.get("history") or ""),
+            expected_uses=int(row["expected_uses"]) if row.get("expected_uses") else None,
+            target_date=parse_stored_date(row["target_date"], date_format) if row.get("target_date") else None,
+            depends_on=(row.get("depends_on") or "").strip(),
+            effort=_parse_effort(row.get("effort") or ""),
+            image_path=(row.get("image_path") or "").strip(),
+            decision=_parse_decision(row.get("decision") or ""),
+        )
+        _read_timestamps(record, row, date_format)
+        if not record.id:
+            record.id = record.stable_id()
+        return record
+
+    def stable_id(self) -> str:
+        """Deterministic id derived from the identifying fields, so re-importing the same row yields the same id."""
+        return _stable_uuid(
+            "item", _utc_stamp(self.date), self.product, self.location, self.reference, f"{self.cost:.2f}"
         )
 
-    def to_row(self, date_format: str = DATE_FMT) -> Dict[str, str]:
+    def to_row(self, date_format: Optional[str] = None) -> Dict[str, str]:
         return {
             "id": self.id,
-            "date": self.date.strftime(date_format),
+            "date": self.date.strftime(date_format) if date_format else format_stored_date(self.date),
             "product": self.product,
             "description": self.description,
             "location": self.location,
@@ -81,8 +218,58 @@ class ItemRecord:
             "justification": self.justification,
             "recurrence": self.recurrence,
             "overall_score": f"{self.overall_score:.2f}" if self.overall_score is not None else "",
+            "history": json.dumps(self.history, ensure_ascii=False) if self.history else "",
+            "expected_uses": str(self.expected_uses) if self.expected_uses else "",
+            "target_date": (
+                self.target_date.strftime(date_format) if date_format else format_stored_date(self.target_date)
+            )
+            if self.target_date
+            else "",
+            "depends_on": self.depends_on,
+            "effort": str(self.effort) if self.effort else "",
+            "image_path": self.image_path,
+            "decision": self.decision,
+            **_timestamp_columns(self, date_format),
         }
 
+    def cost_per_use(self) -> Optional[float]:
+        """Cost amortized over the expected number of uses, or None when no estimate is set."""
+        if not self.expected_uses or self.expected_uses <= 0:
+            return None
+        return self.cost / self.expected_uses
+
+    def reference_url(self) -> Optional[str]:
+        """The reference as a URL when it is an absolute http(s) link, otherwise None."""
+        text = self.reference.strip()
+        parsed = urlparse(text)
+        if parsed.scheme.lower() in ("http", "https") and parsed.netloc:
+            return text
+        return None
+
+    def recurrence_kind(self) -> str:
+        """The recurrence lowercased, with a blank value read as "none"."""
+        return self.recurrence.strip().lower() or "none"
+
+    def is_recurring(self) -> bool:
+        """True for items that repeat (weekly through yearly); "none" and "once" do not."""
+        return self.recurrence_kind() in RECURRENCE_PER_YEAR
+
+    def annualized_cost(self) -> float:
+        """Cost over a year for recurring items; one-off items return their single cost."""
+        return self.cost * RECURRENCE_PER_YEAR.get(self.recurrence_kind(), 1)
+
+    def add_note(self, text: str, when: Optional[datetime] = None) -> str:
+        """Append a timestamped note; existing history entries are never rewritten."""
+        stamp = format_stored_date(when or datetime.now().astimezone())
+        note = f"[{stamp}] {text.strip()}"
+        self.history.append(note)
+        self.touch()
+        return note
+
+    def touch(self) -> None:
+        """Record a change made by the user; recomputed scores do not count."""
+        self.updated_at = datetime.now()
+
 
 @dataclass
 class MoneyRecord:
@@ -93,6 +280,23 @@ class MoneyRecord:
     amount: float
     notes: str = ""
     linked_item_id: str = ""
+    category: str = ""
+    # Id of the entry this one was split from; parts of the same split share it.
+    split_group: str = ""
+    # Marked once the entry shows up on a bank statement; older files have every entry uncleared.
+    cleared: bool = False
+    # When the entry was added and last edited; set in __post_init__ when not given.
+    created_at: Optional[datetime] = None
+    updated_at: Optional[datetime] = None
+
+    _OPTIONAL_HEADERS = ("category", "split_group", "cleared", "created_at", "updated_at")
+
+    def __post_init__(self) -> None:
+        # New records are stamped now; from_row passes the stored times, or the date for older files.
+        if self.created_at is None:
+            self.created_at = datetime.now()
+        if self.updated_at is None:
+            self.updated_at = self.created_at
 
     @classmethod
     def headers(cls) -> list[str]:
@@ -104,27 +308,117 @@ class MoneyRecord:
             "amount",
             "notes",
             "linked_item_id",
+            "category",
+            "split_group",
+            "cleared",
+            "created_at",
+            "updated_at",
         ]
 
     @classmethod
-    def from_row(cls, row: Dict[str, str], date_format: str = DATE_FMT) -> "MoneyRecord":
-        return cls(
-            id=row["id"],
-            date=datetime.strptime(row["date"], date_format),
-            entry_type=row.get("entry_type", "income"),
-            source_or_destination=row.get("source_or_destination", ""),
+    def required_headers(cls) -> list[str]:
+        return [h for h in cls.headers() if h not in cls._OPTIONAL_HEADERS]
+
+    def category_label(self) -> str:
+        """The category for grouping, with blank categories bucketed together."""
+        return self.category.strip() or UNCATEGORIZED
+
+    def signed_amount(self) -> float:
+        """Amount as a cash flow: positive for income, negative for expense, zero for unknown types."""
+        kind = self.entry_type.strip().lower()
+        if kind == "income":
+            return abs(self.amount)
+        if kind == "expense":
+            return -abs(self.amount)
+        return 0.0
+
+    @classmethod
+    def from_row(cls, row: Dict[str, str], date_format: Optional[str] = None) -> "MoneyRecord":
+        record = cls(
+            id=row["id"].strip(),
+            date=parse_stored_date(row["date"], date_format),
+            entry_type=row.get("entry_type") or "income",
+            source_or_destination=row.get("source_or_destination") or "",
             amount=float(row.get("amount", "0") or 0),
-            notes=row.get("notes", ""),
-            linked_item_id=row.get("linked_item_id", ""),
+            notes=row.get("notes") or "",
+            linked_item_id=row.get("linked_item_id") or "",
+            category=row.get("category") or "",
+            split_group=(row.get("split_group", "") or "").strip(),
+            cleared=(row.get("cleared", "") or "").strip().lower() in ("1", "true", "yes", "y", "x"),
         )
+        _read_timestamps(record, row, date_format)
+        if not record.id:
+            record.id = record.stable_id()
+        return record
 
-    def to_row(self, date_format: str = DATE_FMT) -> Dict[str, str]:
+    def stable_id(self) -> str:
+        """Deterministic id from date, type, counterparty, amount, and notes."""
+        return _stable_uuid(
+            "money",
+            _utc_stamp(self.date),
+            self.entry_type,
+            self.source_or_destination,
+            f"{self.amount:.2f}",
+            self.notes,
+        )
+
+    def to_row(self, date_format: Optional[str] = None) -> Dict[str, str]:
         return {
             "id": self.id,
-            "date": self.date.strftime(date_format),
+            "date": self.date.strftime(date_format) if date_format else format_stored_date(self.date),
             "entry_type": self.entry_type,
             "source_or_destination": self.source_or_destination,
             "amount": f"{self.amount:.2f}",
             "notes": self.notes,
             "linked_item_id": self.linked_item_id,
+            "category": self.category,
+            "split_group": self.split_group,
+            "cleared": "yes" if self.cleared else "",
+            **_timestamp_columns(self, date_format),
         }
+
+    def touch(self) -> None:
+        """Record a change made by the user; recomputed scores do not count."""
+        self.updated_at = datetime.now()
+
+
+def _read_timestamps(record, row: Dict[str, str], date_format: Optional[str]) -> None:
+    """created_at / updated_at from the row; files written before these columns use the record's date."""
+    created = (row.get("created_at") or "").strip()
+    updated = (row.get("updated_at") or "").strip()
+    record.created_at = parse_stored_date(created, date_format) if created else record.date
+    record.updated_at = parse_stored_date(updated, date_format) if updated else record.created_at
+
+
+def _timestamp_columns(record, date_format: Optional[str]) -> Dict[str, str]:
+    # To the second, unlike dates, so edits made within the same minute still sort in order.
+    return {
+        key: value.strftime(date_format) if date_format else value.astimezone().isoformat(sep=" ", timespec="seconds")
+        for key, value in (("created_at", record.created_at), ("updated_at", record.updated_at))
+    }
+
+
+def _parse_decision(raw: str) -> str:
+    decision = (raw or "").strip().lower()
+    if decision and decision not in DECISIONS:
+        raise ValueError(f"decision must be one of {', '.join(DECISIONS)} or empty, got '{raw}'")
+    return decision
+
+
+def _parse_effort(raw: str) -> Optional[int]:
+    # Out-of-range values would push the inverted effort score, and so the overall, off the 1-5 scale.
+    if not raw.strip():
+        return None
+    effort = int(raw)
+    if not 1 <= effort <= 5:
+        raise ValueError(f"effort must be 1-5 or empty, got '{raw}'")
+    return effort
+
+
+def _decode_history(raw: str) -> List[str]:
+    if not raw:
+        return []
+    decoded = json.loads(raw)
+    if not isinstance(decoded, list):
+        raise ValueError("history must be a JSON list of notes")
+    return [str(entry) for entry in decoded]
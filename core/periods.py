@@ -0,0 +1,33 @@
+"""Budget months that start on a chosen day (e.g. payday on the 25th) instead of the 1st.
+
+A period is named after the calendar month it starts in: with a start day of 25, "2025-01" runs
+from Jan 25 up to Feb 25. A start day past a month's end clamps to its last day, so with 31 the
+"2025-02" period starts on Feb 28 and runs up to Mar 31. A start day of 1 gives calendar months.
+"""
+
+import calendar
+from datetime import datetime
+from typing import Tuple
+
+Period = Tuple[int, int]
+
+
+def period_start(period: Period, start_day: int = 1) -> datetime:
+    """Midnight on the day the (year, month) period begins."""
+    year, month = period
+    return datetime(year, month, min(start_day, calendar.monthrange(year, month)[1]))
+
+
+def shift_period(period: Period, months: int) -> Period:
+    total = period[0] * 12 + period[1] - 1 + months
+    return total // 12, total % 12 + 1
+
+
+def period_of(date: datetime, start_day: int = 1) -> Period:
+    """The (year, month) period containing date."""
+    period = (date.year, date.month)
+    return period if date >= period_start(period, start_day) else shift_period(period, -1)
+
+
+def period_label(period: Period) -> str:
+    return f"{period[0]:04d}-{period[1]:02d}"
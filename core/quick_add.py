@@ -0,0 +1,93 @@
+"""One-line item shorthand for the quick-add bar: `Desk lamp @39.99 #online #monthly !4`.
+
+`@` sets the cost, `!` the urgency (1-5) and `#` either the recurrence (when it names one) or the
+location. Everything else is the product name; ratings left out come from the defaults passed to
+QuickItem.to_record (the `ui.item_defaults` setting in the app).
+"""
+
+import re
+from dataclasses import dataclass
+from datetime import datetime
+from typing import Dict, List, Optional
+
+from core.models import ItemRecord, new_id
+from core.recurrence import DAY_STEPS, MONTH_STEPS
+
+RECURRENCE_TAGS = ("once",) + tuple(DAY_STEPS) + tuple(MONTH_STEPS)
+
+
+@dataclass
+class QuickItem:
+    product: str
+    cost: float = 0.0
+    urgency: Optional[int] = None
+    location: str = ""
+    recurrence: str = ""
+
+    def describe(self, currency_symbol: str = "$") -> str:
+        """A one-line preview of what Enter would add."""
+        parts = [self.product, f"{currency_symbol}{self.cost:,.2f}"]
+        if self.urgency is not None:
+            parts.append(f"urgency {self.urgency}")
+        if self.location:
+            parts.append(f"at {self.location}")
+        if self.recurrence:
+            parts.append(self.recurrence)
+        return " · ".join(parts)
+
+    def to_record(self, ratings: Dict[str, int]) -> ItemRecord:
+        """The item, with ratings (urgency, value, want, price_comp, effect) filling what was not typed."""
+        return ItemRecord(
+            id=new_id(),
+            date=datetime.now(),
+            product=self.product,
+            description="",
+            location=self.location,
+            reference="",
+            cost=self.cost,
+            urgency=ratings["urgency"] if self.urgency is None else self.urgency,
+            value=ratings["value"],
+            want=ratings["want"],
+            price_comp=ratings["price_comp"],
+            effect=ratings["effect"],
+            justification="",
+            recurrence=self.recurrence,
+        )
+
+
+def parse_quick_add(text: str) -> QuickItem:
+    """Parse the shorthand; ValueError names the first token that does not make sense."""
+    words: List[str] = []
+    costs: List[float] = []
+    urgencies: List[int] = []
+    locations: List[str] = []
+    recurrences: List[str] = []
+    for token in text.split():
+        marker, rest = token[0], token[1:]
+        if marker == "@":
+            try:
+                costs.append(float(re.sub(r"[^\d.\-]", "", rest)))
+            except ValueError:
+                raise ValueError(f"'{token}' is not a cost; write it like @39.99") from None
+            if costs[-1] < 0:
+                raise ValueError(f"'{token}': cost cannot be negative")
+        elif marker == "!":
+            if not rest.isdigit() or not 1 <= int(rest) <= 5:
+                raise ValueError(f"'{token}' is not an urgency; use !1 to !5")
+            urgencies.append(int(rest))
+        elif marker == "#" and rest:
+            (recurrences if rest.lower() in RECURRENCE_TAGS else locations).append(rest)
+        else:
+            words.append(token)
+    for label, values in (("cost", costs), ("urgency", urgencies), ("location", locations), ("recurrence", recurrences)):
+        if len(values) > 1:
+            raise ValueError(f"More than one {label}: {', '.join(str(value) for value in values)}")
+    if not words:
+        raise ValueError("Type a product name, e.g. Desk lamp @39.99 #online !4")
+    return QuickItem(
+        product=" ".join(words),
+        cost=costs[0] if costs else 0.0,
+        urgency=urgencies[0] if urgencies else None,
+        location=locations[0] if locations else "",
+        recurrence=recurrences[0].lower() if recurrences else "",
+    )
@@ -0,0 +1,42 @@
+"""Calendar arithmetic for recurring items, shared by forecasting and anything else that schedules.
+
+Month-based steps clamp to the last day of a shorter month (Jan 31 -> Feb 28, or Feb 29 in a leap
+year) instead of adding a fixed number of days, so a monthly series never drifts.
+"""
+
+import calendar
+from datetime import datetime, timedelta
+from typing import Iterator, Optional
+
+MONTH_STEPS = {"monthly": 1, "quarterly": 3, "yearly": 12}
+DAY_STEPS = {"weekly": 7, "biweekly": 14}
+
+
+def add_months(date: datetime, months: int, day: Optional[int] = None) -> datetime:
+    """date moved by whole calendar months, on `day` (default: date's own day) or the month's last day if shorter."""
+    total = date.month - 1 + months
+    year, month = date.year + total // 12, total % 12 + 1
+    day = min(day or date.day, calendar.monthrange(year, month)[1])
+    return date.replace(year=year, month=month, day=day)
+
+
+def next_occurrence(date: datetime, recurrence: str, day: Optional[int] = None) -> datetime:
+    """The occurrence after date for a weekly/biweekly/monthly/quarterly/yearly recurrence.
+
+    Pass the series' original day of the month as `day` so a clamped date (Feb 28 after Jan 31)
+    returns to the 31st in longer months. ValueError for recurrences that do not repeat.
+    """
+    recurrence = recurrence.lower()
+    if recurrence in DAY_STEPS:
+        return date + timedelta(days=DAY_STEPS[recurrence])
+    if recurrence in MONTH_STEPS:
+        return add_months(date, MONTH_STEPS[recurrence], day)
+    raise ValueError(f"'{recurrence}' does not repeat")
+
+
+def occurrences(first: datetime, recurrence: str) -> Iterator[datetime]:
+    """first and every later occurrence, without end; month steps keep first's day of the month."""
+    when = first
+    while True:
+        yield when
+        when = next_occurrence(when, recurrence, first.day)
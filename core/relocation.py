@@ -0,0 +1,91 @@
+"""Notice when settings point the data files somewhere new, so the old data is not silently left behind.
+
+The paths used on the last run are kept in last_data_paths.json next to settings.json; a path that
+changed while its old file still holds records and the new one holds none is offered for moving.
+"""
+
+import json
+import os
+import shutil
+from typing import Any, Dict, List, Tuple
+
+from core.backup import create_backup
+from core.csv_storage import read_items, read_money
+from core.errors import DataFileParseError
+
+STATE_FILE = "last_data_paths.json"
+DATA_PATH_KEYS = ("items_csv", "money_csv", "data_json")
+
+
+def state_path(settings_path: str) -> str:
+    return os.path.join(os.path.dirname(settings_path), STATE_FILE)
+
+
+def _load(path: str) -> Dict[str, str]:
+    try:
+        with open(path, "r", encoding="utf-8") as f:
+            data = json.load(f)
+    except (OSError, ValueError):
+        # Without a record of the last run there is nothing to compare against; it is written again below.
+        return {}
+    return {key: value for key, value in data.items() if isinstance(value, str)} if isinstance(data, dict) else {}
+
+
+def _keys_in_use(settings: Dict[str, Any]) -> Tuple[str, ...]:
+    if settings.get("storage", {}).get("format") == "json":
+        return ("data_json",)
+    return ("items_csv", "money_csv")
+
+
+def _has_records(key: str, path: str) -> bool:
+    """Whether path holds any rows; an unreadable file counts as holding data so it is never treated as empty."""
+    if not os.path.exists(path):
+        return False
+    try:
+        if key == "items_csv":
+            return bool(read_items(path))
+        if key == "money_csv":
+            return bool(read_money(path))
+        return bool(read_items(path) or read_money(path))
+    except (DataFileParseError, ValueError, OSError):
+        return True
+
+
+def changed_data_paths(settings: Dict[str, Any], settings_path: str) -> List[Tuple[str, str, str]]:
+    """(key, old, new) for each data file in use whose path changed since the last run and whose
+    old file still has records while the new one has none."""
+    last = _load(state_path(settings_path))
+    paths = settings.get("paths", {})
+    changes = []
+    for key in _keys_in_use(settings):
+        old, new = last.get(key), paths.get(key)
+        if not old or not new or os.path.abspath(old) == os.path.abspath(new):
+            continue
+        if _has_records(key, old) and not _has_records(key, new):
+            changes.append((key, old, new))
+    return changes
+
+
+def relocate_data_file(old: str, new: str, backup_dir: str, policy: Dict[str, Any], move: bool = False) -> str:
+    """Back up old, then copy it over new (removing old when move is set); returns the backup path.
+
+    The backup is taken even when backups are switched off, since a move leaves no other copy behind.
+    """
+    backup = create_backup(old, backup_dir, dict(policy, enabled=True))
+    os.makedirs(os.path.dirname(os.path.abspath(new)), exist_ok=True)
+    shutil.copy2(old, new)
+    if move:
+        os.remove(old)
+    return backup
+
+
+def remember_data_paths(settings: Dict[str, Any], settings_path: str) -> None:
+    """Record the current data paths as the ones the next run compares against."""
+    paths = settings.get("paths", {})
+    current = {key: paths[key] for key in DATA_PATH_KEYS if paths.get(key)}
+    path = state_path(settings_path)
+    if _load(path) == current:
+        return
+    os.makedirs(os.path.dirname(path), exist_ok=True)
+    with open(path, "w", encoding="utf-8") as f:
+        json.dump(current, f, indent=2)
@@ -0,0 +1,294 @@
+import csv
+import io
+from dataclasses import dataclass
+from datetime import datetime
+from typing import Dict, Iterable, List, Optional, Tuple
+
+from core.models import DECISIONS, ItemRecord, MoneyRecord
+from core.periods import period_label, period_of, period_start, shift_period
+from scoring.scoring import score_item
+
+SCORE_FIELDS = [
+    ("date", "Age"),
+    ("cost", "Cost Band"),
+    ("urgency", "Urgency"),
+    ("value", "Value"),
+    ("want", "Want"),
+    ("price_comp", "Price vs Similar"),
+    ("effect", "Effect"),
+]
+
+
+def _md_cell(text: str) -> str:
+    return text.replace("|", "\\|").replace("\n", " ")
+
+
+def markdown_report(
+    items: Iterable[ItemRecord],
+    money: Iterable[MoneyRecord],
+    weights: Dict,
+    currency_symbol: str = "$",
+    date_format: str = "%Y-%m-%d %H:%M",
+    generated_at: Optional[datetime] = None,
+) -> str:
+    generated = (generated_at or datetime.now()).strftime(date_format)
+    lines: List[str] = ["# Finance Planner report", "", f"Generated {generated}", "", "## Items", ""]
+
+    scored = [(item, score_item(item, weights)) for item in items]
+    scored.sort(key=lambda pair: pair[1].overall, reverse=True)
+    if scored:
+        header = ["Product", "Date", "Cost"] + [label for _, label in SCORE_FIELDS] + ["Overall"]
+        lines.append("| " + " | ".join(header) + " |")
+        lines.append("| " + " | ".join(["---", "---"] + ["---:"] * (len(header) - 2)) + " |")
+        for item, result in scored:
+            row = [
+                _md_cell(item.product),
+                item.date.strftime(date_format),
+                f"{currency_symbol}{item.cost:,.2f}",
+            ]
+            row += [f"{result.field_scores[key]:.2f}" for key, _ in SCORE_FIELDS]
+            row.append(f"{result.overall:.2f}")
+            lines.append("| " + " | ".join(row) + " |")
+    else:
+        lines.append("_No items._")
+
+    entries = list(money)
+    income = sum(e.amount for e in entries if e.entry_type.lower() == "income")
+    expense = sum(e.amount for e in entries if e.entry_type.lower() == "expense")
+    lines += [
+        "",
+        "## Money",
+        "",
+        f"- Entries: {len(entries)}",
+        f"- Income: {currency_symbol}{income:,.2f}",
+        f"- Expenses: {currency_symbol}{expense:,.2f}",
+        f"- Balance: {currency_symbol}{sum(e.signed_amount() for e in entries):,.2f}",
+        "",
+    ]
+    return "\n".join(lines)
+
+
+def category_totals(money: Iterable[MoneyRecord]) -> Dict[str, Tuple[float, float]]:
+    """Sum (income, expenses) per category; entries without one fall under "Uncategorized"."""
+    totals: Dict[str, Tuple[float, float]] = {}
+    for entry in money:
+        label = entry.category_label()
+        income, expenses = totals.get(label, (0.0, 0.0))
+        flow = entry.signed_amount()
+        if flow >= 0:
+            income += flow
+        else:
+            expenses -= flow
+        totals[label] = (income, expenses)
+    return totals
+
+
+@dataclass
+class MonthBalance:
+    month: str
+    net: float
+    balance: float
+
+
+def balance_as_of(money: Iterable[MoneyRecord], as_of: datetime) -> float:
+    """Sum of signed amounts for every entry dated at or before as_of."""
+    return sum(entry.signed_amount() for entry in money if entry.date <= as_of)
+
+
+def reconciliation(money: Iterable[MoneyRecord], as_of: datetime) -> Tuple[float, float, int]:
+    """(cleared balance, actual balance, uncleared count) for entries dated at or before as_of.
+
+    The cleared balance is what a bank statement should show; the difference is still in flight.
+    """
+    entries = [entry for entry in money if entry.date <= as_of]
+    cleared = [entry for entry in entries if entry.cleared]
+    return balance_as_of(cleared, as_of), balance_as_of(entries, as_of), len(entries) - len(cleared)
+
+
+def monthly_balances(
+    money: Iterable[MoneyRecord], as_of: datetime, months: int, start_day: int = 1
+) -> List[MonthBalance]:
+    """Net flow and closing balance for the `months` months ending with the one holding as_of, oldest first.
+
+    Months start on start_day (see core.periods). The last month closes at as_of, so its balance
+    equals balance_as_of(money, as_of).
+    """
+    current = period_of(as_of, start_day)
+    keys = [shift_period(current, offset) for offset in range(1 - max(months, 1), 1)]
+    first = period_start(keys[0], start_day)
+    entries = [entry for entry in money if entry.date <= as_of]
+    balance = sum(entry.signed_amount() for entry in entries if entry.date < first)
+    nets = {key: 0.0 for key in keys}
+    for entry in entries:
+        if entry.date >= first:
+            nets[period_of(entry.date, start_day)] += entry.signed_amount()
+    result = []
+    for key in keys:
+        balance += nets[key]
+        result.append(MonthBalance(period_label(key), nets[key], balance))
+    return result
+
+
+@dataclass
+class Purchase:
+    """An item together with the expenses linked to it; purchased_on is the earliest of them."""
+
+    item: ItemRecord
+    expenses: List[MoneyRecord]
+
+    @property
+    def purchased_on(self) -> datetime:
+        return min(entry.date for entry in self.expenses)
+
+    @property
+    def total_spent(self) -> float:
+        return sum(entry.amount for entry in self.expenses)
+
+
+def purchase_log(items: Iterable[ItemRecord], money: Iterable[MoneyRecord]) -> List[Purchase]:
+    """Items with at least one linked expense, oldest purchase first."""
+    linked: Dict[str, List[MoneyRecord]] = {}
+    for entry in money:
+        if entry.linked_item_id and entry.entry_type.lower() == "expense":
+            linked.setdefault(entry.linked_item_id, []).append(entry)
+    purchases = [
+        Purchase(item, sorted(linked[item.id], key=lambda e: e.date)) for item in items if item.id in linked
+    ]
+    purchases.sort(key=lambda p: (p.purchased_on, p.item.product.lower()))
+    return purchases
+
+
+def purchases_markdown(
+    purchases: List[Purchase], currency_symbol: str = "$", date_format: str = "%Y-%m-%d", start_day: int = 1
+) -> str:
+    lines: List[str] = ["# Purchase log", ""]
+    if not purchases:
+        lines.append("_No items have linked expenses yet._")
+        return "\n".join(lines) + "\n"
+    month = None
+    month_total = 0.0
+
+    def close_month() -> None:
+        lines.extend(["", f"**{month} total: {currency_symbol}{month_total:,.2f}**", ""])
+
+    for purchase in purchases:
+        purchase_month = period_label(period_of(purchase.purchased_on, start_day))
+        if purchase_month != month:
+            if month is not None:
+                close_month()
+            month, month_total = purchase_month, 0.0
+            lines += [f"## {month}", "", "| Date | Item | Expenses | Spent |", "| --- | --- | --- | ---: |"]
+        expenses = "; ".join(
+            f"{entry.date.strftime(date_format)} {_md_cell(entry.source_or_destination)} "
+            f"{currency_symbol}{entry.amount:,.2f}"
+            for entry in purchase.expenses
+        )
+        lines.append(
+            f"| {purchase.purchased_on.strftime(date_format)} | {_md_cell(purchase.item.product)} | {expenses} "
+            f"| {currency_symbol}{purchase.total_spent:,.2f} |"
+        )
+        month_total += purchase.total_spent
+    close_month()
+    grand_total = sum(p.total_spent for p in purchases)
+    lines.append(f"Total spent on {len(purchases)} item(s): {currency_symbol}{grand_total:,.2f}")
+    return "\n".join(lines) + "\n"
+
+
+def purchases_csv(purchases: List[Purchase], date_format: str = "%Y-%m-%d", start_day: int = 1) -> str:
+    """One row per linked expense, so spreadsheets can re-total by item or by (budget) month."""
+    out = io.StringIO()
+    writer = csv.writer(out)
+    writer.writerow(["month", "purchased_on", "item_id", "product", "expense_id", "expense_date", "paid_to", "amount"])
+    for purchase in purchases:
+        for entry in purchase.expenses:
+            writer.writerow(
+                [
+                    period_label(period_of(purchase.purchased_on, start_day)),
+                    purchase.purchased_on.strftime(date_format),
+                    purchase.item.id,
+                    purchase.item.product,
+                    entry.id,
+                    entry.date.strftime(date_format),
+                    entry.source_or_destination,
+                    f"{entry.amount:.2f}",
+                ]
+            )
+    return out.getvalue()
+
+
+# A decision goes against the model when a bought item scores below this or a skipped one above
+# OVERRIDE_HIGH_SCORE; the same bands as the app's Low and High filters.
+OVERRIDE_LOW_SCORE = 2.5
+OVERRIDE_HIGH_SCORE = 4.0
+UNDECIDED = "undecided"
+
+
+@dataclass
+class DecisionTally:
+    decision: str
+    count: int
+    total_cost: float
+    # Of the scored items only; None when none of them is scored.
+    average_score: Optional[float]
+
+
+def decision_tally(items: Iterable[ItemRecord]) -> List[DecisionTally]:
+    """Count, total cost and average score per decision: buy, skip, defer, then undecided."""
+    groups: Dict[str, List[ItemRecord]] = {decision: [] for decision in DECISIONS + (UNDECIDED,)}
+    for item in items:
+        groups[item.decision or UNDECIDED].append(item)
+    tallies = []
+    for decision, members in groups.items():
+        scores = [item.overall_score for item in members if item.overall_score is not None]
+        tallies.append(
+            DecisionTally(
+                decision,
+                len(members),
+                sum(item.cost for item in members),
+                sum(scores) / len(scores) if scores else None,
+            )
+        )
+    return tallies
+
+
+def decision_overrides(items: Iterable[ItemRecord]) -> List[ItemRecord]:
+    """Items bought despite a low score or skipped despite a high one, highest score first."""
+    overrides = [
+        item
+        for item in items
+        if item.overall_score is not None
+        and (
+            (item.decision == "buy" and item.overall_score < OVERRIDE_LOW_SCORE)
+            or (item.decision == "skip" and item.overall_score > OVERRIDE_HIGH_SCORE)
+        )
+    ]
+    return sorted(overrides, key=lambda item: item.overall_score, reverse=True)
+
+
+def decisions_markdown(items: List[ItemRecord], currency_symbol: str = "$") -> str:
+    lines: List[str] = ["# Decision log", "", "| Decision | Items | Total cost | Average score |"]
+    lines.append("| --- | ---: | ---: | ---: |")
+    for tally in decision_tally(items):
+        average = f"{tally.average_score:.2f}" if tally.average_score is not None else "—"
+        lines.append(
+            f"| {tally.decision} | {tally.count} | {currency_symbol}{tally.total_cost:,.2f} | {average} |"
+        )
+    overrides = decision_overrides(items)
+    lines += ["", "## Overriding the score", ""]
+    if not overrides:
+        lines.append(
+            f"_No items bought below a score of {OVERRIDE_LOW_SCORE:g} or skipped above {OVERRIDE_HIGH_SCORE:g}._"
+        )
+    for item in overrides:
+        lines.append(f"- {item.decision} {_md_cell(item.product)} (score {item.overall_score:.2f})")
+    return "\n".join(lines) + "\n"
+
+
+def decisions_csv(items: List[ItemRecord]) -> str:
+    out = io.StringIO()
+    writer = csv.writer(out)
+    writer.writerow(["decision", "items", "total_cost", "average_score"])
+    for tally in decision_tally(items):
+        average = f"{tally.average_score:.2f}" if tally.average_score is not None else ""
+        writer.writerow([tally.decision, tally.count, f"{tally.total_cost:.2f}", average])
+    return out.getvalue()
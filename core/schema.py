@@ -0,0 +1,51 @@
+"""Comparing a data file's header row with the columns the app reads and writes.
+
+Columns are matched by name, so a reordered file still loads; only missing required columns stop
+a load. Extra columns are ignored on read and dropped on the next save.
+"""
+
+from dataclasses import dataclass, field
+from typing import List
+
+
+@dataclass
+class SchemaDrift:
+    missing_required: List[str] = field(default_factory=list)
+    missing_optional: List[str] = field(default_factory=list)
+    extra: List[str] = field(default_factory=list)
+    duplicated: List[str] = field(default_factory=list)
+    # The known columns in the file's order, when that differs from the order the app writes.
+    reordered: List[str] = field(default_factory=list)
+
+    def ok(self) -> bool:
+        """Whether the file will load; the other kinds of drift do not stop it."""
+        return not self.missing_required
+
+    def lines(self) -> List[str]:
+        lines = []
+        if self.missing_required:
+            lines.append(f"missing required columns: {', '.join(self.missing_required)}")
+        if self.duplicated:
+            lines.append(f"duplicated columns (the last one wins): {', '.join(self.duplicated)}")
+        if self.missing_optional:
+            lines.append(f"missing optional columns (filled with blanks): {', '.join(self.missing_optional)}")
+        if self.extra:
+            lines.append(f"extra columns (ignored, dropped on save): {', '.join(self.extra)}")
+        if self.reordered:
+            lines.append(f"columns in a different order (fine; saves restore it): {', '.join(self.reordered)}")
+        return lines or ["header matches"]
+
+
+def schema_drift(headers: List[str], record_cls) -> SchemaDrift:
+    expected = record_cls.headers()
+    required = set(record_cls.required_headers())
+    present = list(headers)
+    known = list(dict.fromkeys(name for name in present if name in expected))
+    expected_order = [name for name in expected if name in known]
+    return SchemaDrift(
+        missing_required=[name for name in expected if name in required and name not in present],
+        missing_optional=[name for name in expected if name not in required and name not in present],
+        extra=[name for name in present if name not in expected],
+        duplicated=sorted({name for name in present if present.count(name) > 1}),
+        reordered=known if known != expected_order else [],
+    )
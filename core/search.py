@@ -0,0 +1,52 @@
+from difflib import SequenceMatcher
+from typing import Iterable, List, Tuple
+
+from core.models import ItemRecord, MoneyRecord
+
+# Queries shorter than this only match as plain substrings; fuzzy ratios are noisy for 1-2 letters.
+FUZZY_MIN_QUERY = 3
+FUZZY_THRESHOLD = 0.75
+
+
+def match_score(query: str, text: str) -> float:
+    """Return 1.0 for a substring hit, otherwise the best fuzzy ratio against a same-length word window."""
+    query = " ".join(query.lower().split())
+    text = text.lower()
+    if not query or query in text:
+        return 1.0
+    if len(query) < FUZZY_MIN_QUERY:
+        return 0.0
+    words = text.split()
+    span = len(query.split())
+    best = 0.0
+    for start in range(len(words)):
+        window = " ".join(words[start : start + span])
+        best = max(best, SequenceMatcher(None, query, window).ratio())
+    return best
+
+
+def item_match_score(item: ItemRecord, query: str) -> float:
+    return max(match_score(query, item.product), match_score(query, item.description))
+
+
+def rank_items(
+    items: Iterable[ItemRecord], query: str, threshold: float = FUZZY_THRESHOLD
+) -> List[Tuple[float, ItemRecord]]:
+    scored = [(item_match_score(item, query), item) for item in items]
+    matches = [pair for pair in scored if pair[0] >= threshold]
+    matches.sort(key=lambda pair: (-pair[0], pair[1].product.lower()))
+    return matches
+
+
+def money_match_score(entry: MoneyRecord, query: str) -> float:
+    return max(match_score(query, entry.source_or_destination), match_score(query, entry.notes))
+
+
+def rank_money(
+    entries: Iterable[MoneyRecord], query: str, threshold: float = FUZZY_THRESHOLD
+) -> List[Tuple[float, MoneyRecord]]:
+    """Entries whose source/destination or notes match query, best match first and newest first among equals."""
+    scored = [(money_match_score(entry, query), entry) for entry in entries]
+    matches = [pair for pair in scored if pair[0] >= threshold]
+    matches.sort(key=lambda pair: (-pair[0], -pair[1].date.timestamp()))
+    return matches
@@ -0,0 +1,40 @@
+from copy import copy
+from typing import List, Optional, Sequence
+
+from core.models import MoneyRecord, new_id
+
+
+def split_entry(
+    entry: MoneyRecord, amounts: Sequence[float], categories: Optional[Sequence[str]] = None
+) -> List[MoneyRecord]:
+    """Parts of entry with the given amounts, which must be positive and add up to entry.amount.
+
+    Each part copies the original's date, type, counterparty, notes, link and created_at, gets its
+    own id, and records the original id in split_group. categories, when given, sets each part's category
+    (blank keeps the original one). Raises ValueError for amounts that do not add up.
+    """
+    if len(amounts) < 2:
+        raise ValueError("A split needs at least two amounts")
+    if any(amount <= 0 for amount in amounts):
+        raise ValueError("Split amounts must be positive")
+    total = round(sum(amounts), 2)
+    if total != round(abs(entry.amount), 2):
+        raise ValueError(f"Split amounts add up to {total:,.2f}, not {abs(entry.amount):,.2f}")
+    group = entry.split_group or entry.id
+    parts = []
+    for idx, amount in enumerate(amounts):
+        part = copy(entry)
+        part.id = new_id()
+        part.amount = round(amount, 2)
+        part.split_group = group
+        category = categories[idx] if categories and idx < len(categories) else ""
+        part.category = category.strip() or entry.category
+        part.touch()
+        parts.append(part)
+    return parts
+
+
+def replace_with_split(entries: List[MoneyRecord], entry: MoneyRecord, parts: List[MoneyRecord]) -> List[MoneyRecord]:
+    """entries with entry swapped for its parts, in the same position."""
+    index = next(idx for idx, candidate in enumerate(entries) if candidate.id == entry.id)
+    return entries[:index] + parts + entries[index + 1 :]
@@ -0,0 +1,38 @@
+"""Picking something to buy at random, weighted by score, from what the current balance covers.
+
+Each candidate's chance is proportional to its overall score, so an item scoring 4 comes up
+twice as often as one scoring 2. Items already bought (with a linked expense) or marked skip
+are never suggested.
+"""
+
+import random
+from typing import Dict, Iterable, List, Optional, Tuple
+
+from core.models import ItemRecord, MoneyRecord
+from scoring.scoring import ScoreResult, score_item
+
+
+def suggestion_candidates(
+    items: Iterable[ItemRecord], money: Iterable[MoneyRecord], weights: Dict, budget: float
+) -> List[Tuple[ItemRecord, ScoreResult]]:
+    """Unbought, not skipped items costing no more than budget, scored with weights, in file order."""
+    purchased = {
+        entry.linked_item_id for entry in money if entry.linked_item_id and entry.entry_type.lower() == "expense"
+    }
+    return [
+        (item, score_item(item, weights))
+        for item in items
+        if item.id not in purchased and item.decision != "skip" and item.cost <= budget
+    ]
+
+
+def pick_suggestion(
+    candidates: List[Tuple[ItemRecord, ScoreResult]], seed: Optional[int] = None
+) -> Optional[Tuple[ItemRecord, ScoreResult]]:
+    """One candidate drawn with probability proportional to its overall score; the same seed gives the same pick."""
+    if not candidates:
+        return None
+    chances = [max(result.overall, 0.0) for _, result in candidates]
+    if not any(chances):
+        chances = [1.0] * len(candidates)
+    return random.Random(seed).choices(candidates, weights=chances)[0]
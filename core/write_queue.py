@@ -0,0 +1,95 @@
+import threading
+from copy import deepcopy
+from typing import Callable, Dict, Iterable, List, Optional, Tuple
+
+Writer = Callable[[str, list], None]
+
+
+class WriteQueue:
+    """Serializes data-file writes on one background thread, keeping only the latest snapshot per path.
+
+    A save requested while an earlier one for the same file is still queued replaces it, so a burst
+    of changes costs a single locked write (and a single backup) instead of one per change.
+    """
+
+    def __init__(
+        self,
+        after_write: Optional[Callable[[List[str]], None]] = None,
+        on_error: Optional[Callable[[str, Exception], None]] = None,
+        settle_seconds: float = 0.2,
+    ) -> None:
+        self._after_write = after_write
+        self._on_error = on_error
+        self._settle = max(0.0, settle_seconds)
+        # Keyed by path and writer: with combined JSON storage, items and money share one path.
+        self._pending: Dict[Tuple[str, Writer], list] = {}
+        self._busy = False
+        self._closed = False
+        self._flush_waiters = 0
+        self._failed = False
+        self._cond = threading.Condition()
+        self._thread = threading.Thread(target=self._run, name="data-writer", daemon=True)
+        self._thread.start()
+
+    def submit(self, path: str, writer: Writer, records: Iterable) -> None:
+        # Copy now so later in-place edits on the UI thread cannot leak into a half-written file.
+        snapshot = deepcopy(list(records))
+        with self._cond:
+            if self._closed:
+                raise RuntimeError("write queue is closed")
+            self._pending[(path, writer)] = snapshot
+            self._cond.notify_all()
+
+    def flush(self, timeout: Optional[float] = None) -> bool:
+        """Block until every submitted write has finished; False if one failed or the timeout ran out."""
+        with self._cond:
+            self._flush_waiters += 1
+            self._cond.notify_all()
+            try:
+                done = self._cond.wait_for(lambda: not self._pending and not self._busy, timeout)
+                ok = done and not self._failed
+                self._failed = False
+                return ok
+            finally:
+                self._flush_waiters -= 1
+
+    def close(self, timeout: Optional[float] = None) -> bool:
+        """Flush outstanding writes and stop the worker; later submits raise."""
+        ok = self.flush(timeout)
+        with self._cond:
+            self._closed = True
+            self._cond.notify_all()
+        self._thread.join(timeout)
+        return ok
+
+    def _run(self) -> None:
+        while True:
+            with self._cond:
+                self._cond.wait_for(lambda: self._pending or self._closed)
+                if not self._pending:
+                    return
+                # Let a burst of saves settle before taking the file lock, unless someone is waiting on it.
+                self._cond.wait_for(lambda: self._flush_waiters or self._closed, self._settle)
+                batch, self._pending = self._pending, {}
+                self._busy = True
+            try:
+                self._write_batch(batch)
+            finally:
+                with self._cond:
+                    self._busy = False
+                    self._cond.notify_all()
+
+    def _write_batch(self, batch: Dict[Tuple[str, Writer], list]) -> None:
+        written = []
+        for (path, writer), records in batch.items():
+            try:
+                writer(path, records)
+                if path not in written:
+                    written.append(path)
+            except Exception as exc:
+                with self._cond:
+                    self._failed = True
+                if self._on_error:
+                    self._on_error(path, exc)
+        if written and self._after_write:
+            self._after_write(written)
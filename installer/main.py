@@ -99,7 +99,8 @@ def _weights_template(config: dict) -> str:
     bands = config.get("cost_bands", [])
     lines = [
         "# Purchase scoring weights",
-        "# Edit values and restart the app to apply changes.",
+        "# Edit values and restart the app to apply changes, or use the Weights tab.",
+        "# Weights are relative: the overall is their weighted average, so it stays on the 1-5 scale.",
         "",
         f"weight_date={weights.get('date', 1.0)}",
         f"weight_cost={weights.get('cost', 1.0)}",
@@ -108,9 +109,17 @@ def _weights_template(config: dict) -> str:
         f"weight_want={weights.get('want', 1.0)}",
         f"weight_price_comp={weights.get('price_comp', 1.0)}",
         f"weight_effect={weights.get('effect', 1.0)}",
+        "# Cost per expected use (items without an estimate score neutral); 0 disables it",
+        f"weight_cost_per_use={weights.get('cost_per_use', 0.0)}",
+        "# Closeness to an item's target date (items without one score neutral); 0 disables it",
+        f"weight_target_date={weights.get('target_date', 0.0)}",
+        "# Effort to buy or set up; more effort lowers the score (unrated items score neutral); 0 disables it",
+        f"weight_effort={weights.get('effort', 0.0)}",
         "",
         f"date_recent_days={date_scoring.get('recent_days', 7)}",
         f"date_mid_days={date_scoring.get('mid_days', 30)}",
+        "# Days before a target date over which its score ramps from 1 up to 5",
+        f"target_ramp_days={date_scoring.get('target_ramp_days', 30)}",
         "",
         "# Cost bands: ascending maximum (use 'none' for no upper bound)",
     ]
@@ -121,6 +130,13 @@ def _weights_template(config: dict) -> str:
         lines.append(f"cost_band{idx}_score={band.get('score', 1)}")
     lines.append("")
     lines.append(f"urgency_override={config.get('urgency_override', 5)}")
+    lines.append("")
+    lines.append("# Score recurring items on their yearly cost (e.g. monthly cost x 12) instead of one payment")
+    lines.append(f"annualize_recurring_cost={str(config.get('annualize_recurring_cost', False)).lower()}")
+    lines.append("")
+    lines.append("# Pin the 'today' used for age scoring (YYYY-MM-DD) to reproduce past scores; 'none' = today")
+    as_of = config.get("as_of")
+    lines.append(f"as_of_date={as_of.strftime('%Y-%m-%d') if as_of else 'none'}")
     return "\n".join(str(line) for line in lines)
 
 
@@ -2,10 +2,26 @@ from __future__ import annotations
 
 from dataclasses import dataclass
 from datetime import datetime
-from typing import Dict, List, Tuple
+from typing import Callable, Dict, List, Optional, Sequence, Tuple
 
 from core.models import ItemRecord
 
+# Items scored between progress callbacks in overall_scores.
+RESCORE_CHUNK = 500
+# How each dimension reads when it lifts an item's score and when it drags it down.
+DRIVER_PHRASES = {
+    "date": ("waited a while", "added recently"),
+    "cost": ("low cost", "high cost"),
+    "urgency": ("urgent", "not urgent"),
+    "value": ("high value", "low value"),
+    "want": ("really wanted", "little want"),
+    "price_comp": ("good price vs similar", "poor price vs similar"),
+    "effect": ("big effect", "little effect"),
+    "cost_per_use": ("low cost per use", "high cost per use"),
+    "target_date": ("target date close", "target date far off"),
+    "effort": ("little effort", "lots of effort"),
+}
+
 
 @dataclass
 class ScoreResult:
@@ -13,12 +29,14 @@ class ScoreResult:
     overall: float
 
 
-def _score_date(item_date: datetime, config: Dict[str, int], urgency: int, urgency_override: int) -> float:
+def _score_date(
+    item_date: datetime, config: Dict[str, int], urgency: int, urgency_override: int, today: datetime
+) -> float:
     if urgency == urgency_override:
         return 5.0
     recent_days = config.get("recent_days", 7)
     mid_days = config.get("mid_days", 30)
-    days_old = (datetime.now() - item_date).days
+    days_old = (today - item_date).days
     if days_old <= recent_days:
         return 1.0
     if days_old <= mid_days:
@@ -36,29 +54,103 @@ def _score_cost(cost: float, bands: List[Dict[str, float]]) -> float:
     return 1.0
 
 
+def _score_cost_per_use(cost_per_use: Optional[float], bands: List[Dict[str, float]]) -> float:
+    if cost_per_use is None:
+        return 3.0
+    return _score_cost(cost_per_use, bands)
+
+
+def _score_target_date(target: Optional[datetime], today: datetime, ramp_days: int) -> float:
+    if target is None:
+        return 3.0
+    days_left = (target - today).days
+    # A target that has arrived or passed is as pressing as it gets.
+    if days_left <= 0:
+        return 5.0
+    ramp = max(ramp_days, 1)
+    if days_left >= ramp:
+        return 1.0
+    return 1.0 + 4.0 * (1 - days_left / ramp)
+
+
+def _score_effort(effort: Optional[int]) -> float:
+    # Inverted: the more effort an item takes, the lower it scores.
+    if effort is None:
+        return 3.0
+    return 6.0 - effort
+
+
 def _weighted_average(pairs: List[Tuple[float, float]]) -> float:
+    # Dividing by the weight total normalizes the weights, so only their proportions matter.
     numerator = sum(score * weight for score, weight in pairs)
     denominator = sum(weight for _, weight in pairs) or 1.0
     return numerator / denominator
 
 
+def reference_date(weights_config: Dict) -> datetime:
+    """The "today" used for age-based scoring; `as_of` pins it so past scores can be reproduced."""
+    return weights_config.get("as_of") or datetime.now()
+
+
 def score_item(item: ItemRecord, weights_config: Dict) -> ScoreResult:
     weights = weights_config.get("weights", {})
     date_cfg = weights_config.get("date_scoring", {})
     cost_bands = weights_config.get("cost_bands", [])
     urgency_override = weights_config.get("urgency_override", 5)
+    cost = item.annualized_cost() if weights_config.get("annualize_recurring_cost", False) else item.cost
 
     scores = {
-        "date": _score_date(item.date, date_cfg, item.urgency, urgency_override),
-        "cost": _score_cost(item.cost, cost_bands),
+        "date": _score_date(item.date, date_cfg, item.urgency, urgency_override, reference_date(weights_config)),
+        "cost": _score_cost(cost, cost_bands),
         "urgency": float(item.urgency),
         "value": float(item.value),
         "want": float(item.want),
         "price_comp": float(item.price_comp),
         "effect": float(item.effect),
     }
+    # Opt-in dimensions: only counted once the user gives it a weight.
+    if float(weights.get("cost_per_use", 0.0)) > 0:
+        scores["cost_per_use"] = _score_cost_per_use(
+            item.cost_per_use(), weights_config.get("cost_per_use_bands", [])
+        )
+    if float(weights.get("target_date", 0.0)) > 0:
+        scores["target_date"] = _score_target_date(
+            item.target_date, reference_date(weights_config), int(date_cfg.get("target_ramp_days", 30))
+        )
+    if float(weights.get("effort", 0.0)) > 0:
+        scores["effort"] = _score_effort(item.effort)
 
     pairs = [(scores[key], float(weights.get(key, 1.0))) for key in scores]
     overall = _weighted_average(pairs)
     scores["overall"] = overall
     return ScoreResult(field_scores=scores, overall=overall)
+
+
+def score_drivers(result: ScoreResult, weights_config: Dict) -> Tuple[str, str]:
+    """The dimensions that lift and drag the overall score most: (strongest, weakest).
+
+    Each dimension counts as its weight times its distance from the neutral 3, so a heavily
+    weighted middling score can outweigh a lightly weighted extreme one. Either side is "" when
+    nothing scores above (or below) neutral.
+    """
+    weights = weights_config.get("weights", {})
+    pull = {
+        key: float(weights.get(key, 1.0)) * (score - 3.0)
+        for key, score in result.field_scores.items()
+        if key != "overall" and float(weights.get(key, 1.0)) > 0
+    }
+    strongest = max(pull, key=pull.__getitem__, default="")
+    weakest = min(pull, key=pull.__getitem__, default="")
+    return (strongest if pull.get(strongest, 0) > 0 else ""), (weakest if pull.get(weakest, 0) < 0 else "")
+
+
+def overall_scores(
+    items: Sequence[ItemRecord], weights_config: Dict, progress: Optional[Callable[[int], None]] = None
+) -> List[float]:
+    """Overall score of each item, in order; progress gets the running count every RESCORE_CHUNK items."""
+    scores = []
+    for start in range(0, len(items), RESCORE_CHUNK):
+        scores += [score_item(item, weights_config).overall for item in items[start : start + RESCORE_CHUNK]]
+        if progress:
+            progress(len(scores))
+    return scores
@@ -0,0 +1,67 @@
+from copy import deepcopy
+from dataclasses import dataclass
+from typing import Dict, List, Sequence
+
+from core.models import ItemRecord
+from scoring.scoring import score_item
+
+
+@dataclass
+class WeightSensitivity:
+    """How the item ranking reacts when one weight is nudged down and up by the same fraction."""
+
+    key: str
+    weight: float
+    rho_down: float
+    rho_up: float
+    moved_down: int
+    moved_up: int
+
+    @property
+    def impact(self) -> float:
+        """1 - the lower rank correlation: 0 means the order did not change at all."""
+        return 1.0 - min(self.rho_down, self.rho_up)
+
+
+def _ranking(items: Sequence[ItemRecord], weights_config: Dict) -> List[str]:
+    scored = [(score_item(item, weights_config).overall, item.id) for item in items]
+    # Ties fall back to id so identical scores do not count as movement.
+    scored.sort(key=lambda pair: (-pair[0], pair[1]))
+    return [item_id for _, item_id in scored]
+
+
+def spearman_rho(baseline: List[str], other: List[str]) -> float:
+    """Spearman rank correlation between two orderings of the same ids (1.0 = identical)."""
+    n = len(baseline)
+    if n < 2:
+        return 1.0
+    position = {item_id: idx for idx, item_id in enumerate(other)}
+    squared = sum((idx - position[item_id]) ** 2 for idx, item_id in enumerate(baseline))
+    return 1.0 - 6.0 * squared / (n * (n * n - 1))
+
+
+def weight_sensitivity(
+    items: Sequence[ItemRecord], weights_config: Dict, delta: float = 0.1
+) -> List[WeightSensitivity]:
+    """Perturb each non-zero weight by -delta and +delta (as a fraction) and compare rankings.
+
+    Scores go through score_item, so opt-in dimensions and cost annualization behave exactly as
+    they do for real scoring. Results are ordered from most to least influential.
+    """
+    baseline = _ranking(items, weights_config)
+    results = []
+    for key, weight in weights_config.get("weights", {}).items():
+        weight = float(weight)
+        if weight <= 0:
+            continue
+        rhos = []
+        moved = []
+        for factor in (1 - delta, 1 + delta):
+            perturbed = deepcopy(weights_config)
+            perturbed["weights"][key] = weight * factor
+            ranking = _ranking(items, perturbed)
+            rhos.append(spearman_rho(baseline, ranking))
+            moved.append(sum(1 for a, b in zip(baseline, ranking) if a != b))
+        results.append(WeightSensitivity(key, weight, rhos[0], rhos[1], moved[0], moved[1]))
+    results.sort(key=lambda result: (-result.impact, -(result.moved_down + result.moved_up), result.key))
+    return results
@@ -0,0 +1,98 @@
+"""Record factories shared by the tests; every field not given gets a plain, valid default."""
+
+import io
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stderr, redirect_stdout
+from datetime import datetime
+from typing import List, Tuple
+from unittest import mock
+
+from cli.main import main
+from core import csv_storage
+from core.config_manager import ConfigManager
+from core.csv_storage import read_items, read_money, write_items, write_money
+from core.models import ItemRecord, MoneyRecord
+
+FIXED_DATE = datetime(2025, 1, 15, 12, 0)
+
+
+def make_item(**fields) -> ItemRecord:
+    values = dict(
+        id="item-1",
+        date=FIXED_DATE,
+        product="Desk lamp",
+        description="",
+        location="online",
+        reference="",
+        cost=40.0,
+        urgency=3,
+        value=3,
+        want=3,
+        price_comp=3,
+        effect=3,
+        justification="",
+    )
+    values.update(fields)
+    return ItemRecord(**values)
+
+
+def make_money(**fields) -> MoneyRecord:
+    values = dict(
+        id="money-1",
+        date=FIXED_DATE,
+        entry_type="expense",
+        source_or_destination="Grocer",
+        amount=25.0,
+    )
+    values.update(fields)
+    return MoneyRecord(**values)
+
+
+def default_weights(**overrides) -> dict:
+    """The built-in weights, pinned to FIXED_DATE so age-based scores do not depend on today."""
+    weights = ConfigManager._default_weights()
+    weights["as_of"] = FIXED_DATE
+    weights.update(overrides)
+    return weights
+
+
+class CliTestCase(unittest.TestCase):
+    """Runs `python -m cli.main` commands in-process against a throwaway data directory."""
+
+    def setUp(self):
+        temp = tempfile.TemporaryDirectory()
+        self.addCleanup(temp.cleanup)
+        self.data_root = temp.name
+        # Run from the scratch directory: the packaged config/ is not found there, so data paths
+        # default to the temporary data root and nothing lands in the working tree.
+        self.addCleanup(os.chdir, os.getcwd())
+        os.chdir(self.data_root)
+        env = mock.patch.dict(os.environ, {"XDG_DATA_HOME": self.data_root, "APPDATA": self.data_root})
+        env.start()
+        self.addCleanup(env.stop)
+        # main() sets the module-wide lock settings from the config; put the test defaults back afterwards.
+        self.addCleanup(csv_storage.set_lock_timeout, csv_storage._lock_timeout)
+        self.addCleanup(csv_storage.set_read_lock, csv_storage._read_lock)
+        self.config = ConfigManager()
+        self.items_path, self.money_path = self.config.data_files()
+
+    def run_cli(self, *args: str) -> Tuple[int, str, str]:
+        """(exit code, stdout, stderr) of one command."""
+        out, err = io.StringIO(), io.StringIO()
+        with redirect_stdout(out), redirect_stderr(err):
+            code = main(list(args))
+        return code, out.getvalue(), err.getvalue()
+
+    def save_items(self, items: List[ItemRecord]) -> None:
+        write_items(self.items_path, items)
+
+    def saved_items(self) -> List[ItemRecord]:
+        return read_items(self.items_path)
+
+    def save_money(self, entries: List[MoneyRecord]) -> None:
+        write_money(self.money_path, entries)
+
+    def saved_money(self) -> List[MoneyRecord]:
+        return read_money(self.money_path)
@@ -0,0 +1,73 @@
+import os
+import tempfile
+import unittest
+from typing import List
+
+from core.backup import backup_usage, enforce_retention, preview_retention
+
+BASE_TIME = 1_700_000_000
+
+
+class RetentionTests(unittest.TestCase):
+    def setUp(self):
+        temp = tempfile.TemporaryDirectory()
+        self.addCleanup(temp.cleanup)
+        self.backup_dir = temp.name
+
+    def fake_backups(self, sizes: List[int], stem: str = "items") -> List[str]:
+        """Backups of <stem>.csv with the given sizes in bytes, oldest first, an hour apart."""
+        paths = []
+        for index, size in enumerate(sizes):
+            path = os.path.join(self.backup_dir, f"{stem}_202501{index + 1:02d}120000.csv")
+            with open(path, "wb") as f:
+                f.write(b"x" * size)
+            os.utime(path, (BASE_TIME + index * 3600, BASE_TIME + index * 3600))
+            paths.append(path)
+        return paths
+
+    def test_count_rules_alone_ignore_size(self):
+        paths = self.fake_backups([1000] * 6)
+        keep, delete = preview_retention("items.csv", self.backup_dir, {"keep_recent": 3, "keep_historical": 3})
+        self.assertEqual(sorted(keep), paths)
+        self.assertEqual(delete, [])
+
+    def test_cap_drops_the_oldest_historical_backups_first(self):
+        paths = self.fake_backups([100] * 6)
+        policy = {"keep_recent": 3, "keep_historical": 3, "max_total_bytes": 450}
+        keep, delete = preview_retention("items.csv", self.backup_dir, policy)
+        self.assertEqual(sorted(delete), paths[:2])
+        self.assertEqual(sorted(keep), paths[2:])
+
+    def test_recent_set_is_never_deleted_even_over_the_cap(self):
+        paths = self.fake_backups([100] * 6)
+        policy = {"keep_recent": 3, "keep_historical": 3, "max_total_bytes": 50}
+        keep, delete = preview_retention("items.csv", self.backup_dir, policy)
+        self.assertEqual(sorted(keep), paths[3:])
+        self.assertEqual(sorted(delete), paths[:3])
+
+    def test_backups_under_the_cap_are_all_kept(self):
+        self.fake_backups([100, 200, 300])
+        policy = {"keep_recent": 1, "keep_historical": 3, "max_total_bytes": 600}
+        keep, delete = preview_retention("items.csv", self.backup_dir, policy)
+        self.assertEqual((len(keep), delete), (3, []))
+
+    def test_cap_applies_after_the_count_rules(self):
+        paths = self.fake_backups([400, 10, 10, 10, 10, 10, 10, 10])
+        policy = {"keep_recent": 2, "keep_historical": 2, "max_total_bytes": 100}
+        keep, delete = preview_retention("items.csv", self.backup_dir, policy)
+        # Count rules keep the two newest plus two spread over the rest (the oldest and the fourth);
+        # dropping the 400-byte oldest is enough to fit the cap, so the fourth stays.
+        self.assertEqual(sorted(keep), [paths[3], paths[6], paths[7]])
+        self.assertEqual(sorted(delete), [paths[0], paths[1], paths[2], paths[4], paths[5]])
+
+    def test_enforce_deletes_files_and_leaves_other_stems(self):
+        items = self.fake_backups([100] * 5)
+        money = self.fake_backups([100] * 5, stem="money")
+        removed = enforce_retention("items.csv", self.backup_dir, {"keep_recent": 2, "max_total_bytes": 250})
+        self.assertEqual(sorted(removed), items[:3])
+        self.assertEqual(backup_usage("items.csv", self.backup_dir), (2, 200))
+        self.assertTrue(all(os.path.exists(path) for path in money))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,285 @@
+import unittest
+from datetime import datetime
+
+from cli.main import _find_by_id
+from core.config_manager import ConfigManager
+from core.errors import AmbiguousIdError, RecordNotFoundError
+from core.models import UNSCORED_MARK, format_score
+from tests.support import CliTestCase, make_item, make_money
+
+
+class BulkEditTests(CliTestCase):
+    def test_location_takes_the_known_spelling(self):
+        self.save_items([make_item(id="a1", location="Online"), make_item(id="b2", location="store")])
+        code, out, _ = self.run_cli("items", "bulk-edit", "--filter", "id=b2", "--set", "location=ONLINE")
+        self.assertEqual(code, 0)
+        self.assertIn("Modified 1 item(s).", out)
+        self.assertEqual([item.location for item in self.saved_items()], ["Online", "Online"])
+
+    def test_new_location_is_added_to_the_list(self):
+        self.save_items([make_item(id="a1")])
+        self.run_cli("items", "bulk-edit", "--filter", "id=a1", "--set", "location=  Corner   shop ")
+        self.assertEqual(self.saved_items()[0].location, "Corner shop")
+        self.assertIn("Corner shop", ConfigManager().settings["ui"]["locations"])
+
+    def test_out_of_range_effort_is_refused(self):
+        self.save_items([make_item(id="a1", effort=2)])
+        code, _, err = self.run_cli("items", "bulk-edit", "--filter", "id=a1", "--set", "effort=9")
+        self.assertEqual(code, 1)
+        self.assertIn("effort must be 1-5 or empty, got '9'", err)
+        self.assertEqual(self.saved_items()[0].effort, 2)
+
+
+class UnscoredItemTests(CliTestCase):
+    def setUp(self):
+        super().setUp()
+        self.save_items(
+            [
+                make_item(id="new1", product="Imported", overall_score=None),
+                make_item(id="high", product="Chair", overall_score=4.5),
+                make_item(id="zero", product="Zero", overall_score=0.0),
+                make_item(id="mid", product="Lamp", overall_score=3.0),
+            ]
+        )
+
+    def listed(self, *options: str) -> list:
+        code, out, err = self.run_cli("items", "list", "--format", "plain", *options)
+        self.assertEqual(code, 0, err)
+        return [line.split(" | ")[0] for line in out.splitlines()]
+
+    def test_unscored_items_render_as_a_dash(self):
+        self.assertEqual(format_score(None), UNSCORED_MARK)
+        self.assertEqual(format_score(0.0), "0.00")
+        _, out, _ = self.run_cli("items", "list", "--format", "plain", "--filter", "id=new1")
+        self.assertEqual(out.split(" | ")[-1].strip(), UNSCORED_MARK)
+
+    def test_unscored_items_sort_after_scored_ones_either_way(self):
+        self.assertEqual(self.listed(), ["high", "mid", "zero", "new1"])
+        self.assertEqual(self.listed("--sort", "overall_score"), ["zero", "mid", "high", "new1"])
+        self.assertEqual(self.listed("--sort", "overall_score", "--desc"), ["high", "mid", "zero", "new1"])
+
+    def test_score_filters_leave_unscored_items_out_unless_asked(self):
+        self.assertEqual(self.listed("--max-score", "3"), ["mid", "zero"])
+        self.assertEqual(self.listed("--max-score", "3", "--include-unscored"), ["mid", "zero", "new1"])
+        self.assertEqual(self.listed("--filter", "score<4"), ["mid", "zero"])
+        self.assertEqual(self.listed("--filter", "score<4 or score=''"), ["mid", "zero", "new1"])
+
+    def test_score_all_unscored_fills_only_the_missing_scores(self):
+        code, out, _ = self.run_cli("items", "score")
+        self.assertEqual((code, out.strip()), (0, "Scored 1 unscored item(s)."))
+        scores = {item.id: item.overall_score for item in self.saved_items()}
+        self.assertIsNotNone(scores["new1"])
+        self.assertEqual((scores["high"], scores["zero"], scores["mid"]), (4.5, 0.0, 3.0))
+
+
+class ReducedColumnTests(CliTestCase):
+    def setUp(self):
+        super().setUp()
+        self.originals = [
+            make_item(id="a1", product="Lamp", cost=40.0, urgency=4, justification="reading", recurrence="monthly"),
+            make_item(id="b2", product="Chair", cost=250.0, value=5, justification="back pain"),
+        ]
+        self.save_items(self.originals)
+        self.export_path = f"{self.data_root}/shared.csv"
+
+    def export(self, columns: str) -> list:
+        code, _, err = self.run_cli("items", "export", self.export_path, "--columns", columns)
+        self.assertEqual(code, 0, err)
+        with open(self.export_path, encoding="utf-8") as f:
+            return f.read().splitlines()
+
+    def test_export_keeps_only_the_named_columns_and_id(self):
+        lines = self.export("cost,product")
+        self.assertEqual(lines[0], "id,product,cost")
+        self.assertEqual(lines[1:], ["a1,Lamp,40.00", "b2,Chair,250.00"])
+
+    def test_round_trip_into_an_empty_store_fills_defaults(self):
+        self.export("date,product,cost,recurrence")
+        self.save_items([])
+        code, out, err = self.run_cli("items", "import", self.export_path, "--partial")
+        self.assertEqual(code, 0, err)
+        self.assertIn("Imported 2 item(s).", out)
+        lamp, chair = self.saved_items()
+        for imported, original in ((lamp, self.originals[0]), (chair, self.originals[1])):
+            self.assertEqual(
+                (imported.id, imported.date, imported.product, imported.cost, imported.recurrence),
+                (original.id, original.date, original.product, original.cost, original.recurrence),
+            )
+            # Scoring fields left out of the file take the defaults and the item is scored with them.
+            self.assertEqual((imported.urgency, imported.value, imported.want), (1, 1, 3))
+            self.assertEqual(imported.justification, "")
+            self.assertIsNotNone(imported.overall_score)
+
+    def test_round_trip_into_the_same_store_keeps_the_other_fields(self):
+        lines = self.export("cost")
+        with open(self.export_path, "w", encoding="utf-8") as f:
+            f.write("\n".join([lines[0], "a1,45.00", lines[2]]) + "\n")
+        self.assertEqual(self.run_cli("items", "import", self.export_path, "--partial")[0], 0)
+        lamp, chair = self.saved_items()
+        self.assertEqual((lamp.cost, lamp.urgency, lamp.justification), (45.0, 4, "reading"))
+        self.assertEqual((chair.cost, chair.value, chair.justification), (250.0, 5, "back pain"))
+
+    def test_ragged_new_rows_import_with_defaults(self):
+        with open(self.export_path, "w", encoding="utf-8") as f:
+            f.write("id,date,product,depends_on,image_path\nc3,2025-02-01,Desk\n")
+        code, _, err = self.run_cli("items", "import", self.export_path, "--partial")
+        self.assertEqual(code, 0, err)
+        desk = self.saved_items()[-1]
+        self.assertEqual((desk.id, desk.product, desk.depends_on, desk.image_path), ("c3", "Desk", "", ""))
+
+    def test_new_rows_without_a_date_are_reported(self):
+        with open(self.export_path, "w", encoding="utf-8") as f:
+            f.write("id,product\nc3,Desk\n")
+        code, out, _ = self.run_cli("items", "import", self.export_path, "--partial")
+        self.assertEqual(code, 1)
+        self.assertIn("new records need a date", out)
+        self.assertEqual(len(self.saved_items()), 2)
+
+
+class RescoreOnLoadTests(CliTestCase):
+    def setUp(self):
+        super().setUp()
+        self.config.settings["storage"]["rescore_on_load"] = True
+        self.config.save_settings()
+        self.save_items([make_item(id="new1", overall_score=None), make_item(id="old", overall_score=1.0)])
+
+    def test_list_shows_fresh_scores_without_writing_them(self):
+        _, out, _ = self.run_cli("items", "list", "--format", "plain")
+        self.assertNotIn(UNSCORED_MARK, out)
+        self.assertNotIn("1.00", out)
+        self.assertEqual([item.overall_score for item in self.saved_items()], [None, 1.0])
+
+    def test_score_still_finds_and_saves_the_unscored_items(self):
+        code, out, _ = self.run_cli("items", "score")
+        self.assertEqual((code, out.strip()), (0, "Scored 1 unscored item(s)."))
+        new, old = self.saved_items()
+        self.assertIsNotNone(new.overall_score)
+        self.assertEqual(old.overall_score, 1.0)
+
+    def test_score_all_saves_every_fresh_score(self):
+        self.assertEqual(self.run_cli("items", "score", "--all")[1].strip(), "Scored 2 item(s).")
+        self.assertNotIn(1.0, [item.overall_score for item in self.saved_items()])
+
+
+class EditTimeCommandTests(CliTestCase):
+    ADDED = datetime(2025, 1, 10, 8, 0)
+
+    def setUp(self):
+        super().setUp()
+        self.save_fresh_records()
+
+    def save_fresh_records(self) -> None:
+        self.save_items([make_item(id="item-1", created_at=self.ADDED), make_item(id="item-2", created_at=self.ADDED)])
+        self.save_money([make_money(id="money-1", created_at=self.ADDED)])
+
+    def assertEdited(self, record) -> None:
+        self.assertEqual(record.created_at, self.ADDED)
+        self.assertGreater(record.updated_at, self.ADDED)
+
+    def test_item_commands_bump_updated_at(self):
+        for args in (
+            ("items", "decide", "item-1", "buy"),
+            ("items", "note", "item-1", "checked the price"),
+            ("items", "depend", "item-1", "item-2"),
+        ):
+            with self.subTest(args=args):
+                self.save_fresh_records()
+                self.assertEqual(self.run_cli(*args)[0], 0)
+                edited, untouched = self.saved_items()
+                self.assertEdited(edited)
+                self.assertEqual(untouched.updated_at, self.ADDED)
+
+    def test_bulk_edit_bumps_only_the_matching_items(self):
+        self.run_cli("items", "bulk-edit", "--filter", "id=item-2", "--set", "urgency=5")
+        untouched, edited = self.saved_items()
+        self.assertEdited(edited)
+        self.assertEqual(untouched.updated_at, self.ADDED)
+
+    def test_money_commands_bump_updated_at(self):
+        for args in (("money", "link", "money-1", "item-1"), ("money", "clear", "money-1")):
+            with self.subTest(args=args):
+                self.save_fresh_records()
+                self.assertEqual(self.run_cli(*args)[0], 0)
+                self.assertEdited(self.saved_money()[0])
+
+    def test_show_uses_the_configured_date_format(self):
+        self.save_items([make_item(id="item-1", created_at=self.ADDED, updated_at=datetime(2025, 2, 3, 17, 45))])
+        for date_format, date, edited in (
+            ("%d/%m/%Y", "15/01/2025", "03/02/2025 17:45 (added 10/01/2025 08:00)"),
+            ("%d.%m.%Y %I:%M %p", "15.01.2025 12:00 PM", "03.02.2025 05:45 PM (added 10.01.2025 08:00 AM)"),
+        ):
+            with self.subTest(date_format=date_format):
+                self.config.settings["ui"]["date_format"] = date_format
+                self.config.save_settings()
+                _, out, _ = self.run_cli("items", "show", "item-1")
+                self.assertIn(f"Date:        {date}\n", out)
+                self.assertIn(f"Edited:      {edited}\n", out)
+
+    def test_scoring_does_not_count_as_an_edit(self):
+        self.save_items([make_item(id="item-1", created_at=self.ADDED, overall_score=None)])
+        self.run_cli("items", "score")
+        self.assertEqual(self.saved_items()[0].updated_at, self.ADDED)
+
+
+class IdPrefixTests(unittest.TestCase):
+    ITEMS = [make_item(id="3f2a9c10-aaaa"), make_item(id="3f2b0d44-bbbb"), make_item(id="7c01e5aa-cccc")]
+
+    def test_unique_prefix_finds_the_record(self):
+        self.assertEqual(_find_by_id(self.ITEMS, "7c", "item").id, "7c01e5aa-cccc")
+        self.assertEqual(_find_by_id(self.ITEMS, "3F2B", "item").id, "3f2b0d44-bbbb")
+        self.assertEqual(_find_by_id(self.ITEMS, " 3f2a9 ", "item").id, "3f2a9c10-aaaa")
+
+    def test_full_id_wins_over_longer_ids_it_prefixes(self):
+        items = [make_item(id="abc-1"), make_item(id="abc")]
+        self.assertEqual(_find_by_id(items, "abc", "item").id, "abc")
+
+    def test_ambiguous_prefix_lists_the_candidates(self):
+        with self.assertRaises(AmbiguousIdError) as caught:
+            _find_by_id(self.ITEMS, "3f2", "item")
+        self.assertEqual(caught.exception.candidates, ["3f2a9c10-aaaa", "3f2b0d44-bbbb"])
+        self.assertIn("Id prefix '3f2' matches 2 items", str(caught.exception))
+
+    def test_long_candidate_lists_are_cut_short(self):
+        items = [make_item(id=f"dup-{n:02d}") for n in range(12)]
+        with self.assertRaises(AmbiguousIdError) as caught:
+            _find_by_id(items, "dup", "item")
+        self.assertEqual(len(caught.exception.candidates), 12)
+        self.assertIn("dup-09\n  ...", str(caught.exception))
+        self.assertNotIn("dup-10", str(caught.exception))
+
+    def test_no_match(self):
+        with self.assertRaises(RecordNotFoundError) as caught:
+            _find_by_id(self.ITEMS, "9", "money entry")
+        self.assertEqual(str(caught.exception), "No money entry with id 9")
+        with self.assertRaises(RecordNotFoundError):
+            _find_by_id(self.ITEMS, "", "item")
+
+
+class IdPrefixCommandTests(CliTestCase):
+    def setUp(self):
+        super().setUp()
+        self.save_items(IdPrefixTests.ITEMS)
+        self.save_money([make_money(id="m-100"), make_money(id="m-200")])
+
+    def test_commands_accept_a_unique_prefix(self):
+        code, out, _ = self.run_cli("items", "show", "7c")
+        self.assertEqual(code, 0)
+        self.assertIn("(7c01e5aa-cccc)", out)
+        self.assertEqual(self.run_cli("money", "link", "m-2", "3f2a")[0], 0)
+        self.assertEqual([entry.linked_item_id for entry in self.saved_money()], ["", "3f2a9c10-aaaa"])
+
+    def test_ambiguous_prefix_fails_with_the_candidates(self):
+        code, _, err = self.run_cli("items", "decide", "3f2", "buy")
+        self.assertEqual(code, 1)
+        self.assertIn("3f2a9c10-aaaa", err)
+        self.assertIn("3f2b0d44-bbbb", err)
+        self.assertEqual([item.decision for item in self.saved_items()], ["", "", ""])
+
+    def test_unknown_id_fails(self):
+        code, _, err = self.run_cli("money", "clear", "x")
+        self.assertEqual(code, 1)
+        self.assertIn("No money entry with id x", err)
+
+
+if __name__ == "__main__":
+    unittest.main()
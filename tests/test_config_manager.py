@@ -0,0 +1,139 @@
+import io
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stderr
+from unittest import mock
+
+from core.config_manager import FALLBACK_DATA_DIR, ConfigManager, normalize_cost_bands
+from core.errors import InvalidWeightsError
+
+
+class DataDirFallbackTests(unittest.TestCase):
+    def setUp(self):
+        temp = tempfile.TemporaryDirectory()
+        self.addCleanup(temp.cleanup)
+        # The fallback is relative to the working directory, so run from a scratch one.
+        self.addCleanup(os.chdir, os.getcwd())
+        os.chdir(temp.name)
+        self.fallback = os.path.abspath(FALLBACK_DATA_DIR)
+        self.scratch = temp.name
+
+    def load_with_platform_dir(self, preferred):
+        err = io.StringIO()
+        with mock.patch.object(ConfigManager, "_user_data_root", return_value=preferred), redirect_stderr(err):
+            config = ConfigManager()
+        return config, err.getvalue()
+
+    def test_uncreatable_platform_dir_falls_back(self):
+        blocker = os.path.join(self.scratch, "not-a-folder")
+        open(blocker, "w").close()
+        config, warning = self.load_with_platform_dir(os.path.join(blocker, "finance_planner"))
+        self.assertEqual(config.user_root, self.fallback)
+        self.assertTrue(config.settings_path.startswith(self.fallback + os.sep))
+        self.assertTrue(os.path.isfile(config.settings_path))
+        self.assertEqual(config.data_files()[0], os.path.join(self.fallback, "data", "items.csv"))
+        self.assertIn("could not be created", warning)
+        self.assertIn(f"using {self.fallback} instead", config.load_messages[0])
+
+    def test_unwritable_platform_dir_falls_back(self):
+        preferred = os.path.join(self.scratch, "readonly")
+        real_access = os.access
+        with mock.patch("os.access", lambda path, mode: path != preferred and real_access(path, mode)):
+            config, warning = self.load_with_platform_dir(preferred)
+        self.assertEqual(config.user_root, self.fallback)
+        self.assertIn(f"{preferred} is not writable", warning)
+
+    def test_no_home_directory_falls_back(self):
+        config, warning = self.load_with_platform_dir(None)
+        self.assertEqual(config.user_root, self.fallback)
+        self.assertIn("no home directory could be determined", warning)
+
+    def test_usable_platform_dir_is_used_without_a_warning(self):
+        preferred = os.path.join(self.scratch, "platform", "finance_planner")
+        config, warning = self.load_with_platform_dir(preferred)
+        self.assertEqual(config.user_root, preferred)
+        self.assertEqual(warning, "")
+        self.assertFalse(os.path.exists(self.fallback))
+
+
+def bands(*pairs):
+    return [{"max": top, "score": score} for top, score in pairs]
+
+
+class CostBandTests(unittest.TestCase):
+    def assertInvalid(self, given, message: str) -> None:
+        with self.assertRaises(InvalidWeightsError) as caught:
+            normalize_cost_bands(given)
+        self.assertIn(message, str(caught.exception))
+
+    def test_unsorted_bands_are_sorted_with_the_unbounded_band_last(self):
+        given = bands((None, 1), (400, 3), (50, 5), (150, 4))
+        self.assertEqual(normalize_cost_bands(given), bands((50, 5), (150, 4), (400, 3), (None, 1)))
+
+    def test_sorted_bands_are_unchanged(self):
+        given = ConfigManager._default_weights()["cost_bands"]
+        self.assertEqual(normalize_cost_bands(given), given)
+
+    def test_overlapping_bands_are_rejected(self):
+        self.assertInvalid(bands((150, 5), (150, 4), (None, 1)), "Cost band 2 maximum (150) must be greater")
+        self.assertInvalid(bands((150.0, 4), (None, 1), (150, 5)), "must be greater than the band before it")
+
+    def test_missing_unbounded_band_is_rejected(self):
+        self.assertInvalid(bands((50, 5), (150, 4)), "exactly one band without a maximum; found 0")
+
+    def test_several_unbounded_bands_are_rejected(self):
+        self.assertInvalid(bands((50, 5), (None, 2), (None, 1)), "exactly one band without a maximum; found 2")
+
+    def test_non_numeric_bounds_are_rejected(self):
+        self.assertInvalid(bands((50, 5), ("cheap", 4), (None, 1)), "Cost band 2 maximum ('cheap') is not a number")
+        self.assertInvalid(bands((True, 5), (None, 1)), "is not a number")
+
+
+class WeightsFileBandTests(unittest.TestCase):
+    def setUp(self):
+        temp = tempfile.TemporaryDirectory()
+        self.addCleanup(temp.cleanup)
+        self.path = os.path.join(temp.name, "weights.txt")
+        self.defaults = ConfigManager._default_weights()
+
+    def load(self, text: str):
+        with open(self.path, "w", encoding="utf-8") as f:
+            f.write(text)
+        config = ConfigManager.__new__(ConfigManager)
+        return config._load_weights_text(self.path, self.defaults)
+
+    def test_unsorted_bands_in_the_file_load_sorted(self):
+        weights, warnings = self.load(
+            "cost_band1_max=none\ncost_band1_score=1\ncost_band2_max=100\ncost_band2_score=4\n"
+            "cost_band3_max=20\ncost_band3_score=5\n"
+        )
+        self.assertEqual(weights["cost_bands"], bands((20.0, 5.0), (100.0, 4.0), (None, 1.0)))
+        self.assertEqual(warnings, [])
+
+    def test_non_numeric_bound_in_the_file_keeps_the_default_with_a_warning(self):
+        weights, warnings = self.load(
+            "weight_cost=2\ncost_band1_max=50\ncost_band2_max=cheap\ncost_band3_max=none\ncost_band3_score=1\n"
+        )
+        self.assertEqual([band["max"] for band in weights["cost_bands"]], [50.0, 150, None])
+        self.assertEqual(weights["weights"]["cost"], 2.0)
+        self.assertIn("Line 3: invalid max for cost_band2_max; using default.", warnings)
+
+    def test_bands_that_cannot_be_scored_fall_back_to_the_defaults(self):
+        weights, warnings = self.load("weight_cost=2\ncost_band1_max=50\ncost_band1_score=5\n")
+        self.assertEqual(weights, self.defaults)
+        self.assertEqual(len(warnings), 1)
+        self.assertIn("exactly one band without a maximum; found 0", warnings[0])
+        self.assertIn("Using defaults.", warnings[0])
+
+    def test_overlapping_bands_in_the_file_fall_back_to_the_defaults(self):
+        weights, warnings = self.load(
+            "cost_band1_max=100\ncost_band1_score=5\ncost_band2_max=100\ncost_band2_score=3\n"
+            "cost_band3_max=none\ncost_band3_score=1\n"
+        )
+        self.assertEqual(weights["cost_bands"], self.defaults["cost_bands"])
+        self.assertIn("must be greater than the band before it", warnings[0])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,257 @@
+import csv
+import gzip
+import io
+import os
+import tempfile
+import time
+import unittest
+from contextlib import redirect_stderr
+from unittest import mock
+
+from core import csv_storage
+from core.csv_storage import (
+    locked_file,
+    read_header,
+    read_items,
+    read_money,
+    validate_import,
+    write_items,
+    write_money,
+)
+from core.errors import DataFileLockedError, DataFileParseError
+from core.models import ItemRecord, MoneyRecord
+from tests.support import make_item, make_money
+
+LOCKING = csv_storage.fcntl is not None or csv_storage.msvcrt is not None
+
+
+class StorageTestCase(unittest.TestCase):
+    def setUp(self):
+        self._dir = tempfile.TemporaryDirectory()
+        self.addCleanup(self._dir.cleanup)
+        self.items_path = os.path.join(self._dir.name, "items.csv")
+
+    def write_file(self, name: str, data: bytes) -> str:
+        path = os.path.join(self._dir.name, name)
+        with open(path, "wb") as f:
+            f.write(data)
+        return path
+
+    def read_file(self, path: str) -> str:
+        with open(path, encoding="utf-8") as f:
+            return f.read()
+
+
+@unittest.skipUnless(LOCKING, "file locking is not available on this platform")
+class LockTimeoutTests(StorageTestCase):
+    def setUp(self):
+        super().setUp()
+        write_items(self.items_path, [make_item()])
+        self.addCleanup(csv_storage.set_lock_timeout, csv_storage._lock_timeout)
+
+    def test_held_lock_raises_locked_error_after_the_timeout(self):
+        with locked_file(self.items_path, "a"):
+            started = time.monotonic()
+            with self.assertRaises(DataFileLockedError) as caught:
+                with locked_file(self.items_path, "r", timeout=0.3):
+                    pass
+            waited = time.monotonic() - started
+        self.assertGreaterEqual(waited, 0.3)
+        self.assertLess(waited, 0.3 + 1.0)
+        self.assertEqual(caught.exception.path, self.items_path)
+        self.assertIn("locked by another process", str(caught.exception))
+
+    def test_lock_is_taken_once_the_holder_lets_go(self):
+        with locked_file(self.items_path, "a"):
+            pass
+        with locked_file(self.items_path, "r", timeout=0) as fh:
+            self.assertTrue(fh.read().startswith("id,"))
+
+    def test_timed_out_writer_leaves_the_file_intact(self):
+        before = self.read_file(self.items_path)
+        csv_storage.set_lock_timeout(0.2)
+        with locked_file(self.items_path, "a"):
+            with self.assertRaises(DataFileLockedError):
+                write_items(self.items_path, [])
+        self.assertEqual(self.read_file(self.items_path), before)
+        self.assertEqual([item.id for item in read_items(self.items_path)], ["item-1"])
+
+
+@unittest.skipUnless(LOCKING, "file locking is not available on this platform")
+class ReadLockModeTests(StorageTestCase):
+    def setUp(self):
+        super().setUp()
+        write_items(self.items_path, [make_item(id="a1")])
+        self.addCleanup(csv_storage.set_lock_timeout, csv_storage._lock_timeout)
+        self.addCleanup(csv_storage.set_read_lock, csv_storage._read_lock)
+        csv_storage.set_lock_timeout(0.3)
+
+    def read_while_locked(self, mode: str):
+        """(ids read, stderr, seconds taken) for a read in mode while a writer holds the lock."""
+        csv_storage.set_read_lock(mode)
+        err = io.StringIO()
+        with locked_file(self.items_path, "a"), redirect_stderr(err):
+            started = time.monotonic()
+            ids = [item.id for item in read_items(self.items_path)]
+            return ids, err.getvalue(), time.monotonic() - started
+
+    def test_none_reads_while_a_writer_holds_the_lock(self):
+        ids, warning, took = self.read_while_locked("none")
+        self.assertEqual(ids, ["a1"])
+        self.assertEqual(warning, "")
+        self.assertLess(took, 0.3)
+
+    def test_try_falls_back_to_an_unlocked_read_with_a_warning(self):
+        ids, warning, took = self.read_while_locked("try")
+        self.assertEqual(ids, ["a1"])
+        self.assertIn("reading it without the lock", warning)
+        self.assertGreaterEqual(took, csv_storage.TRY_LOCK_SECONDS)
+
+    def test_wait_gives_up_after_the_lock_timeout(self):
+        with self.assertRaises(DataFileLockedError):
+            self.read_while_locked("wait")
+
+    def test_unknown_mode_is_rejected(self):
+        with self.assertRaises(ValueError):
+            csv_storage.set_read_lock("never")
+
+
+class EmptyFileTests(StorageTestCase):
+    def test_missing_file_reads_as_no_records(self):
+        self.assertEqual(read_items(self.items_path), [])
+
+    def test_zero_byte_file_reads_as_no_records(self):
+        path = self.write_file("items.csv", b"")
+        self.assertEqual(read_items(path), [])
+        self.assertEqual(read_money(self.write_file("money.csv", b"")), [])
+
+    def test_blank_line_file_reads_as_no_records(self):
+        self.assertEqual(read_items(self.write_file("items.csv", b"\n  \r\n")), [])
+
+    def test_header_only_file_reads_as_no_records(self):
+        items_header = ",".join(ItemRecord.headers()) + "\r\n"
+        money_header = ",".join(MoneyRecord.required_headers()) + "\n"
+        self.assertEqual(read_items(self.write_file("items.csv", items_header.encode())), [])
+        self.assertEqual(read_money(self.write_file("money.csv", money_header.encode())), [])
+
+    def test_file_without_the_required_columns_names_them(self):
+        path = self.write_file("items.csv", b"name,price\nLamp,40\n")
+        with self.assertRaises(DataFileParseError) as caught:
+            read_items(path)
+        self.assertIn("Missing required columns: id, date", str(caught.exception))
+        self.assertEqual(caught.exception.line, 1)
+
+
+class RaggedRowTests(StorageTestCase):
+    def test_rows_missing_trailing_fields_load_with_defaults(self):
+        header = ",".join(ItemRecord.headers())
+        row = "a1,2025-01-15 12:00,Lamp,,online,,40.00,3,3,3,3,3,,monthly"
+        items = read_items(self.write_file("items.csv", f"{header}\n{row}\n".encode()))
+        item = items[0]
+        self.assertEqual((item.product, item.recurrence, item.depends_on, item.image_path), ("Lamp", "monthly", "", ""))
+        self.assertEqual((item.history, item.decision, item.created_at), ([], "", item.date))
+
+    def test_money_rows_missing_trailing_fields_load_with_defaults(self):
+        header = ",".join(MoneyRecord.headers())
+        row = "m1,2025-01-15 12:00,expense,Grocer,9.50"
+        entry = read_money(self.write_file("money.csv", f"{header}\n{row}\n".encode()))[0]
+        self.assertEqual((entry.notes, entry.linked_item_id, entry.category, entry.cleared), ("", "", "", False))
+        self.assertEqual(entry.signed_amount(), -9.5)
+
+
+class RatingRangeTests(StorageTestCase):
+    def test_out_of_range_effort_is_a_parse_error(self):
+        out = io.StringIO()
+        writer = csv.DictWriter(out, fieldnames=ItemRecord.headers())
+        writer.writeheader()
+        writer.writerow({**make_item(id="a1").to_row(), "effort": "9"})
+        path = self.write_file("items.csv", out.getvalue().encode())
+        with self.assertRaises(DataFileParseError) as caught:
+            read_items(path)
+        self.assertEqual(caught.exception.line, 2)
+        self.assertIn("effort must be 1-5 or empty, got '9'", str(caught.exception))
+
+
+class GzipTests(StorageTestCase):
+    def setUp(self):
+        super().setUp()
+        write_items(self.items_path, [make_item(id="a1"), make_item(id="b2", product="Chair")])
+        with open(self.items_path, "rb") as f:
+            self.plain = f.read()
+
+    def test_gzipped_csv_reads_like_the_plain_file(self):
+        path = self.write_file("items.csv.gz", gzip.compress(self.plain))
+        self.assertEqual(read_items(path), read_items(self.items_path))
+        self.assertEqual(read_header(path), ItemRecord.headers())
+
+    def test_detection_uses_the_magic_bytes_not_the_name(self):
+        path = self.write_file("export.csv", gzip.compress(self.plain))
+        self.assertEqual([item.id for item in read_items(path)], ["a1", "b2"])
+
+    def test_plain_csv_is_not_decompressed(self):
+        self.assertNotEqual(self.plain[:2], b"\x1f\x8b")
+        path = self.write_file("plain.csv.gz", self.plain)
+        self.assertEqual([item.product for item in read_items(path)], ["Desk lamp", "Chair"])
+
+    def test_gzipped_import_is_validated(self):
+        money_path = os.path.join(self._dir.name, "money.csv")
+        write_money(money_path, [make_money(id="m1"), make_money(id="m2", amount=3.5)])
+        with open(money_path, "rb") as f:
+            path = self.write_file("money.csv.gz", gzip.compress(f.read()))
+        report = validate_import(path, MoneyRecord)
+        self.assertTrue(report.ok)
+        self.assertEqual([entry.amount for entry in report.records], [25.0, 3.5])
+
+    def test_truncated_gzip_is_a_parse_error(self):
+        path = self.write_file("items.csv.gz", gzip.compress(self.plain)[:20])
+        with self.assertRaises(DataFileParseError) as caught:
+            read_items(path)
+        self.assertIn("not a readable gzip file", str(caught.exception))
+
+
+class ByteRangeLockTests(StorageTestCase):
+    """Windows locks a byte range: while it is held, reads through any other handle on the file fail."""
+
+    def setUp(self):
+        super().setUp()
+        write_items(self.items_path, [make_item(id="a1"), make_item(id="b2")])
+        self.addCleanup(csv_storage.set_read_lock, csv_storage._read_lock)
+        csv_storage.set_read_lock("wait")
+        self.held = []
+        real_open = open
+
+        def open_unless_locked(file, *args, **kwargs):
+            if file in [fh.name for fh in self.held]:
+                raise PermissionError(13, "The process cannot access the file because it is locked", file)
+            return real_open(file, *args, **kwargs)
+
+        for patch in (
+            mock.patch.object(csv_storage, "_lock_file", lambda fh, path, timeout: self.held.append(fh)),
+            mock.patch.object(csv_storage, "_unlock_file", lambda fh: self.held.remove(fh)),
+            mock.patch("builtins.open", open_unless_locked),
+        ):
+            patch.start()
+            self.addCleanup(patch.stop)
+
+    def test_records_are_read_through_the_locked_handle(self):
+        self.assertEqual([item.id for item in read_items(self.items_path)], ["a1", "b2"])
+        self.assertEqual(self.held, [])
+
+    def test_gzipped_file_is_read_through_the_locked_handle(self):
+        path = self.write_file("items.csv.gz", gzip.compress(self.read_file(self.items_path).encode("utf-8")))
+        self.assertEqual([item.id for item in read_items(path)], ["a1", "b2"])
+
+    def test_header_is_read_through_the_locked_handle(self):
+        self.assertEqual(read_header(self.items_path), ItemRecord.headers())
+        path = self.write_file("items.csv.gz", gzip.compress(self.read_file(self.items_path).encode("utf-8")))
+        self.assertEqual(read_header(path), ItemRecord.headers())
+        self.assertEqual(self.held, [])
+
+    def test_a_second_handle_would_fail_while_the_lock_is_held(self):
+        with locked_file(self.items_path, "rb"):
+            with self.assertRaises(PermissionError):
+                open(self.items_path, "rb")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,187 @@
+import unittest
+from datetime import datetime
+
+from core.filters import FilterError, filter_fields, parse_filter
+from tests.support import make_item
+
+ITEMS = [
+    make_item(id="lamp", product="Desk lamp", cost=40.0, location="Online", recurrence="", urgency=2),
+    make_item(
+        id="sub",
+        product="Music streaming",
+        cost=12.0,
+        location="online",
+        recurrence="monthly",
+        urgency=4,
+        overall_score=3.5,
+        date=datetime(2025, 3, 1, 9, 0),
+    ),
+    make_item(
+        id="chair",
+        product='Chair "Pro" 2',
+        cost=250.0,
+        location="Brick and Mortar",
+        description="ergonomic, mesh back",
+        urgency=5,
+        overall_score=4.2,
+        target_date=datetime(2025, 6, 30),
+    ),
+    make_item(id="desk", product="Standing desk", cost=480.0, location="", justification="back pain", urgency=3),
+]
+
+
+def matching(expression: str) -> list:
+    predicate = parse_filter(expression)
+    return [item.id for item in ITEMS if predicate(item)]
+
+
+class PrecedenceTests(unittest.TestCase):
+    def test_and_binds_tighter_than_or(self):
+        # Read left to right, (location=online or cost>400) and urgency=3 would match only the desk.
+        self.assertEqual(matching("location=online or cost>400 and urgency=3"), ["lamp", "sub", "desk"])
+        self.assertEqual(matching("cost>400 and urgency=3 or location=online"), ["lamp", "sub", "desk"])
+
+    def test_not_binds_tightest(self):
+        self.assertEqual(matching("not cost>100 and urgency>2"), ["sub"])
+        self.assertEqual(matching("not not cost>100"), ["chair", "desk"])
+
+    def test_adjacent_terms_are_anded(self):
+        self.assertEqual(matching("cost>10 location=online"), ["lamp", "sub"])
+
+    def test_keywords_are_case_insensitive(self):
+        self.assertEqual(matching("cost>400 OR cost<20"), ["sub", "desk"])
+        self.assertEqual(matching("NOT cost>20"), ["sub"])
+
+    def test_empty_expression_matches_everything(self):
+        self.assertEqual(matching("   "), ["lamp", "sub", "chair", "desk"])
+
+
+class ParenthesesTests(unittest.TestCase):
+    def test_parentheses_override_precedence(self):
+        self.assertEqual(matching("(cost<20 or urgency>=5) and location=online"), ["sub"])
+        self.assertEqual(matching("(cost<20 or urgency>=5) and not location=online"), ["chair"])
+
+    def test_nested_parentheses(self):
+        self.assertEqual(matching("((cost>100) and (urgency=3 or (urgency=5 and cost<300)))"), ["chair", "desk"])
+
+    def test_not_applies_to_a_whole_group(self):
+        self.assertEqual(matching("not (cost>100 or recurrence=monthly)"), ["lamp"])
+
+
+class ValueTests(unittest.TestCase):
+    def test_quoted_values_keep_spaces_and_operators(self):
+        self.assertEqual(matching('location="brick and mortar"'), ["chair"])
+        self.assertEqual(matching("description~'mesh back'"), ["chair"])
+        self.assertEqual(matching('description~"ergonomic, mesh"'), ["chair"])
+
+    def test_escaped_quotes_inside_quoted_values(self):
+        self.assertEqual(matching(r'product="Chair \"Pro\" 2"'), ["chair"])
+        self.assertEqual(matching(r"product~'\"pro\"'"), ["chair"])
+
+    def test_bare_words_and_quoted_phrases_search_the_product(self):
+        self.assertEqual(matching("lamp"), ["lamp"])
+        self.assertEqual(matching('"standing desk"'), ["desk"])
+        self.assertEqual(matching("desk"), ["lamp", "desk"])
+
+    def test_contains_and_not_contains_on_text(self):
+        self.assertEqual(matching("product~STREAM"), ["sub"])
+        self.assertEqual(matching("product!~desk"), ["sub", "chair"])
+        self.assertEqual(matching("justification~pain"), ["desk"])
+
+    def test_text_equality_ignores_case(self):
+        self.assertEqual(matching("location=ONLINE"), ["lamp", "sub"])
+        self.assertEqual(matching("location!=online"), ["chair", "desk"])
+        self.assertEqual(matching("location==online"), ["lamp", "sub"])
+
+    def test_empty_value_form(self):
+        self.assertEqual(matching('location=""'), ["desk"])
+        self.assertEqual(matching("recurrence!=''"), ["sub"])
+        self.assertEqual(matching('overall_score=""'), ["lamp", "desk"])
+        self.assertEqual(matching('target_date!=""'), ["chair"])
+
+
+class ComparisonTests(unittest.TestCase):
+    def test_numeric_comparisons(self):
+        self.assertEqual(matching("cost>40"), ["chair", "desk"])
+        self.assertEqual(matching("cost>=40"), ["lamp", "chair", "desk"])
+        self.assertEqual(matching("cost<=12"), ["sub"])
+        self.assertEqual(matching("cost=250"), ["chair"])
+        self.assertEqual(matching("cost!=250.00"), ["lamp", "sub", "desk"])
+        self.assertEqual(matching("urgency<3"), ["lamp"])
+
+    def test_unset_numbers_only_satisfy_not_equal(self):
+        self.assertEqual(matching("overall_score<4"), ["sub"])
+        self.assertEqual(matching("overall_score!=4.2"), ["lamp", "sub", "desk"])
+
+    def test_date_comparisons_are_by_day(self):
+        self.assertEqual(matching("date=2025-03-01"), ["sub"])
+        self.assertEqual(matching("date>2025-01-15"), ["sub"])
+        self.assertEqual(matching("date<=2025-01-15"), ["lamp", "chair", "desk"])
+        self.assertEqual(matching("target_date<2025-07-01"), ["chair"])
+        self.assertEqual(matching("target_date>2025-07-01"), [])
+
+    def test_edit_times_compare_by_day(self):
+        added = datetime(2025, 1, 10, 8, 0)
+        items = [
+            make_item(id="old", created_at=added, updated_at=added),
+            make_item(id="edited", created_at=added, updated_at=datetime(2025, 2, 3, 23, 59)),
+        ]
+
+        def ids(expression: str) -> list:
+            return [item.id for item in items if parse_filter(expression)(item)]
+
+        self.assertEqual(ids("updated_at>=2025-02-01"), ["edited"])
+        self.assertEqual(ids("updated_at=2025-02-03"), ["edited"])
+        self.assertEqual(ids("created_at=2025-01-10 and updated_at<2025-02-01"), ["old"])
+        self.assertEqual(ids("created_at>2025-01-10"), [])
+
+    def test_score_and_overall_are_aliases(self):
+        self.assertEqual(matching("score>4"), ["chair"])
+        self.assertEqual(matching("overall>=3.5"), ["sub", "chair"])
+        self.assertEqual(matching("Score>=3.5"), matching("overall_score>=3.5"))
+        self.assertIn("score", filter_fields())
+        self.assertNotIn("history", filter_fields())
+
+
+class FilterErrorTests(unittest.TestCase):
+    def assertFilterError(self, expression: str, message: str) -> None:
+        with self.assertRaises(FilterError) as caught:
+            parse_filter(expression)
+        self.assertIn(message, str(caught.exception))
+
+    def test_unknown_field(self):
+        self.assertFilterError("price>10", "unknown field 'price' at position 1")
+        self.assertFilterError("cost>1 and colour=red", "unknown field 'colour' at position 12")
+        self.assertFilterError("history~x", "unknown field 'history'")
+
+    def test_unbalanced_parentheses(self):
+        self.assertFilterError("(cost>10 or urgency=5", "missing ')' for '(' at position 1")
+        self.assertFilterError("cost>10)", "unexpected ')' at position 8")
+        self.assertFilterError("((cost>10)", "missing ')' for '(' at position 1")
+        self.assertFilterError("()", "unexpected ')' at position 2")
+
+    def test_non_numeric_values(self):
+        self.assertFilterError("cost>cheap", "'cost' needs a number, got 'cheap'")
+        self.assertFilterError("score=high", "'score' needs a number, got 'high'")
+
+    def test_bad_dates(self):
+        self.assertFilterError("date>last-week", "invalid date 'last-week'")
+
+    def test_operators_that_do_not_fit_the_field(self):
+        self.assertFilterError("cost~4", "'~' only applies to text fields, not 'cost'")
+        self.assertFilterError("location>b", "'>' needs a numeric or date field, not 'location'")
+        self.assertFilterError("updated_at~2025", "'~' only applies to text fields, not 'updated_at'")
+
+    def test_incomplete_expressions(self):
+        self.assertFilterError("cost>", "expression ends too early")
+        self.assertFilterError("cost>10 and", "expression ends too early")
+        self.assertFilterError("cost > > 3", "expected a value after '>' at position 8")
+        self.assertFilterError("= 3", "unexpected '=' at position 1")
+
+    def test_filter_error_is_a_value_error(self):
+        with self.assertRaises(ValueError):
+            parse_filter("price>10")
+
+
+if __name__ == "__main__":
+    unittest.main()
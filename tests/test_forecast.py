@@ -0,0 +1,52 @@
+import unittest
+from datetime import datetime
+
+from core.forecast import cashflow_projection
+from tests.support import make_item, make_money
+
+START = datetime(2025, 1, 10)
+
+
+class CashflowProjectionTests(unittest.TestCase):
+    def setUp(self):
+        self.money = [
+            # Before the start: these make up the opening balance of 800.
+            make_money(id="m1", date=datetime(2025, 1, 1), entry_type="income", amount=1000.0),
+            make_money(id="m2", date=datetime(2024, 12, 20), entry_type="Expense", amount=200.0),
+            # Scheduled after the start.
+            make_money(id="m3", date=datetime(2025, 2, 5), entry_type="INCOME", amount=500.0),
+            make_money(id="m4", date=datetime(2025, 3, 15), entry_type="expense", amount=100.0),
+            make_money(id="m5", date=datetime(2025, 2, 1), entry_type="transfer", amount=50.0),
+            # Past the horizon.
+            make_money(id="m6", date=datetime(2025, 4, 2), entry_type="income", amount=999.0),
+        ]
+        self.items = [
+            make_item(id="sub", date=datetime(2024, 11, 20), cost=30.0, recurrence=" Monthly"),
+            make_item(id="gym", date=datetime(2025, 1, 1), cost=10.0, recurrence="biweekly"),
+            make_item(id="desk", date=datetime(2025, 2, 1), cost=250.0, recurrence="once"),
+            make_item(id="chair", date=datetime(2025, 2, 1), cost=120.0),
+        ]
+
+    def test_mixed_set_nets_income_against_money_and_item_expenses(self):
+        projection = cashflow_projection(self.items, self.money, 3, start=START)
+        self.assertEqual([month.month for month in projection], ["2025-01", "2025-02", "2025-03"])
+        # The monthly item is paid on the 20th; the biweekly one on Jan 15 and 29, Feb 12 and 26, Mar 12 and 26.
+        self.assertEqual([month.income for month in projection], [0.0, 500.0, 0.0])
+        self.assertEqual([month.expenses for month in projection], [50.0, 50.0, 150.0])
+        self.assertEqual([month.net for month in projection], [-50.0, 450.0, -150.0])
+        self.assertEqual([month.balance for month in projection], [750.0, 1200.0, 1050.0])
+
+    def test_one_off_items_are_not_projected(self):
+        projection = cashflow_projection(self.items[2:], [], 3, start=START)
+        self.assertEqual([month.expenses for month in projection], [0.0, 0.0, 0.0])
+
+    def test_months_follow_the_start_day(self):
+        projection = cashflow_projection(self.items[:1], self.money, 2, start=START, start_day=25)
+        # Periods run Dec 25 - Jan 25 and Jan 25 - Feb 25, so the Feb 5 income lands in "2025-01".
+        self.assertEqual([month.month for month in projection], ["2024-12", "2025-01"])
+        self.assertEqual([month.income for month in projection], [0.0, 500.0])
+        self.assertEqual([month.expenses for month in projection], [30.0, 30.0])
+
+
+if __name__ == "__main__":
+    unittest.main()
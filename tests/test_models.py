@@ -0,0 +1,172 @@
+import os
+import time
+import unittest
+import uuid
+from datetime import datetime, timedelta, timezone
+from unittest import mock
+
+from core.models import ItemRecord, MoneyRecord, format_stored_date, new_id, parse_stored_date
+from tests.support import make_item, make_money
+
+
+class SignedAmountTests(unittest.TestCase):
+    def test_income_is_positive_and_expense_negative(self):
+        self.assertEqual(make_money(entry_type="income", amount=120.0).signed_amount(), 120.0)
+        self.assertEqual(make_money(entry_type="expense", amount=45.5).signed_amount(), -45.5)
+
+    def test_entry_type_case_and_spacing_are_ignored(self):
+        self.assertEqual(make_money(entry_type="INCOME", amount=10.0).signed_amount(), 10.0)
+        self.assertEqual(make_money(entry_type=" Expense ", amount=10.0).signed_amount(), -10.0)
+
+    def test_sign_follows_the_type_not_the_stored_amount(self):
+        self.assertEqual(make_money(entry_type="income", amount=-10.0).signed_amount(), 10.0)
+        self.assertEqual(make_money(entry_type="expense", amount=-10.0).signed_amount(), -10.0)
+
+    def test_other_types_do_not_move_the_balance(self):
+        for entry_type in ("transfer", "Transfer", ""):
+            self.assertEqual(make_money(entry_type=entry_type, amount=300.0).signed_amount(), 0.0)
+
+    def test_zero_amounts_stay_zero(self):
+        for entry_type in ("income", "expense"):
+            self.assertEqual(make_money(entry_type=entry_type, amount=0.0).signed_amount(), 0.0)
+
+    def test_balance_is_the_sum_of_signed_amounts(self):
+        entries = [
+            make_money(entry_type="Income", amount=1000.0),
+            make_money(entry_type="expense", amount=250.0),
+            make_money(entry_type="EXPENSE", amount=0.0),
+            make_money(entry_type="transfer", amount=75.0),
+        ]
+        self.assertEqual(sum(entry.signed_amount() for entry in entries), 750.0)
+
+
+class EditTimeTests(unittest.TestCase):
+    ADDED = datetime(2025, 1, 10, 8, 0, 5)
+
+    def test_new_records_are_stamped_with_one_time(self):
+        for record in (make_item(), make_money()):
+            self.assertIsNotNone(record.created_at)
+            self.assertEqual(record.updated_at, record.created_at)
+
+    def test_touch_bumps_updated_at_only(self):
+        for record in (make_item(created_at=self.ADDED), make_money(created_at=self.ADDED)):
+            record.touch()
+            self.assertEqual(record.created_at, self.ADDED)
+            self.assertGreater(record.updated_at, self.ADDED)
+
+    def test_adding_a_note_touches_the_item(self):
+        item = make_item(created_at=self.ADDED)
+        item.add_note("asked for a discount")
+        self.assertGreater(item.updated_at, self.ADDED)
+
+    def test_times_round_trip_to_the_second(self):
+        edited = datetime(2025, 2, 3, 17, 45, 30)
+        item = ItemRecord.from_row(make_item(created_at=self.ADDED, updated_at=edited).to_row())
+        money = MoneyRecord.from_row(make_money(created_at=self.ADDED, updated_at=edited).to_row())
+        for record in (item, money):
+            self.assertEqual((record.created_at, record.updated_at), (self.ADDED, edited))
+
+    def test_rows_without_the_columns_use_the_record_date(self):
+        when = datetime(2024, 11, 2, 9, 0)
+        for cls, record in ((ItemRecord, make_item(date=when)), (MoneyRecord, make_money(date=when))):
+            row = record.to_row()
+            del row["created_at"], row["updated_at"]
+            loaded = cls.from_row(row)
+            self.assertEqual((loaded.created_at, loaded.updated_at), (when, when))
+
+
+class StableIdTests(unittest.TestCase):
+    def test_same_fields_give_the_same_id(self):
+        self.assertEqual(make_item(id="a").stable_id(), make_item(id="b").stable_id())
+        self.assertEqual(make_money(id="a").stable_id(), make_money(id="b").stable_id())
+
+    def test_ids_are_version_5_uuids(self):
+        self.assertEqual(uuid.UUID(make_item().stable_id()).version, 5)
+
+    def test_each_identifying_field_changes_the_id(self):
+        base = make_item().stable_id()
+        variants = [
+            make_item(date=datetime(2025, 1, 16, 12, 0)),
+            make_item(product="Floor lamp"),
+            make_item(location="store"),
+            make_item(reference="https://example.com/lamp"),
+            make_item(cost=40.01),
+        ]
+        ids = [item.stable_id() for item in variants]
+        self.assertNotIn(base, ids)
+        self.assertEqual(len(set(ids)), len(ids))
+
+    def test_fields_outside_the_identity_keep_the_id(self):
+        self.assertEqual(make_item().stable_id(), make_item(urgency=5, justification="needed").stable_id())
+
+    def test_items_and_money_never_share_an_id(self):
+        self.assertNotEqual(make_item().stable_id(), make_money().stable_id())
+
+    def test_rows_without_an_id_get_the_same_id_on_every_import(self):
+        item_row = {**make_item().to_row(), "id": ""}
+        money_row = {**make_money().to_row(), "id": " "}
+        self.assertEqual(ItemRecord.from_row(item_row).id, ItemRecord.from_row(dict(item_row)).id)
+        self.assertEqual(ItemRecord.from_row(item_row).id, make_item().stable_id())
+        self.assertEqual(MoneyRecord.from_row(money_row).id, make_money().stable_id())
+
+    def test_interactive_ids_stay_random(self):
+        self.assertNotEqual(new_id(), new_id())
+        self.assertEqual(uuid.UUID(new_id()).version, 4)
+
+
+PLUS_ONE = timezone(timedelta(hours=1))
+
+
+@unittest.skipUnless(hasattr(time, "tzset"), "needs time.tzset to change the local timezone")
+class StoredDateTimezoneTests(unittest.TestCase):
+    def use_timezone(self, tz: str) -> None:
+        """Make the process's local timezone tz (POSIX form: "UTC-1" is one hour ahead of UTC)."""
+        patch = mock.patch.dict(os.environ, {"TZ": tz})
+        patch.start()
+        self.addCleanup(time.tzset)
+        self.addCleanup(patch.stop)
+        time.tzset()
+
+    def test_stored_dates_carry_the_local_offset(self):
+        self.use_timezone("UTC-1")
+        self.assertEqual(format_stored_date(datetime(2025, 1, 31, 9, 30)), "2025-01-31 09:30+01:00")
+
+    def test_instant_is_kept_when_read_in_another_timezone(self):
+        self.use_timezone("UTC-1")
+        stored = format_stored_date(datetime(2025, 1, 31, 9, 30))
+        self.use_timezone("UTC+5")
+        self.assertEqual(parse_stored_date(stored), datetime(2025, 1, 31, 3, 30))
+
+    def test_same_timezone_round_trip_is_equal(self):
+        self.use_timezone("UTC+5")
+        when = datetime(2024, 2, 29, 23, 45)
+        self.assertEqual(parse_stored_date(format_stored_date(when)), when)
+
+    def test_record_round_trip_across_timezones(self):
+        self.use_timezone("UTC-1")
+        row = make_item(date=datetime(2025, 6, 1, 0, 15), target_date=datetime(2025, 7, 1, 8, 0)).to_row()
+        self.use_timezone("UTC")
+        item = ItemRecord.from_row(row)
+        self.assertEqual(item.date, datetime(2025, 5, 31, 23, 15))
+        self.assertEqual(item.target_date, datetime(2025, 7, 1, 7, 0))
+
+    def test_legacy_values_without_an_offset_are_local_time(self):
+        self.use_timezone("UTC+5")
+        self.assertEqual(parse_stored_date("2025-01-31 09:30"), datetime(2025, 1, 31, 9, 30))
+        row = {**make_item().to_row(), "date": "2025-01-31 09:30", "created_at": "", "updated_at": ""}
+        item = ItemRecord.from_row(row)
+        self.assertEqual(item.date, datetime(2025, 1, 31, 9, 30))
+        # The next save adds the offset without moving the time.
+        self.assertEqual(item.to_row()["date"], "2025-01-31 09:30-05:00")
+
+    def test_history_notes_are_stamped_with_the_offset(self):
+        self.use_timezone("UTC+5")
+        item = make_item()
+        note = item.add_note(" checked the price ", when=datetime(2025, 3, 1, 10, 0, tzinfo=PLUS_ONE))
+        self.assertEqual(note, "[2025-03-01 04:00-05:00] checked the price")
+        item.add_note("again")
+        self.assertRegex(item.history[-1], r"^\[\d{4}-\d\d-\d\d \d\d:\d\d-05:00\] again$")
+
+
+if __name__ == "__main__":
+    unittest.main()
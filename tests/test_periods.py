@@ -0,0 +1,60 @@
+import unittest
+from datetime import datetime
+
+from core.periods import period_label, period_of, period_start, shift_period
+from core.reports import monthly_balances
+from tests.support import make_money
+
+
+class MonthEndStartTests(unittest.TestCase):
+    def test_day_31_start_clamps_in_30_day_months(self):
+        self.assertEqual(period_start((2025, 4), 31), datetime(2025, 4, 30))
+        self.assertEqual(period_start((2025, 5), 31), datetime(2025, 5, 31))
+
+    def test_day_31_start_clamps_in_february(self):
+        self.assertEqual(period_start((2025, 2), 31), datetime(2025, 2, 28))
+        self.assertEqual(period_start((2024, 2), 31), datetime(2024, 2, 29))
+
+    def test_clamped_day_opens_the_new_period(self):
+        self.assertEqual(period_of(datetime(2025, 4, 29, 23, 59), 31), (2025, 3))
+        self.assertEqual(period_of(datetime(2025, 4, 30), 31), (2025, 4))
+        self.assertEqual(period_of(datetime(2025, 2, 27), 31), (2025, 1))
+        self.assertEqual(period_of(datetime(2025, 2, 28), 31), (2025, 2))
+        self.assertEqual(period_of(datetime(2025, 3, 30), 31), (2025, 2))
+
+    def test_day_1_start_gives_calendar_months(self):
+        self.assertEqual(period_of(datetime(2025, 3, 1)), (2025, 3))
+        self.assertEqual(period_of(datetime(2025, 2, 28, 23, 59)), (2025, 2))
+
+
+class YearBoundaryTests(unittest.TestCase):
+    def test_day_15_start_carries_december_into_january(self):
+        self.assertEqual(period_of(datetime(2024, 12, 15), 15), (2024, 12))
+        self.assertEqual(period_of(datetime(2025, 1, 10), 15), (2024, 12))
+        self.assertEqual(period_of(datetime(2025, 1, 15), 15), (2025, 1))
+
+    def test_day_15_start_before_the_first_period_of_the_year(self):
+        self.assertEqual(period_of(datetime(2024, 12, 14), 15), (2024, 11))
+
+    def test_shift_period_wraps_years(self):
+        self.assertEqual(shift_period((2025, 1), -1), (2024, 12))
+        self.assertEqual(shift_period((2024, 12), 1), (2025, 1))
+        self.assertEqual(shift_period((2025, 3), -15), (2023, 12))
+        self.assertEqual(period_label(shift_period((2025, 1), -1)), "2024-12")
+
+    def test_monthly_balances_group_by_the_shifted_period(self):
+        money = [
+            make_money(id="a", entry_type="income", amount=100.0, date=datetime(2024, 12, 14)),
+            make_money(id="b", entry_type="expense", amount=30.0, date=datetime(2024, 12, 20)),
+            make_money(id="c", entry_type="expense", amount=20.0, date=datetime(2025, 1, 10)),
+            make_money(id="d", entry_type="expense", amount=5.0, date=datetime(2025, 1, 15)),
+        ]
+        balances = monthly_balances(money, datetime(2025, 1, 20), 2, start_day=15)
+        self.assertEqual(
+            [(row.month, row.net, row.balance) for row in balances],
+            [("2024-12", -50.0, 50.0), ("2025-01", -5.0, 45.0)],
+        )
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,80 @@
+import unittest
+from datetime import datetime
+from itertools import islice
+
+from core.recurrence import add_months, next_occurrence, occurrences
+
+
+def first(count: int, start: datetime, recurrence: str) -> list:
+    return [when.date().isoformat() for when in islice(occurrences(start, recurrence), count)]
+
+
+class MonthEndTests(unittest.TestCase):
+    def test_jan_31_clamps_to_the_end_of_february(self):
+        self.assertEqual(next_occurrence(datetime(2025, 1, 31), "monthly"), datetime(2025, 2, 28))
+        self.assertEqual(next_occurrence(datetime(2024, 1, 31), "monthly"), datetime(2024, 2, 29))
+
+    def test_day_31_series_stays_on_the_month_end(self):
+        self.assertEqual(
+            first(6, datetime(2025, 1, 31), "monthly"),
+            ["2025-01-31", "2025-02-28", "2025-03-31", "2025-04-30", "2025-05-31", "2025-06-30"],
+        )
+
+    def test_day_30_series_returns_to_the_30th_after_february(self):
+        self.assertEqual(first(3, datetime(2024, 1, 30), "monthly"), ["2024-01-30", "2024-02-29", "2024-03-30"])
+
+    def test_clamped_date_without_the_anchor_keeps_the_clamped_day(self):
+        self.assertEqual(next_occurrence(datetime(2025, 2, 28), "monthly"), datetime(2025, 3, 28))
+        self.assertEqual(next_occurrence(datetime(2025, 2, 28), "monthly", day=31), datetime(2025, 3, 31))
+
+    def test_quarterly_from_a_month_end(self):
+        self.assertEqual(
+            first(5, datetime(2024, 11, 30), "quarterly"),
+            ["2024-11-30", "2025-02-28", "2025-05-30", "2025-08-30", "2025-11-30"],
+        )
+
+    def test_time_of_day_is_kept(self):
+        self.assertEqual(next_occurrence(datetime(2025, 1, 31, 18, 45), "monthly"), datetime(2025, 2, 28, 18, 45))
+
+
+class LeapYearTests(unittest.TestCase):
+    def test_feb_29_yearly_falls_on_feb_28_until_the_next_leap_year(self):
+        self.assertEqual(
+            first(5, datetime(2024, 2, 29), "yearly"),
+            ["2024-02-29", "2025-02-28", "2026-02-28", "2027-02-28", "2028-02-29"],
+        )
+
+    def test_century_years_are_only_leap_every_400_years(self):
+        self.assertEqual(add_months(datetime(2096, 2, 29), 48), datetime(2100, 2, 28))
+        self.assertEqual(add_months(datetime(1996, 2, 29), 48), datetime(2000, 2, 29))
+
+    def test_weekly_steps_cross_feb_29(self):
+        self.assertEqual(next_occurrence(datetime(2024, 2, 26), "weekly"), datetime(2024, 3, 4))
+        self.assertEqual(next_occurrence(datetime(2024, 2, 22), "weekly"), datetime(2024, 2, 29))
+
+
+class RolloverTests(unittest.TestCase):
+    def test_monthly_rolls_into_the_next_year(self):
+        self.assertEqual(next_occurrence(datetime(2025, 12, 15), "monthly"), datetime(2026, 1, 15))
+
+    def test_quarterly_and_yearly_roll_over(self):
+        self.assertEqual(next_occurrence(datetime(2025, 11, 30), "quarterly"), datetime(2026, 2, 28))
+        self.assertEqual(next_occurrence(datetime(2025, 12, 31), "yearly"), datetime(2026, 12, 31))
+
+    def test_day_steps_roll_over(self):
+        self.assertEqual(next_occurrence(datetime(2025, 12, 25), "biweekly"), datetime(2026, 1, 8))
+
+    def test_negative_months_go_back_across_years(self):
+        self.assertEqual(add_months(datetime(2025, 1, 31), -2), datetime(2024, 11, 30))
+
+    def test_recurrence_names_ignore_case(self):
+        self.assertEqual(next_occurrence(datetime(2025, 1, 31), "Monthly"), datetime(2025, 2, 28))
+
+    def test_one_off_recurrences_do_not_repeat(self):
+        for recurrence in ("once", "none", ""):
+            with self.assertRaises(ValueError):
+                next_occurrence(datetime(2025, 1, 1), recurrence)
+
+
+if __name__ == "__main__":
+    unittest.main()
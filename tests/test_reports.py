@@ -0,0 +1,54 @@
+import unittest
+
+from core.reports import decision_overrides, decision_tally, decisions_csv, decisions_markdown
+from tests.support import make_item
+
+ITEMS = [
+    make_item(id="a", decision="buy", cost=100.0, overall_score=4.0),
+    make_item(id="b", decision="buy", cost=50.0, overall_score=2.0),
+    make_item(id="c", decision="buy", cost=10.0, overall_score=None),
+    make_item(id="d", decision="skip", cost=300.0, overall_score=4.5),
+    make_item(id="e", decision="", cost=20.0, overall_score=3.0),
+]
+
+
+class DecisionTallyTests(unittest.TestCase):
+    def test_counts_costs_and_averages_per_decision(self):
+        self.assertEqual(
+            [(row.decision, row.count, row.total_cost, row.average_score) for row in decision_tally(ITEMS)],
+            [
+                ("buy", 3, 160.0, 3.0),
+                ("skip", 1, 300.0, 4.5),
+                ("defer", 0, 0.0, None),
+                ("undecided", 1, 20.0, 3.0),
+            ],
+        )
+
+    def test_every_item_is_counted_once(self):
+        self.assertEqual(sum(row.count for row in decision_tally(ITEMS)), len(ITEMS))
+
+    def test_group_with_no_scored_items_has_no_average(self):
+        tally = decision_tally([make_item(decision="defer", overall_score=None)])
+        self.assertEqual([(row.count, row.average_score) for row in tally if row.decision == "defer"], [(1, None)])
+
+    def test_overrides_go_against_the_score(self):
+        self.assertEqual([item.id for item in decision_overrides(ITEMS)], ["d", "b"])
+
+    def test_csv_and_markdown_show_the_counts(self):
+        self.assertEqual(
+            decisions_csv(ITEMS).splitlines(),
+            [
+                "decision,items,total_cost,average_score",
+                "buy,3,160.00,3.00",
+                "skip,1,300.00,4.50",
+                "defer,0,0.00,",
+                "undecided,1,20.00,3.00",
+            ],
+        )
+        markdown = decisions_markdown(ITEMS)
+        self.assertIn("| buy | 3 | $160.00 | 3.00 |", markdown)
+        self.assertIn("| defer | 0 | $0.00 | — |", markdown)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,172 @@
+import unittest
+from datetime import datetime, timedelta
+
+from core.models import ItemRecord
+from scoring.scoring import score_item
+from tests.support import default_weights, make_item
+
+
+class WeightNormalizationTests(unittest.TestCase):
+    ITEM = make_item(cost=250.0, urgency=5, value=2, want=4, price_comp=1, effect=3, effort=2)
+
+    def scaled(self, factor: float, **overrides: float) -> dict:
+        weights = default_weights()
+        weights["weights"].update(overrides)
+        weights["weights"] = {key: weight * factor for key, weight in weights["weights"].items()}
+        return weights
+
+    def test_scaling_every_weight_leaves_the_overall_unchanged(self):
+        base = score_item(self.ITEM, self.scaled(1.0, urgency=2.0, effort=0.5)).overall
+        for factor in (7.5, 0.25):
+            with self.subTest(factor=factor):
+                scaled = score_item(self.ITEM, self.scaled(factor, urgency=2.0, effort=0.5)).overall
+                self.assertAlmostEqual(scaled, base)
+
+    def test_overall_stays_on_the_1_to_5_scale(self):
+        overall = score_item(self.ITEM, self.scaled(7.5)).overall
+        self.assertGreaterEqual(overall, 1.0)
+        self.assertLessEqual(overall, 5.0)
+
+    def test_changing_the_proportions_moves_the_overall(self):
+        even = score_item(self.ITEM, self.scaled(1.0)).overall
+        # Urgency is the item's highest sub-score, so weighting it more lifts the overall.
+        self.assertGreater(score_item(self.ITEM, self.scaled(1.0, urgency=3.0)).overall, even)
+        self.assertLess(score_item(self.ITEM, self.scaled(1.0, price_comp=3.0)).overall, even)
+
+
+class AnnualizedCostTests(unittest.TestCase):
+    def test_annualized_cost_multiplies_by_occurrences_per_year(self):
+        self.assertEqual(make_item(cost=10.0, recurrence="weekly").annualized_cost(), 520.0)
+        self.assertEqual(make_item(cost=20.0, recurrence="Monthly").annualized_cost(), 240.0)
+        self.assertEqual(make_item(cost=20.0, recurrence=" monthly ").annualized_cost(), 240.0)
+
+    def test_one_off_items_keep_their_single_cost(self):
+        for recurrence in ("", "once", "none"):
+            self.assertEqual(make_item(cost=20.0, recurrence=recurrence).annualized_cost(), 20.0)
+
+    def test_per_instance_cost_is_scored_when_annualizing_is_off(self):
+        weekly = make_item(cost=10.0, recurrence="weekly")
+        self.assertEqual(score_item(weekly, default_weights()).field_scores["cost"], 5.0)
+
+    def test_weekly_item_is_scored_on_its_yearly_cost(self):
+        weekly = make_item(cost=10.0, recurrence="weekly")
+        result = score_item(weekly, default_weights(annualize_recurring_cost=True))
+        # $520 a year falls in the up-to-$800 band.
+        self.assertEqual(result.field_scores["cost"], 2.0)
+
+    def test_monthly_item_is_scored_on_its_yearly_cost(self):
+        monthly = make_item(cost=20.0, recurrence="monthly")
+        self.assertEqual(score_item(monthly, default_weights()).field_scores["cost"], 5.0)
+        self.assertEqual(score_item(monthly, default_weights(annualize_recurring_cost=True)).field_scores["cost"], 3.0)
+
+    def test_annualizing_lowers_the_overall_score_of_recurring_items_only(self):
+        weights, annualized = default_weights(), default_weights(annualize_recurring_cost=True)
+        monthly = make_item(cost=20.0, recurrence="monthly")
+        one_off = make_item(cost=20.0)
+        self.assertLess(score_item(monthly, annualized).overall, score_item(monthly, weights).overall)
+        self.assertEqual(score_item(one_off, annualized).overall, score_item(one_off, weights).overall)
+
+
+class CostPerUseTests(unittest.TestCase):
+    def test_cost_is_spread_over_the_expected_uses(self):
+        self.assertEqual(make_item(cost=120.0, expected_uses=40).cost_per_use(), 3.0)
+
+    def test_no_estimate_gives_no_cost_per_use(self):
+        self.assertIsNone(make_item(expected_uses=None).cost_per_use())
+        self.assertIsNone(make_item(expected_uses=0).cost_per_use())
+
+    def test_dimension_is_only_scored_once_it_has_a_weight(self):
+        item = make_item(cost=120.0, expected_uses=40)
+        self.assertNotIn("cost_per_use", score_item(item, default_weights()).field_scores)
+        weights = default_weights()
+        weights["weights"]["cost_per_use"] = 1.0
+        # $3 a use falls in the up-to-$5 band.
+        self.assertEqual(score_item(item, weights).field_scores["cost_per_use"], 4.0)
+
+    def test_missing_estimate_scores_neutral(self):
+        weights = default_weights()
+        weights["weights"]["cost_per_use"] = 1.0
+        self.assertEqual(score_item(make_item(), weights).field_scores["cost_per_use"], 3.0)
+
+
+class TargetDateTests(unittest.TestCase):
+    TARGET = datetime(2025, 6, 30)
+
+    def weights_on(self, today: datetime) -> dict:
+        weights = default_weights(as_of=today)
+        weights["weights"]["target_date"] = 1.0
+        return weights
+
+    def target_score(self, today: datetime, item=None) -> float:
+        item = item or make_item(target_date=self.TARGET)
+        return score_item(item, self.weights_on(today)).field_scores["target_date"]
+
+    def test_score_rises_as_the_target_nears(self):
+        days_left = [60, 30, 20, 10, 1]
+        scores = [self.target_score(self.TARGET - timedelta(days=days)) for days in days_left]
+        self.assertEqual(scores[:2], [1.0, 1.0])
+        self.assertEqual(scores, sorted(scores))
+        self.assertLess(scores[2], scores[3])
+        self.assertAlmostEqual(scores[-1], 1.0 + 4.0 * 29 / 30)
+
+    def test_overall_score_rises_with_it(self):
+        item = make_item(target_date=self.TARGET)
+        far = score_item(item, self.weights_on(self.TARGET - timedelta(days=45))).overall
+        near = score_item(item, self.weights_on(self.TARGET - timedelta(days=5))).overall
+        self.assertGreater(near, far)
+
+    def test_reached_and_past_targets_score_highest(self):
+        self.assertEqual(self.target_score(self.TARGET), 5.0)
+        self.assertEqual(self.target_score(self.TARGET + timedelta(days=90)), 5.0)
+
+    def test_ramp_length_comes_from_the_date_settings(self):
+        weights = self.weights_on(self.TARGET - timedelta(days=30))
+        weights["date_scoring"] = dict(weights["date_scoring"], target_ramp_days=60)
+        self.assertEqual(score_item(make_item(target_date=self.TARGET), weights).field_scores["target_date"], 3.0)
+
+    def test_items_without_a_target_score_neutral(self):
+        self.assertEqual(self.target_score(self.TARGET, make_item(target_date=None)), 3.0)
+
+    def test_dimension_is_only_scored_once_it_has_a_weight(self):
+        item = make_item(target_date=self.TARGET)
+        self.assertNotIn("target_date", score_item(item, default_weights(as_of=self.TARGET)).field_scores)
+
+
+class EffortTests(unittest.TestCase):
+    def weights(self) -> dict:
+        weights = default_weights()
+        weights["weights"]["effort"] = 1.0
+        return weights
+
+    def effort_score(self, effort) -> float:
+        return score_item(make_item(effort=effort), self.weights()).field_scores["effort"]
+
+    def test_score_is_inverted(self):
+        self.assertEqual([self.effort_score(effort) for effort in range(1, 6)], [5.0, 4.0, 3.0, 2.0, 1.0])
+
+    def test_unrated_effort_scores_neutral(self):
+        self.assertEqual(self.effort_score(None), 3.0)
+
+    def test_overall_score_falls_as_effort_rises(self):
+        overall = [score_item(make_item(effort=effort), self.weights()).overall for effort in (1, 3, 5)]
+        self.assertEqual(overall, sorted(overall, reverse=True))
+        self.assertGreater(overall[0], overall[1])
+
+    def test_dimension_is_only_scored_once_it_has_a_weight(self):
+        result = score_item(make_item(effort=5), default_weights())
+        self.assertNotIn("effort", result.field_scores)
+        self.assertEqual(result.overall, score_item(make_item(effort=1), default_weights()).overall)
+
+    def test_effort_round_trips_through_a_row(self):
+        self.assertEqual(ItemRecord.from_row(make_item(effort=4).to_row()).effort, 4)
+        self.assertIsNone(ItemRecord.from_row(make_item(effort=None).to_row()).effort)
+
+    def test_out_of_range_effort_is_rejected_on_load(self):
+        for raw in ("0", "6", "9", "-1"):
+            with self.subTest(effort=raw):
+                with self.assertRaisesRegex(ValueError, f"effort must be 1-5 or empty, got '{raw}'"):
+                    ItemRecord.from_row({**make_item().to_row(), "effort": raw})
+
+
+if __name__ == "__main__":
+    unittest.main()
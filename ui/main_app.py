@@ -2,26 +2,407 @@ from __future__ import annotations
 
 import ctypes
 import math
+from copy import deepcopy
 import os
 import subprocess
 import sys
+import threading
+from datetime import datetime
 from pathlib import Path
-from typing import Dict, List, Optional
+from typing import Any, Callable, Dict, List, Optional, Tuple
 
 from PySide6 import QtCore, QtGui, QtWidgets
 
-from core.backup import create_backup
-from core.config_manager import ConfigManager, ensure_paths, ensure_startup_files
-from core.csv_storage import read_bundle, read_items, read_money, write_bundle, write_items, write_money
-from core.models import DATE_FMT, ItemRecord, MoneyRecord
-from scoring.scoring import ScoreResult, score_item
+from core.backup import (
+    backup_usage,
+    create_backup,
+    enforce_retention,
+    latest_backup,
+    restore_backup,
+    startup_backup,
+)
+from core.config_manager import (
+    DEFAULT_BOOK,
+    ConfigManager,
+    describe_money_template,
+    ensure_paths,
+    ensure_startup_files,
+    score_color,
+)
+from core.csv_storage import (
+    ImportReport,
+    read_bundle,
+    read_items,
+    read_money,
+    set_lock_timeout,
+    validate_import,
+    validate_pasted_rows,
+    write_bundle,
+    write_items,
+    write_jsonl,
+    write_money,
+)
+from core.dependencies import dependency_error
+from core.duplicates import duplicate_groups, merge_group
+from core.errors import BackupSourceMissingError, DataFileLockedError, DataFileParseError
+from core.filters import FilterError, parse_filter
+from core.merge import Conflict, find_conflicts, merge_records, resolve_conflicts
+from core.models import DATE_FMT, DECISIONS, UNCATEGORIZED, ItemRecord, MoneyRecord, format_score, new_id
+from core.quick_add import parse_quick_add
+from core.reports import reconciliation
+from core.relocation import changed_data_paths, relocate_data_file, remember_data_paths
+from core.search import FUZZY_THRESHOLD, item_match_score
+from core.splits import replace_with_split, split_entry
+from core.write_queue import WriteQueue
+from scoring.scoring import ScoreResult, overall_scores, score_item
+
+
+CONFLICT_CHOICES = {
+    "Take the imported version": "keep-theirs",
+    "Keep my version": "keep-mine",
+    "Keep whichever has the later date": "newest",
+    "Decide each one, field by field": "prompt",
+}
+
+
+def _merge_import(parent: QtWidgets.QWidget, existing: list, imported: list, noun: str) -> Optional[list]:
+    """existing merged with imported by id, asking how to settle ids that differ; None cancels the import."""
+    conflicts = find_conflicts(existing, imported)
+    if not conflicts:
+        return merge_records(existing, imported)
+    choice, ok = QtWidgets.QInputDialog.getItem(
+        parent,
+        "Import conflicts",
+        f"{len(conflicts)} imported {noun} have ids that already exist with different values.\n"
+        "Which version should be kept?",
+        list(CONFLICT_CHOICES),
+        0,
+        False,
+    )
+    if not ok:
+        return None
+    strategy = CONFLICT_CHOICES[choice]
+    if strategy == "prompt":
+        for position, conflict in enumerate(conflicts, 1):
+            keep = _ask_conflict(parent, conflict, f"{position} of {len(conflicts)}")
+            if keep is None:
+                return None
+            conflict.keep, conflict.reason = keep, "your choice"
+    else:
+        resolve_conflicts(conflicts, strategy)
+    mine = sum(1 for conflict in conflicts if conflict.keep == "mine")
+    QtWidgets.QMessageBox.information(
+        parent,
+        "Import conflicts",
+        f"Kept yours for {mine} and took the import's for {len(conflicts) - mine}:\n\n"
+        + "\n".join(conflict.describe() for conflict in conflicts[:25])
+        + (f"\n... and {len(conflicts) - 25} more" if len(conflicts) > 25 else ""),
+    )
+    return merge_records(existing, imported, conflicts)
+
+
+def _ask_conflict(parent: QtWidgets.QWidget, conflict: Conflict, progress: str) -> Optional[str]:
+    """Show one conflict field by field; returns "mine", "theirs", or None to cancel the import."""
+    dialog = QtWidgets.QDialog(parent)
+    dialog.setWindowTitle(f"Conflict {progress}")
+    layout = QtWidgets.QVBoxLayout(dialog)
+    layout.addWidget(QtWidgets.QLabel(f"Id {conflict.id} exists with different values:"))
+    values = conflict.values()
+    table = QtWidgets.QTableWidget(len(values), 3)
+    table.setHorizontalHeaderLabels(["Field", "Yours", "Imported"])
+    table.verticalHeader().setVisible(False)
+    table.setEditTriggers(QtWidgets.QAbstractItemView.NoEditTriggers)
+    for row, cells in enumerate(values):
+        for column, text in enumerate(cells):
+            table.setItem(row, column, QtWidgets.QTableWidgetItem(text))
+    table.resizeColumnsToContents()
+    table.horizontalHeader().setStretchLastSection(True)
+    layout.addWidget(table)
+    buttons = QtWidgets.QDialogButtonBox()
+    mine_btn = buttons.addButton("Keep mine", QtWidgets.QDialogButtonBox.ActionRole)
+    theirs_btn = buttons.addButton("Take imported", QtWidgets.QDialogButtonBox.ActionRole)
+    buttons.addButton("Cancel import", QtWidgets.QDialogButtonBox.RejectRole)
+    buttons.rejected.connect(dialog.reject)
+    chosen: Dict[str, str] = {}
+    mine_btn.clicked.connect(lambda: (chosen.update(keep="mine"), dialog.accept()))
+    theirs_btn.clicked.connect(lambda: (chosen.update(keep="theirs"), dialog.accept()))
+    dialog.resize(640, 320)
+    dialog.exec()
+    return chosen.get("keep")
+
+
+def _review_import(parent: QtWidgets.QWidget, report: ImportReport, noun: str) -> Optional[list]:
+    """Show problems found by validate_import and return the records to import, or None to cancel."""
+    if report.errors and not report.records:
+        QtWidgets.QMessageBox.critical(parent, "Import failed", "\n".join(report.lines()))
+        return None
+    if report.ok and not report.dangling_links:
+        return report.records
+    details = "\n".join(report.lines()[:25])
+    question = (
+        f"Import the {len(report.records)} valid {noun} and skip the rest?"
+        if not report.ok
+        else f"Import {len(report.records)} {noun} anyway?"
+    )
+    choice = QtWidgets.QMessageBox.warning(
+        parent,
+        "Review import",
+        f"{details}\n\n{question}",
+        QtWidgets.QMessageBox.Yes | QtWidgets.QMessageBox.No,
+        QtWidgets.QMessageBox.No,
+    )
+    return report.records if choice == QtWidgets.QMessageBox.Yes else None
+
+
+def _paste_rows(parent: QtWidgets.QWidget, record_cls, noun: str, describe, known_item_ids=None) -> Optional[list]:
+    """Parse clipboard rows (CSV or TSV, with or without a header), preview them and return the ones to append."""
+    text = QtWidgets.QApplication.clipboard().text()
+    report = validate_pasted_rows(text, record_cls, known_item_ids)
+    if not report.row_count and not report.errors:
+        QtWidgets.QMessageBox.information(parent, "Paste Rows", f"The clipboard holds no {noun} rows.")
+        return None
+    records = _review_import(parent, report, noun)
+    if not records:
+        return None
+    preview = [describe(record) for record in records[:10]]
+    if len(records) > len(preview):
+        preview.append(f"... and {len(records) - len(preview)} more")
+    choice = QtWidgets.QMessageBox.question(
+        parent, "Paste Rows", f"Append {len(records)} {noun}?\n\n" + "\n".join(preview)
+    )
+    return records if choice == QtWidgets.QMessageBox.Yes else None
+
+
+def _export_view(parent: QtWidgets.QWidget, records: list, csv_writer, noun: str) -> None:
+    """Save exactly the rows a grid shows, in its current order, as CSV or JSON Lines."""
+    path, chosen = QtWidgets.QFileDialog.getSaveFileName(
+        parent, "Export current view", filter="CSV Files (*.csv);;JSON Lines (*.jsonl)"
+    )
+    if not path:
+        return
+    try:
+        if chosen.startswith("JSON") or path.lower().endswith(".jsonl"):
+            write_jsonl(path, records)
+        else:
+            csv_writer(path, records)
+    except Exception as exc:
+        QtWidgets.QMessageBox.critical(parent, "Export failed", str(exc))
+    else:
+        QtWidgets.QMessageBox.information(parent, "Export", f"Exported {len(records)} {noun} from the current view.")
+
+
+def _import_bundle(parent: QtWidgets.QWidget, main: MainWindow) -> None:
+    path, _ = QtWidgets.QFileDialog.getOpenFileName(parent, "Select bundle", filter="Bundle Files (*.json)")
+    if not path:
+        return
+    try:
+        items, money, metadata, weights_text = main.run_busy("Reading bundle...", lambda progress: read_bundle(path))
+    except Exception as exc:
+        main.notify(f"Import failed: could not read bundle: {exc}", error=True)
+        return
+    details = metadata.get("generated_at", "unknown time") if isinstance(metadata, dict) else "unknown time"
+    choice = QtWidgets.QMessageBox.question(
+        parent,
+        "Import Bundle",
+        (
+            f"Bundle contains {len(items)} items and {len(money)} money entries "
+            f"(created {details}).\n\n"
+            "Yes = overwrite current items and money with bundle contents.\n"
+            "No = append/merge; incoming records replace matching IDs and new IDs are added."
+        ),
+        QtWidgets.QMessageBox.Yes | QtWidgets.QMessageBox.No,
+    )
+    if choice == QtWidgets.QMessageBox.Yes:
+        main.items = items
+        main.money = money
+    else:
+        merged_items = _merge_import(parent, main.items, items, "items")
+        merged_money = None if merged_items is None else _merge_import(parent, main.money, money, "money entries")
+        if merged_money is None:
+            return
+        main.items, main.money = merged_items, merged_money
+    main._sort_items()
+    main._sort_money()
+    main.save_items()
+    main.save_money()
+    main.notify(f"Imported bundle with {len(items)} items and {len(money)} money entries.")
+    if weights_text is not None and (
+        QtWidgets.QMessageBox.question(
+            parent,
+            "Import Bundle",
+            "The bundle includes the scoring weights it was exported with.\n\n"
+            "Replace your weights with them (and rescore every item) so the scores match the sender's?",
+        )
+        == QtWidgets.QMessageBox.Yes
+    ):
+        weights, warnings = main.config_manager.parse_weights_text(weights_text)
+        try:
+            main.apply_weights(weights)
+        except (ValueError, OSError) as exc:
+            main.notify(f"Bundle weights not applied: {exc}", error=True)
+            return
+        # Show the imported weights in the Weights tab too.
+        main.weights_tab._load(main.weights)
+        skipped = f" ({len(warnings)} line(s) skipped)" if warnings else ""
+        main.notify(f"Applied the bundle's scoring weights{skipped}.")
+
+
+def _export_bundle(parent: QtWidgets.QWidget, main: MainWindow) -> None:
+    path, _ = QtWidgets.QFileDialog.getSaveFileName(parent, "Save bundle", filter="Bundle Files (*.json)")
+    if not path:
+        return
+    include_weights = (
+        QtWidgets.QMessageBox.question(
+            parent,
+            "Export Bundle",
+            "Include your scoring weights, so whoever imports the bundle can reproduce the same scores?",
+        )
+        == QtWidgets.QMessageBox.Yes
+    )
+    try:
+        write_bundle(path, main.items, main.money, main.config_manager.weights_text() if include_weights else None)
+    except Exception as exc:
+        QtWidgets.QMessageBox.critical(parent, "Export failed", f"Could not write bundle:\n{exc}")
+    else:
+        QtWidgets.QMessageBox.information(
+            parent,
+            "Export",
+            f"Bundle exported with {len(main.items)} items and {len(main.money)} money entries"
+            + (" and the scoring weights." if include_weights else "."),
+        )
+
+
+def _keep_scroll_position(table: QtWidgets.QTableWidget) -> Callable[[], None]:
+    """Capture a grid's scroll offsets before it is rebuilt; call the result afterwards to restore them."""
+    h_bar, v_bar = table.horizontalScrollBar(), table.verticalScrollBar()
+    h_value, v_value = h_bar.value(), v_bar.value()
+
+    def restore() -> None:
+        # Deferred so the scroll ranges reflect the new rows first; Qt clamps values past the end.
+        QtCore.QTimer.singleShot(0, lambda: (h_bar.setValue(h_value), v_bar.setValue(v_value)))
+
+    return restore
+
 
+def _actions_menu_button(buttons: Dict[str, QtWidgets.QPushButton]) -> QtWidgets.QToolButton:
+    """A hamburger button offering the tab's action buttons as a menu, shown while the window is narrow."""
+    menu_btn = QtWidgets.QToolButton()
+    menu_btn.setText("\u2630")
+    menu_btn.setToolTip("Actions")
+    menu_btn.setPopupMode(QtWidgets.QToolButton.InstantPopup)
+    menu = QtWidgets.QMenu(menu_btn)
+    for label, btn in buttons.items():
+        if label != "Clear Filters":
+            menu.addAction(label).triggered.connect(btn.click)
+    menu_btn.setMenu(menu)
+    menu_btn.setVisible(False)
+    return menu_btn
 
-def _merge_by_id(existing, imported):
-    merged = {record.id: record for record in existing}
-    for record in imported:
-        merged[record.id] = record
-    return list(merged.values())
+
+def _compact_check(main: MainWindow) -> QtWidgets.QCheckBox:
+    """The "Compact" toggle shown on both grid tabs; either one switches the whole window."""
+    check = QtWidgets.QCheckBox("Compact")
+    check.setToolTip("Denser grids: less padding, shorter rows, and only the main columns.")
+    check.toggled.connect(main.set_compact_mode)
+    return check
+
+
+def _show_item_image(label: QtWidgets.QLabel, path: str) -> None:
+    """Show the picture at path scaled to ITEM_IMAGE_SIZE, or a text placeholder when it cannot be loaded."""
+    pixmap = QtGui.QPixmap(path) if path else QtGui.QPixmap()
+    label.setToolTip(path)
+    if pixmap.isNull():
+        label.setPixmap(QtGui.QPixmap())
+        if not path:
+            label.setText("No image")
+        elif os.path.isfile(path):
+            label.setText("Image could not be read")
+        else:
+            label.setText("Image not found")
+        return
+    label.setPixmap(
+        pixmap.scaled(ITEM_IMAGE_SIZE, ITEM_IMAGE_SIZE, QtCore.Qt.KeepAspectRatio, QtCore.Qt.SmoothTransformation)
+    )
+
+
+def _item_image_label() -> QtWidgets.QLabel:
+    label = QtWidgets.QLabel()
+    label.setAlignment(QtCore.Qt.AlignCenter)
+    label.setMinimumSize(ITEM_IMAGE_SIZE, ITEM_IMAGE_SIZE // 2)
+    label.setFrameStyle(QtWidgets.QFrame.StyledPanel | QtWidgets.QFrame.Sunken)
+    return label
+
+
+def _apply_compact_spacing(tab: QtWidgets.QWidget, check: QtWidgets.QCheckBox, enabled: bool) -> None:
+    check.blockSignals(True)
+    check.setChecked(enabled)
+    check.blockSignals(False)
+    tab.layout().setSpacing(2 if enabled else 8)
+    margin = 2 if enabled else 9
+    tab.layout().setContentsMargins(margin, margin, margin, margin)
+
+
+# Columns the purchases grid can show: key -> (header label, sort key).
+ITEM_COLUMNS = {
+    "product": ("Product", lambda i: i.product.lower()),
+    "date": ("Date", lambda i: i.date),
+    "cost": ("Cost", lambda i: i.cost),
+    "urgency": ("Urgency", lambda i: i.urgency),
+    "value": ("Value", lambda i: i.value),
+    "want": ("Want", lambda i: i.want),
+    "price_comp": ("Price vs Similar", lambda i: i.price_comp),
+    "effect": ("Effect", lambda i: i.effect),
+    "expected_uses": ("Expected Uses", lambda i: i.expected_uses or 0),
+    "effort": ("Effort", lambda i: i.effort or 0),
+    "decision": ("Decision", lambda i: i.decision),
+    "target_date": ("Target Date", lambda i: i.target_date or datetime.max),
+    "description": ("Description", lambda i: i.description.lower()),
+    "location": ("Location", lambda i: i.location.lower()),
+    "reference": ("Reference", lambda i: i.reference.lower()),
+    "recurrence": ("Recurrence", lambda i: i.recurrence.lower()),
+    "justification": ("Justification", lambda i: i.justification.lower()),
+    "overall": ("Overall", None),
+}
+DEFAULT_ITEM_COLUMNS = ["product", "date", "cost", "urgency", "want", "overall"]
+# Compact mode keeps only these item columns (of those chosen) and hides Linked Item and Category on Money.
+COMPACT_ITEM_COLUMNS = ("product", "date", "cost", "overall")
+COMPACT_HIDDEN_MONEY_COLUMNS = (4, 6)
+# Sort columns were persisted as indices before the grid columns became configurable.
+_LEGACY_ITEM_SORT_COLUMNS = ["product", "date", "cost", "urgency", "overall"]
+# Scoring runs at roughly 5 microseconds per item, so smaller sets rescore in one go without a noticeable pause.
+BACKGROUND_RESCORE_THRESHOLD = 20000
+RECURRENCE_OPTIONS = ["none", "once", "weekly", "biweekly", "monthly", "quarterly", "yearly"]
+
+# Theme color keys for the money grid's Status column.
+MONEY_STATUS_COLORS = {
+    "Future income": "warning",
+    "Missing item": "negative",
+}
+QUICK_ADD_ERROR_COLOR = "#dc2626"
+# Longest side, in pixels, of the item picture in the view and edit dialogs.
+ITEM_IMAGE_SIZE = 180
+IMAGE_FILE_FILTER = "Images (*.png *.jpg *.jpeg *.gif *.bmp *.webp);;All Files (*)"
+
+
+def _money_status(entry: MoneyRecord, item_ids: set, now: datetime) -> str:
+    if entry.linked_item_id and entry.linked_item_id not in item_ids:
+        return "Missing item"
+    if entry.entry_type.lower() == "income" and entry.date > now:
+        return "Future income"
+    if entry.linked_item_id:
+        return "Linked"
+    return ""
+
+
+def _confirm_discard(parent: QtWidgets.QWidget) -> bool:
+    choice = QtWidgets.QMessageBox.question(
+        parent,
+        "Unsaved changes",
+        "This form has unsaved changes. Discard them?",
+        QtWidgets.QMessageBox.Discard | QtWidgets.QMessageBox.Cancel,
+        QtWidgets.QMessageBox.Cancel,
+    )
+    return choice == QtWidgets.QMessageBox.Discard
 
 
 def _make_icon_pixmap(name: str, color: QtGui.QColor, size: int) -> QtGui.QPixmap:
@@ -88,72 +469,364 @@ def _make_icon_pixmap(name: str, color: QtGui.QColor, size: int) -> QtGui.QPixma
     return pixmap
 
 
+class Toast(QtWidgets.QFrame):
+    """Transient notice stacked in the main window's corner; errors stay until closed."""
+
+    SUCCESS_MS = 3000
+    closed = QtCore.Signal(object)
+
+    def __init__(self, parent: QtWidgets.QWidget, message: str, error: bool) -> None:
+        super().__init__(parent)
+        self.setAttribute(QtCore.Qt.WA_StyledBackground, True)
+        background, border = ("#fdecea", "#d93025") if error else ("#e6f4ea", "#1e8e3e")
+        self.setStyleSheet(
+            f"Toast {{ background: {background}; border: 1px solid {border}; border-radius: 6px; }}"
+            "QLabel { color: #202124; background: transparent; }"
+        )
+        layout = QtWidgets.QHBoxLayout(self)
+        layout.setContentsMargins(10, 6, 6, 6)
+        label = QtWidgets.QLabel(message)
+        label.setWordWrap(True)
+        label.setMaximumWidth(360)
+        layout.addWidget(label)
+        if error:
+            close_btn = QtWidgets.QToolButton()
+            close_btn.setText("✕")
+            close_btn.setAutoRaise(True)
+            close_btn.clicked.connect(self.dismiss)
+            layout.addWidget(close_btn)
+        self._opacity = QtWidgets.QGraphicsOpacityEffect(self)
+        self._opacity.setOpacity(1.0)
+        self.setGraphicsEffect(self._opacity)
+        self._fade = QtCore.QPropertyAnimation(self._opacity, b"opacity", self)
+        self._fade.setDuration(400)
+        self._fade.setStartValue(1.0)
+        self._fade.setEndValue(0.0)
+        self._fade.finished.connect(self.dismiss)
+        if not error:
+            QtCore.QTimer.singleShot(self.SUCCESS_MS, self._fade.start)
+
+    def dismiss(self) -> None:
+        self.hide()
+        self.closed.emit(self)
+        self.deleteLater()
+
+
 def launch() -> None:
     _detach_console_on_windows()
     _redirect_stdio_to_null_on_windows()
-    app = QtWidgets.QApplication(sys.argv)
+    safe_mode = "--safe" in sys.argv
+    theme, qt_args = _theme_argument(sys.argv)
+    app = QtWidgets.QApplication([arg for arg in qt_args if arg != "--safe"])
     config = ConfigManager()
-    ensure_paths(config.settings)
+    if theme:
+        try:
+            config.override_theme(theme)
+        except ValueError as exc:
+            QtWidgets.QMessageBox.warning(None, "Theme", f"{exc}\n\nStarting with the saved theme instead.")
+    ensure_paths(config.paths)
+    if not safe_mode:
+        # Before the startup files exist, so a freshly created empty file is not taken for data.
+        _offer_data_relocation(config)
     ensure_startup_files(config)
-    window = MainWindow(config)
+    set_lock_timeout(config.settings["storage"]["lock_timeout_seconds"])
+    window = None
+    while window is None:
+        try:
+            window = MainWindow(config, safe_mode=safe_mode)
+        except DataFileLockedError as exc:
+            choice = QtWidgets.QMessageBox.critical(
+                None, "Data file locked", str(exc), QtWidgets.QMessageBox.Retry | QtWidgets.QMessageBox.Close
+            )
+            if choice != QtWidgets.QMessageBox.Retry:
+                sys.exit(1)
+        except DataFileParseError as exc:
+            safe_mode = _recover_from_parse_error(exc, config.paths["backup_dir"])
+        except ValueError as exc:
+            choice = QtWidgets.QMessageBox.critical(
+                None,
+                "Could not load data",
+                f"{exc}\n\nOpen in safe mode? Your data files will not be loaded or written.",
+                QtWidgets.QMessageBox.Open | QtWidgets.QMessageBox.Close,
+            )
+            if choice != QtWidgets.QMessageBox.Open:
+                sys.exit(1)
+            safe_mode = True
+    # Quitting without closing the window (e.g. at logout) still writes pending changes.
+    app.aboutToQuit.connect(window.shutdown)
     window.show()
     sys.exit(app.exec())
 
 
+def _theme_argument(argv: List[str]) -> Tuple[Optional[str], List[str]]:
+    """The --theme NAME (or --theme=NAME) given at launch, and argv without it."""
+    theme, rest = None, []
+    args = iter(argv)
+    for arg in args:
+        if arg == "--theme":
+            theme = next(args, None)
+        elif arg.startswith("--theme="):
+            theme = arg.split("=", 1)[1]
+        else:
+            rest.append(arg)
+    return theme, rest
+
+
+def _offer_data_relocation(config: ConfigManager) -> None:
+    """Ask whether to move or copy data left behind at a data path the settings no longer point to.
+
+    Quitting leaves everything untouched, so the question comes back on the next start.
+    """
+    config.moved_data_paths = changed_data_paths(config.settings, config.settings_path)
+    for key, old, new in config.moved_data_paths:
+        box = QtWidgets.QMessageBox(
+            QtWidgets.QMessageBox.Question,
+            "Data file location changed",
+            f"The {key} setting now points to\n{new}\nwhich has no data, but your data is still in\n{old}",
+        )
+        box.setInformativeText("The existing file is backed up before it is moved or copied.")
+        move_btn = box.addButton("Move it there", QtWidgets.QMessageBox.AcceptRole)
+        copy_btn = box.addButton("Copy it there", QtWidgets.QMessageBox.AcceptRole)
+        empty_btn = box.addButton("Start empty", QtWidgets.QMessageBox.DestructiveRole)
+        box.addButton("Quit", QtWidgets.QMessageBox.RejectRole)
+        box.exec()
+        clicked = box.clickedButton()
+        if clicked not in (move_btn, copy_btn, empty_btn):
+            sys.exit(0)
+        if clicked is empty_btn:
+            continue
+        try:
+            backup_dir = config.settings["paths"]["backup_dir"]
+            relocate_data_file(old, new, backup_dir, config.settings["backup"], move=clicked is move_btn)
+        except OSError as exc:
+            QtWidgets.QMessageBox.critical(None, "Could not relocate data", f"{old} -> {new}\n{exc}")
+            sys.exit(1)
+    remember_data_paths(config.settings, config.settings_path)
+
+
+def _recover_from_parse_error(exc: DataFileParseError, backup_dir: str) -> bool:
+    """Offer to restore the newest backup of the damaged file or open in safe mode; returns safe mode."""
+    backup = latest_backup(exc.path, backup_dir)
+    box = QtWidgets.QMessageBox(QtWidgets.QMessageBox.Critical, "Could not load data", str(exc))
+    box.setInformativeText(
+        (f"The newest backup is {os.path.basename(backup)}. " if backup else "No backup of this file exists. ")
+        + "Safe mode opens without loading or writing your data files."
+    )
+    restore_btn = box.addButton("Restore latest backup", QtWidgets.QMessageBox.AcceptRole) if backup else None
+    safe_btn = box.addButton("Open in safe mode", QtWidgets.QMessageBox.ActionRole)
+    box.addButton(QtWidgets.QMessageBox.Close)
+    box.exec()
+    clicked = box.clickedButton()
+    if restore_btn is not None and clicked is restore_btn:
+        set_aside = restore_backup(backup, exc.path)
+        QtWidgets.QMessageBox.information(
+            None, "Backup restored", f"Restored {os.path.basename(backup)}.\nThe damaged file was kept as {set_aside}."
+        )
+        return False
+    if clicked is safe_btn:
+        return True
+    sys.exit(1)
+
+
 class MainWindow(QtWidgets.QMainWindow):
-    def __init__(self, config: ConfigManager) -> None:
+    # Emitted from the writer thread; Qt queues it onto the UI thread.
+    write_failed = QtCore.Signal(str, object)
+    files_written = QtCore.Signal(list)
+    # Progress and completion of the current run_busy task, emitted from its worker thread.
+    task_progress = QtCore.Signal(int)
+    task_finished = QtCore.Signal()
+
+    def __init__(self, config: ConfigManager, safe_mode: bool = False) -> None:
         super().__init__()
-        self.setWindowTitle("Finance Planner (Qt)")
+        # Safe mode starts with empty data and never writes the data files, so broken files can be inspected.
+        self.safe_mode = safe_mode
         self._icon_cache: Dict[str, QtGui.QIcon] = {}
         self.config_manager = config
         self.settings = config.settings
         self.weights = config.weights
         self.theme = config.get_theme()
         self.apply_theme()
-        self.items_path = self.settings["paths"]["items_csv"]
-        self.money_path = self.settings["paths"]["money_csv"]
-        self.backup_dir = self.settings["paths"]["backup_dir"]
+        self._base_font_size = QtWidgets.QApplication.font().pointSizeF()
+        self.items_path, self.money_path = config.data_files()
+        self.backup_dir = config.paths["backup_dir"]
+        self._update_window_title()
         self.date_fmt = self.settings["ui"]["date_format"]
         self.currency_symbol = self.settings["ui"]["currency_symbol"]
+        self.item_columns = self._load_item_columns()
         self.item_sort_column, self.item_sort_ascending = self._load_sort_state(
-            "items_sort", default_column=1, default_direction="desc"
+            "items_sort", default_column="overall", default_direction="desc"
         )
-        self.money_sort_column, self.money_sort_ascending = self._load_sort_state(
+        if isinstance(self.item_sort_column, int):
+            legacy = self.item_sort_column
+            self.item_sort_column = (
+                _LEGACY_ITEM_SORT_COLUMNS[legacy] if 0 <= legacy < len(_LEGACY_ITEM_SORT_COLUMNS) else "date"
+            )
+            self._persist_sort_state("items_sort", self.item_sort_column, self.item_sort_ascending)
+        money_sort_column, self.money_sort_ascending = self._load_sort_state(
             "money_sort", default_column=0, default_direction="desc"
         )
+        self.money_sort_column = money_sort_column if isinstance(money_sort_column, int) else 0
 
         self.items: List[ItemRecord] = []
         self.money: List[MoneyRecord] = []
+        self._items_dirty = False
+        self._money_dirty = False
+        self._writes_in_flight: set = set()
+        self.last_saved: Optional[datetime] = None
+        self._toasts: List[Toast] = []
+        # Label of the run_busy task in progress, if any; the window will not close under it.
+        self._busy_task: Optional[str] = None
+        self._shut_down = False
+        # Writes and the backups that follow them run on one worker thread, so backups never copy a
+        # half-written file and rapid saves collapse into a single write.
+        self.write_failed.connect(self._on_write_failed)
+        self.files_written.connect(self._on_files_written)
+        self._write_queue = WriteQueue(
+            after_write=self._after_write,
+            on_error=self.write_failed.emit,
+        )
+        self._autosave_timer = QtCore.QTimer(self)
+        self._autosave_timer.setSingleShot(True)
+        self._autosave_timer.timeout.connect(self.flush_pending_saves)
+        self.set_autosave_interval(int(self.settings["ui"].get("autosave_interval_seconds", 5)))
 
         self.tabs = QtWidgets.QTabWidget()
         self.purchases_tab = PurchasesWidget(self)
         self.money_tab = MoneyWidget(self)
         self.theme_tab = ThemeWidget(self)
+        self.weights_tab = WeightsWidget(self)
         self.settings_tab = SettingsWidget(self)
         self.tabs.addTab(self.purchases_tab, "Purchases")
         self.tabs.addTab(self.money_tab, "Money")
         self.tabs.addTab(self.theme_tab, "Themes")
+        self.tabs.addTab(self.weights_tab, "Weights")
         self.tabs.addTab(self.settings_tab, "Settings")
         container = QtWidgets.QWidget()
         layout = QtWidgets.QVBoxLayout(container)
         layout.setContentsMargins(12, 12, 12, 12)
+        self.safe_mode_banner = QtWidgets.QLabel()
+        self.safe_mode_banner.setWordWrap(True)
+        self.safe_mode_banner.setTextInteractionFlags(QtCore.Qt.TextSelectableByMouse)
+        self.safe_mode_banner.setStyleSheet(
+            "QLabel { background: #fdecea; color: #8a1c1c; border: 1px solid #d93025; padding: 8px; }"
+        )
+        self.safe_mode_banner.setVisible(safe_mode)
+        layout.addWidget(self.safe_mode_banner)
+        layout.addLayout(self._build_book_row())
         layout.addWidget(self.tabs)
         self.setCentralWidget(container)
-        self.setMinimumSize(960, 640)
+        self.save_status_label = QtWidgets.QLabel()
+        self.statusBar().addPermanentWidget(self.save_status_label)
+        self.statusBar().setVisible(bool(self.settings["ui"].get("show_save_status", True)))
+        self._update_save_status()
+        self.set_compact_mode(bool(self.settings["ui"].get("compact_mode", False)))
+        min_size = self.settings["ui"].get("min_window_size", [720, 480])
+        self.setMinimumSize(int(min_size[0]), int(min_size[1]))
 
         self._load_data()
         self._setup_shortcuts()
         self._show_config_messages()
 
-    def _load_sort_state(self, key: str, default_column: int, default_direction: str) -> tuple[int, bool]:
+    def _update_window_title(self) -> None:
+        book = self.config_manager.book
+        self.setWindowTitle(
+            "Finance Planner (Qt)"
+            + (f" - {book}" if book != DEFAULT_BOOK else "")
+            + (" - Safe Mode" if self.safe_mode else "")
+        )
+
+    def _build_book_row(self) -> QtWidgets.QHBoxLayout:
+        row = QtWidgets.QHBoxLayout()
+        self.book_combo = QtWidgets.QComboBox()
+        self.book_combo.setToolTip("Each book has its own items, money entries and backups.")
+        self._refresh_book_combo()
+        self.book_combo.activated.connect(lambda _index: self.switch_book(self.book_combo.currentText()))
+        new_btn = QtWidgets.QPushButton("New Book...")
+        new_btn.clicked.connect(self._create_book)
+        # Safe mode never writes the data files, so there is nothing to switch between.
+        self.book_combo.setEnabled(not self.safe_mode)
+        new_btn.setEnabled(not self.safe_mode)
+        row.addWidget(QtWidgets.QLabel("Book"))
+        row.addWidget(self.book_combo)
+        row.addWidget(new_btn)
+        row.addStretch(1)
+        return row
+
+    def _refresh_book_combo(self) -> None:
+        self.book_combo.blockSignals(True)
+        self.book_combo.clear()
+        self.book_combo.addItems(self.config_manager.book_names())
+        self.book_combo.setCurrentText(self.config_manager.book)
+        self.book_combo.blockSignals(False)
+
+    def _create_book(self) -> None:
+        name, ok = QtWidgets.QInputDialog.getText(self, "New Book", "Name (letters, digits, '-' and '_'):")
+        if not ok or not name.strip():
+            return
+        try:
+            self.config_manager.create_book(name.strip())
+        except (ValueError, OSError) as exc:
+            QtWidgets.QMessageBox.warning(self, "New Book", str(exc))
+            return
+        self.switch_book(name.strip())
+
+    def switch_book(self, name: str) -> None:
+        """Write the open book's pending changes, then load another book's data in its place."""
+        if name == self.config_manager.book:
+            return
+        if self._busy_task or not self._write_before_close():
+            self._refresh_book_combo()
+            return
+        try:
+            self.config_manager.set_current_book(name)
+            ensure_paths(self.config_manager.paths)
+            ensure_startup_files(self.config_manager)
+        except (ValueError, OSError) as exc:
+            QtWidgets.QMessageBox.warning(self, "Switch book", str(exc))
+            self._refresh_book_combo()
+            return
+        self.items_path, self.money_path = self.config_manager.data_files()
+        self.backup_dir = self.config_manager.paths["backup_dir"]
+        self._items_dirty = self._money_dirty = False
+        self.last_saved = None
+        try:
+            self._load_data()
+        except (DataFileLockedError, DataFileParseError, ValueError) as exc:
+            self.items, self.money = [], []
+            self.purchases_tab.refresh()
+            self.money_tab.refresh()
+            QtWidgets.QMessageBox.critical(self, "Could not load book", str(exc))
+        self._refresh_book_combo()
+        self._update_window_title()
+        self._update_save_status()
+        self.settings_tab.refresh_paths()
+
+    def _load_item_columns(self) -> List[str]:
+        configured = self.settings["ui"].get("items_columns", DEFAULT_ITEM_COLUMNS)
+        columns = [key for key in configured if key in ITEM_COLUMNS] if isinstance(configured, list) else []
+        return columns or list(DEFAULT_ITEM_COLUMNS)
+
+    def set_item_columns(self, columns: List[str]) -> None:
+        self.item_columns = columns
+        self.settings["ui"]["items_columns"] = list(columns)
+        self.config_manager.save_settings()
+        self.purchases_tab.refresh()
+
+    def _load_sort_state(self, key: str, default_column, default_direction: str) -> tuple:
         ui_settings = self.settings.setdefault("ui", {})
+        configured = ui_settings.get(f"default_{key}", {})
+        if isinstance(configured, dict):
+            default_column = configured.get("column", default_column)
+            default_direction = configured.get("direction", default_direction)
+        if not ui_settings.get("remember_sort", True):
+            return default_column, default_direction == "asc"
         sort_cfg = ui_settings.get(key, {})
         changed = False
         if not isinstance(sort_cfg, dict):
             sort_cfg = {}
             ui_settings[key] = sort_cfg
             changed = True
-        column = int(sort_cfg.get("column", default_column))
+        column = sort_cfg.get("column", default_column)
         direction = sort_cfg.get("direction", default_direction)
         if "column" not in sort_cfg:
             sort_cfg["column"] = column
@@ -165,7 +838,9 @@ class MainWindow(QtWidgets.QMainWindow):
             self.config_manager.save_settings()
         return column, direction == "asc"
 
-    def _persist_sort_state(self, key: str, column: int, ascending: bool) -> None:
+    def _persist_sort_state(self, key: str, column, ascending: bool) -> None:
+        if not self.settings["ui"].get("remember_sort", True):
+            return
         self.settings["ui"][key] = {"column": column, "direction": "asc" if ascending else "desc"}
         self.config_manager.save_settings()
 
@@ -173,6 +848,7 @@ class MainWindow(QtWidgets.QMainWindow):
         QtGui.QShortcut(QtGui.QKeySequence("Ctrl+F"), self, self._focus_search)
         QtGui.QShortcut(QtGui.QKeySequence("Ctrl+N"), self, self._add_current)
         QtGui.QShortcut(QtGui.QKeySequence("Ctrl+E"), self, self._edit_current)
+        QtGui.QShortcut(QtGui.QKeySequence.Save, self, self.save_now)
 
     def _focus_search(self) -> None:
         current = self.tabs.currentWidget()
@@ -196,43 +872,60 @@ class MainWindow(QtWidgets.QMainWindow):
             current.edit_entry()
 
     def _load_data(self) -> None:
+        if self.safe_mode:
+            self._show_safe_mode_status()
+            return
         self.items = read_items(self.items_path)
         self.money = read_money(self.money_path)
+        # Only files that just loaded cleanly are worth a daily snapshot.
+        try:
+            startup_backup([self.items_path, self.money_path], self.backup_dir, self.settings["backup"])
+        except OSError as exc:
+            print(f"Startup backup failed: {exc}")
         self._sort_items()
         self._sort_money()
         self._rescore_items()
         self.purchases_tab.refresh()
         self.money_tab.refresh()
 
+    def _show_safe_mode_status(self) -> None:
+        problems = []
+        for path, reader in [(self.items_path, read_items), (self.money_path, read_money)]:
+            try:
+                reader(path)
+            except Exception as exc:
+                problems.append(str(exc))
+        details = "\n".join(problems) if problems else "Both data files currently load without errors."
+        self.safe_mode_banner.setText(
+            "Safe mode: data files were not loaded and nothing you change here will be saved to them.\n"
+            f"{details}\n"
+            "Fix the files (Settings > Open data folder, or run `python -m cli.main doctor`) and restart normally."
+        )
+
     def _sort_items(self) -> None:
         col = self.item_sort_column
         asc = self.item_sort_ascending
-        if col == 0:
-            self.items.sort(key=lambda i: i.product.lower(), reverse=not asc)
-        elif col == 1:
-            self.items.sort(key=lambda i: i.date, reverse=not asc)
-        elif col == 2:
-            self.items.sort(key=lambda i: i.cost, reverse=not asc)
-        elif col == 3:
-            self.items.sort(key=lambda i: i.urgency, reverse=not asc)
-        elif col == 4:
+        if col == "overall":
             self.items.sort(
                 key=lambda i: (
                     i.overall_score is None,
                     (i.overall_score or 0.0) if asc else -(i.overall_score or 0.0),
                 )
             )
+        elif col in ITEM_COLUMNS:
+            self.items.sort(key=ITEM_COLUMNS[col][1], reverse=not asc)
         else:
             self.items.sort(key=lambda i: i.date, reverse=True)
-            self.item_sort_column = 1
+            self.item_sort_column = "date"
             self.item_sort_ascending = False
 
     def _sort_money(self) -> None:
         col = self.money_sort_column
         asc = self.money_sort_ascending
         linked_names = {item.id: item.product for item in self.items}
-        if col == 0:
-            self.money.sort(key=lambda m: m.date, reverse=not asc)
+        if col in (0, 7):
+            # Balance runs in date order, so sorting by it is sorting by date; id keeps same-day rows stable.
+            self.money.sort(key=lambda m: (m.date, m.id), reverse=not asc)
         elif col == 1:
             self.money.sort(key=lambda m: m.entry_type.lower(), reverse=not asc)
         elif col == 2:
@@ -243,25 +936,90 @@ class MainWindow(QtWidgets.QMainWindow):
             self.money.sort(
                 key=lambda m: linked_names.get(m.linked_item_id, m.linked_item_id).lower(), reverse=not asc
             )
+        elif col == 5:
+            item_ids = set(linked_names)
+            now = datetime.now()
+            self.money.sort(key=lambda m: _money_status(m, item_ids, now), reverse=not asc)
+        elif col == 6:
+            self.money.sort(key=lambda m: m.category_label().lower(), reverse=not asc)
+        elif col == 8:
+            self.money.sort(key=lambda m: m.cleared, reverse=not asc)
         else:
             self.money.sort(key=lambda m: m.date, reverse=True)
             self.money_sort_column = 0
             self.money_sort_ascending = False
 
+    def run_busy(self, label: str, work: Callable[[Callable[[int], None]], Any], total: int = 0) -> Any:
+        """Run work on a worker thread behind a window-modal progress dialog and return its result.
+
+        The window keeps painting while the modal dialog keeps conflicting actions out until the
+        result is in. work gets a callback for its running count out of total; with total 0 the
+        dialog shows a busy indicator instead. Exceptions from work are re-raised here.
+        """
+        progress = QtWidgets.QProgressDialog(label, None, 0, total, self)
+        progress.setWindowModality(QtCore.Qt.WindowModal)
+        progress.setMinimumDuration(300)
+        loop = QtCore.QEventLoop()
+        self.task_progress.connect(progress.setValue)
+        self.task_finished.connect(loop.quit)
+        outcome: Dict[str, Any] = {}
+
+        def run() -> None:
+            try:
+                outcome["result"] = work(self.task_progress.emit)
+            except Exception as exc:
+                outcome["error"] = exc
+            finally:
+                self.task_finished.emit()
+
+        self._busy_task = label
+        threading.Thread(target=run, name="busy-task", daemon=True).start()
+        loop.exec()
+        self._busy_task = None
+        self.task_progress.disconnect(progress.setValue)
+        self.task_finished.disconnect(loop.quit)
+        progress.close()
+        if "error" in outcome:
+            raise outcome["error"]
+        return outcome["result"]
+
     def _rescore_items(self) -> None:
-        for item in self.items:
-            item.overall_score = score_item(item, self.weights).overall
+        items = list(self.items)
+        if len(items) < BACKGROUND_RESCORE_THRESHOLD:
+            scores = overall_scores(items, self.weights)
+        else:
+            weights = deepcopy(self.weights)
+            scores = self.run_busy(
+                "Rescoring items...", lambda progress: overall_scores(items, weights, progress), len(items)
+            )
+        for item, score in zip(items, scores):
+            item.overall_score = score
+
+    def apply_weights(self, weights: Dict[str, Any]) -> None:
+        """Persist new weights, then rescore and re-sort every item under them."""
+        self.config_manager.save_weights(weights)
+        self.weights = self.config_manager.weights
+        self._rescore_items()
+        self._sort_items()
+        self.save_items()
 
     def apply_theme(self, name: Optional[str] = None) -> None:
-        theme_name = name or self.settings.get("themes", {}).get("default", "light")
+        theme_name = name or self.config_manager.active_theme_name()
         if theme_name not in self.config_manager.themes:
             theme_name = "light"
             self.config_manager.set_default_theme(theme_name)
         elif name is not None:
+            # Picking a theme in the app replaces a --theme given at launch.
+            self.config_manager.theme_override = None
             self.config_manager.set_default_theme(theme_name)
         self.theme = self.config_manager.get_theme(theme_name)
         self._apply_theme_palette(self.theme)
         self._refresh_icons()
+        # Score and entry-type colors come from the theme, so the grids repaint with the new ones.
+        for name in ("purchases_tab", "money_tab"):
+            tab = getattr(self, name, None)
+            if tab is not None:
+                tab.refresh()
 
     def _refresh_icons(self) -> None:
         for name in ("purchases_tab", "money_tab", "settings_tab", "theme_tab"):
@@ -437,17 +1195,214 @@ QScrollBar::add-line:horizontal, QScrollBar::sub-line:horizontal {{
 }}
 """
 
-    def save_items(self, trigger_backup: bool = True) -> None:
-        write_items(self.items_path, self.items)
-        if trigger_backup:
-            create_backup(self.items_path, self.backup_dir, self.settings["backup"])
+    def save_items(self) -> None:
+        self._items_dirty = True
         self.purchases_tab.refresh()
+        self._update_save_status()
+        self._schedule_autosave()
 
-    def save_money(self, trigger_backup: bool = True) -> None:
-        write_money(self.money_path, self.money)
-        if trigger_backup:
-            create_backup(self.money_path, self.backup_dir, self.settings["backup"])
+    def save_money(self) -> None:
+        self._money_dirty = True
         self.money_tab.refresh()
+        self._update_save_status()
+        self._schedule_autosave()
+
+    def _update_save_status(self) -> None:
+        if self.safe_mode:
+            text = "Safe mode: changes are not saved"
+        elif self._items_dirty or self._money_dirty:
+            text = "Unsaved changes"
+        elif self._writes_in_flight:
+            text = "Saving..."
+        elif self.last_saved:
+            text = f"Saved at {self.last_saved.strftime('%H:%M')}"
+        else:
+            text = "No changes"
+        self.save_status_label.setText(text)
+
+    def apply_accessibility(self) -> None:
+        """Scale the application font by ui.font_scale and size grid rows to ui.row_height.
+
+        A row height of 0 fits rows to the scaled font, so larger text never gets clipped.
+        """
+        ui_settings = self.settings["ui"]
+        scale = min(3.0, max(0.5, float(ui_settings.get("font_scale", 1.0) or 1.0)))
+        font = QtWidgets.QApplication.font()
+        if self._base_font_size > 0:
+            font.setPointSizeF(self._base_font_size * scale)
+            QtWidgets.QApplication.setFont(font)
+        compact = bool(ui_settings.get("compact_mode", False))
+        row_height = int(ui_settings.get("row_height", 0) or 0)
+        if compact:
+            row_height = QtGui.QFontMetrics(font).height() + 2
+        elif row_height <= 0:
+            row_height = QtGui.QFontMetrics(font).height() + 8
+        for tab in (self.purchases_tab, self.money_tab):
+            tab.table.verticalHeader().setMinimumSectionSize(min(row_height, 12))
+            tab.table.verticalHeader().setDefaultSectionSize(row_height)
+            tab.table.setStyleSheet("QTableWidget::item { padding: 0px 2px; }" if compact else "")
+
+    def set_compact_mode(self, enabled: bool) -> None:
+        """The one-click density switch (ui.compact_mode): less padding, shorter rows, fewer grid columns.
+
+        Unlike set_compact on the tabs, which folds the action buttons on narrow windows, this is
+        the user's choice and overrides ui.row_height while on.
+        """
+        if bool(self.settings["ui"].get("compact_mode", False)) != enabled:
+            self.settings["ui"]["compact_mode"] = enabled
+            self.config_manager.save_settings()
+        self.apply_accessibility()
+        self.purchases_tab.set_compact_mode(enabled)
+        self.money_tab.set_compact_mode(enabled)
+
+    def set_autosave_interval(self, seconds: int) -> None:
+        self._autosave_timer.setInterval(max(1, seconds) * 1000)
+
+    def _schedule_autosave(self) -> None:
+        # Changes arriving while the timer runs ride along with the pending write.
+        if self.settings["ui"].get("autosave", True) and not self._autosave_timer.isActive():
+            self._autosave_timer.start()
+
+    def notify(self, message: str, error: bool = False) -> None:
+        """Show a toast and log the same message; error toasts persist until dismissed."""
+        print(("Error: " if error else "") + message)
+        toast = Toast(self, message, error)
+        toast.closed.connect(self._remove_toast)
+        self._toasts.append(toast)
+        toast.show()
+        self._layout_toasts()
+
+    def _remove_toast(self, toast: Toast) -> None:
+        if toast in self._toasts:
+            self._toasts.remove(toast)
+            self._layout_toasts()
+
+    def _layout_toasts(self) -> None:
+        margin = 16
+        bottom = self.height() - margin
+        for toast in reversed(self._toasts):
+            toast.adjustSize()
+            bottom -= toast.height()
+            toast.move(self.width() - toast.width() - margin, bottom)
+            toast.raise_()
+            bottom -= 8
+
+    def resizeEvent(self, event: QtGui.QResizeEvent) -> None:
+        super().resizeEvent(event)
+        self._layout_toasts()
+        # Below ui.compact_width the action buttons fold into a menu so the grids keep their width.
+        compact = self.width() < int(self.settings["ui"].get("compact_width", 1100))
+        self.purchases_tab.set_compact(compact)
+        self.money_tab.set_compact(compact)
+
+    def save_now(self) -> None:
+        """Write pending changes immediately and confirm the outcome."""
+        if self.safe_mode:
+            self.notify("Safe mode: changes are not saved.", error=True)
+            return
+        if not (self._items_dirty or self._money_dirty):
+            self.notify("Nothing to save.")
+            return
+        if self.flush_pending_saves(wait=True):
+            self.notify("All changes saved.")
+
+    def flush_pending_saves(self, wait: bool = False) -> bool:
+        """Queue dirty data files for writing; with wait, block until written and return False on failure.
+
+        Failures are reported through write_failed, which also marks the file dirty again.
+        """
+        self._autosave_timer.stop()
+        if self._shut_down:
+            # The writer is gone; a retry scheduled by a late write failure has nowhere to go.
+            return False
+        if self.safe_mode:
+            return True
+        if self._items_dirty:
+            self._write_queue.submit(self.items_path, write_items, self.items)
+            self._writes_in_flight.add(self.items_path)
+            self._items_dirty = False
+        if self._money_dirty:
+            self._write_queue.submit(self.money_path, write_money, self.money)
+            self._writes_in_flight.add(self.money_path)
+            self._money_dirty = False
+        self._update_save_status()
+        return self._write_queue.flush() if wait else True
+
+    def _after_write(self, paths: List[str]) -> None:
+        # Runs on the writer thread: report success to the UI, then back up the fresh files.
+        self.files_written.emit(paths)
+        self._backup_files(paths)
+
+    def _on_files_written(self, paths: List[str]) -> None:
+        self._writes_in_flight.difference_update(paths)
+        self.last_saved = datetime.now()
+        self._update_save_status()
+
+    def _on_write_failed(self, path: str, exc: Exception) -> None:
+        self._writes_in_flight.discard(path)
+        if path == self.items_path:
+            self._items_dirty = True
+        elif path == self.money_path:
+            self._money_dirty = True
+        self._update_save_status()
+        if isinstance(exc, DataFileLockedError) and self.settings["ui"].get("autosave", True):
+            # Locks are usually brief; the next autosave tries again.
+            self.notify(f"Save postponed: {exc} Retrying shortly.", error=True)
+            self._schedule_autosave()
+            return
+        self.notify(f"Save failed: {exc}", error=True)
+
+    def _backup_files(self, paths: List[str]) -> None:
+        for path in paths:
+            try:
+                create_backup(path, self.backup_dir, self.settings["backup"])
+            except Exception as exc:
+                print(f"Backup failed: {exc}")
+
+    def closeEvent(self, event: QtGui.QCloseEvent) -> None:
+        if self._busy_task:
+            self.notify(f"Wait for '{self._busy_task.rstrip('.')}' to finish before closing.", error=True)
+            event.ignore()
+            return
+        if not self._write_before_close():
+            event.ignore()
+            return
+        self.shutdown()
+        super().closeEvent(event)
+
+    def _write_before_close(self) -> bool:
+        """Write every pending change; on failure ask to retry, close without it, or keep the window open."""
+        while not self.flush_pending_saves(wait=True):
+            # Deliver the queued write_failed signals, which mark the failed files dirty again.
+            QtWidgets.QApplication.processEvents()
+            choice = QtWidgets.QMessageBox.warning(
+                self,
+                "Unsaved changes",
+                "Some changes could not be written (see the notification).\n\n"
+                "Retry, discard them and close, or cancel to keep the window open?",
+                QtWidgets.QMessageBox.Retry | QtWidgets.QMessageBox.Discard | QtWidgets.QMessageBox.Cancel,
+                QtWidgets.QMessageBox.Retry,
+            )
+            if choice == QtWidgets.QMessageBox.Discard:
+                self._items_dirty = self._money_dirty = False
+                return True
+            if choice != QtWidgets.QMessageBox.Retry:
+                return False
+        return True
+
+    def shutdown(self) -> None:
+        """Write whatever is still pending and stop the writer; runs on close and again (harmlessly) on quit.
+
+        Settings such as sort order and columns are saved as they change, so only data is left. Every
+        lock is held by an open file handle, and closing the queue joins the writer thread, so no lock
+        outlives this call.
+        """
+        if self._shut_down:
+            return
+        self.flush_pending_saves()
+        self._shut_down = True
+        if not self._write_queue.close():
+            print("Error: some changes could not be written before exit.")
 
     def add_or_edit_item(self, existing: Optional[ItemRecord] = None) -> None:
         dialog = ItemDialog(self, existing)
@@ -460,7 +1415,7 @@ QScrollBar::add-line:horizontal, QScrollBar::sub-line:horizontal {{
             else:
                 self.items.append(record)
             self._sort_items()
-            self.save_items(trigger_backup=self.settings["ui"].get("autosave", True))
+            self.save_items()
 
     def _show_config_messages(self) -> None:
         restart_note = "Edit weights in weights.txt and restart the app to apply changes."
@@ -475,6 +1430,11 @@ QScrollBar::add-line:horizontal, QScrollBar::sub-line:horizontal {{
         dialog = ItemViewDialog(self, record, self.date_fmt, self.currency_symbol)
         dialog.exec()
 
+    def add_item_note(self, record: ItemRecord, text: str) -> str:
+        note = record.add_note(text)
+        self.save_items()
+        return note
+
     def add_or_edit_money(self, existing: Optional[MoneyRecord] = None) -> None:
         dialog = MoneyDialog(self, existing, self.items)
         if dialog.exec() == QtWidgets.QDialog.Accepted:
@@ -484,12 +1444,14 @@ QScrollBar::add-line:horizontal, QScrollBar::sub-line:horizontal {{
             else:
                 self.money.append(record)
             self._sort_money()
-            self.save_money(trigger_backup=self.settings["ui"].get("autosave", True))
+            self.save_money()
 
 
 class ItemViewDialog(QtWidgets.QDialog):
     def __init__(self, parent: MainWindow, record: ItemRecord, date_fmt: str, currency_symbol: str) -> None:
         super().__init__(parent)
+        self.main = parent
+        self.record = record
         self.setWindowTitle("Item Details")
         self.setMinimumWidth(520)
         self._build_ui(record, date_fmt, currency_symbol)
@@ -513,6 +1475,10 @@ class ItemViewDialog(QtWidgets.QDialog):
         title.setFont(title_font)
         header.addWidget(title)
         header.addStretch()
+        self.copy_id_btn = QtWidgets.QPushButton("Copy id")
+        self.copy_id_btn.setToolTip(record.id)
+        self.copy_id_btn.clicked.connect(self._copy_id)
+        header.addWidget(self.copy_id_btn)
         layout.addLayout(header)
 
         details_section, details_layout = self._section_container(
@@ -527,11 +1493,37 @@ class ItemViewDialog(QtWidgets.QDialog):
         def add_detail(label: str, widget: QtWidgets.QWidget) -> None:
             details_form.addRow(self._section_label(label), widget)
 
+        add_detail("Id", self._readonly_field(record.id))
         add_detail("Date", self._readonly_field(record.date.strftime(date_fmt)))
         add_detail("Cost", self._readonly_field(f"{currency_symbol}{record.cost:,.2f}"))
         add_detail("Location", self._readonly_field(record.location))
         add_detail("Recurrence", self._readonly_field(record.recurrence))
+        add_detail("Effort", self._readonly_field(str(record.effort or "Not set")))
+        add_detail("Decision", self._readonly_field(record.decision or "Undecided"))
+        add_detail(
+            "Target date",
+            self._readonly_field(record.target_date.strftime("%Y-%m-%d") if record.target_date else "Not set"),
+        )
+        if record.depends_on:
+            prereq = next((item for item in self.main.items if item.id == record.depends_on), None)
+            add_detail("Depends on", self._readonly_field(prereq.product if prereq else record.depends_on))
+        cost_per_use = record.cost_per_use()
+        add_detail("Expected uses", self._readonly_field(str(record.expected_uses or "Not set")))
+        add_detail(
+            "Cost per use",
+            self._readonly_field(f"{currency_symbol}{cost_per_use:,.2f}" if cost_per_use is not None else "—"),
+        )
         add_detail("Reference", self._readonly_field(record.reference, multiline=True))
+        url = record.reference_url()
+        if url:
+            open_btn = QtWidgets.QPushButton("Open link")
+            open_btn.setToolTip(url)
+            open_btn.clicked.connect(lambda: QtGui.QDesktopServices.openUrl(QtCore.QUrl(url)))
+            add_detail("", open_btn)
+        if record.image_path:
+            image_label = _item_image_label()
+            _show_item_image(image_label, record.image_path)
+            add_detail("Image", image_label)
 
         details_layout.addLayout(details_form)
 
@@ -550,14 +1542,33 @@ class ItemViewDialog(QtWidgets.QDialog):
         add_eval("Want", self._readonly_field(str(record.want)))
         add_eval("Price vs Similar", self._readonly_field(str(record.price_comp)))
         add_eval("Effect", self._readonly_field(str(record.effect)))
-        add_eval("Overall Score", self._readonly_field(f"{(record.overall_score or 0):.2f}"))
+        add_eval("Overall Score", self._readonly_field(format_score(record.overall_score)))
         add_eval("Description", self._readonly_field(record.description, multiline=True))
         add_eval("Justification", self._readonly_field(record.justification, multiline=True))
 
         eval_layout.addLayout(eval_form)
 
+        history_section, history_layout = self._section_container("History", QtWidgets.QStyle.SP_FileDialogListView)
+        self.history_list = QtWidgets.QListWidget()
+        self.history_list.setMinimumHeight(80)
+        self.history_list.setWordWrap(True)
+        self.history_list.addItems(record.history)
+        if not record.history:
+            self.history_list.addItem("No notes yet.")
+        history_layout.addWidget(self.history_list)
+        note_row = QtWidgets.QHBoxLayout()
+        self.note_edit = QtWidgets.QLineEdit()
+        self.note_edit.setPlaceholderText("Why did this decision change?")
+        self.note_edit.returnPressed.connect(self._add_note)
+        note_btn = QtWidgets.QPushButton("Add note")
+        note_btn.clicked.connect(self._add_note)
+        note_row.addWidget(self.note_edit)
+        note_row.addWidget(note_btn)
+        history_layout.addLayout(note_row)
+
         layout.addWidget(details_section)
         layout.addWidget(eval_section)
+        layout.addWidget(history_section)
 
         buttons = QtWidgets.QDialogButtonBox(QtWidgets.QDialogButtonBox.Close)
         buttons.accepted.connect(self.accept)
@@ -567,6 +1578,26 @@ class ItemViewDialog(QtWidgets.QDialog):
             close_btn.setDefault(True)
         layout.addWidget(buttons)
 
+    def _copy_id(self) -> None:
+        QtGui.QGuiApplication.clipboard().setText(self.record.id)
+        self.copy_id_btn.setText("Copied")
+        QtCore.QTimer.singleShot(1500, lambda: self.copy_id_btn.setText("Copy id"))
+
+    def _add_note(self) -> None:
+        text = self.note_edit.text().strip()
+        if not text:
+            return
+        try:
+            note = self.main.add_item_note(self.record, text)
+        except Exception as exc:
+            QtWidgets.QMessageBox.critical(self, "Save failed", str(exc))
+            return
+        if len(self.record.history) == 1:
+            self.history_list.clear()
+        self.history_list.addItem(note)
+        self.history_list.scrollToBottom()
+        self.note_edit.clear()
+
     def _section_container(
         self, title: str, icon_type: QtWidgets.QStyle.StandardPixmap
     ) -> tuple[QtWidgets.QGroupBox, QtWidgets.QVBoxLayout]:
@@ -602,6 +1633,7 @@ class ItemViewDialog(QtWidgets.QDialog):
             widget.setHorizontalScrollBarPolicy(QtCore.Qt.ScrollBarAlwaysOff)
             widget.setSizePolicy(QtWidgets.QSizePolicy.Expanding, QtWidgets.QSizePolicy.Preferred)
             widget.setMinimumHeight(64)
+            widget.setTextInteractionFlags(QtCore.Qt.TextSelectableByMouse | QtCore.Qt.TextSelectableByKeyboard)
             return widget
         line_edit = QtWidgets.QLineEdit(text)
         line_edit.setReadOnly(True)
@@ -626,14 +1658,39 @@ class PurchasesWidget(QtWidgets.QWidget):
         layout = QtWidgets.QVBoxLayout(self)
         layout.setSpacing(8)
 
+        quick_row = QtWidgets.QHBoxLayout()
+        quick_row.setSpacing(6)
+        self.quick_edit = QtWidgets.QLineEdit()
+        self.quick_edit.setPlaceholderText("Quick add: Desk lamp @39.99 #online !4, then Enter")
+        self.quick_edit.setToolTip(
+            "@cost, !urgency (1-5), #location or #monthly-style recurrence; the rest is the product.\n"
+            "Other ratings start at ui.item_defaults in settings.json; use Edit for the details."
+        )
+        self.quick_edit.textChanged.connect(self._update_quick_hint)
+        self.quick_edit.returnPressed.connect(self.quick_add)
+        self.quick_hint = QtWidgets.QLabel()
+        quick_row.addWidget(self.quick_edit, 2)
+        quick_row.addWidget(self.quick_hint, 3)
+        layout.addLayout(quick_row)
+
         controls = QtWidgets.QHBoxLayout()
         controls.setSpacing(6)
         self.search_edit = QtWidgets.QLineEdit()
-        self.search_edit.setPlaceholderText("Search")
+        self.search_edit.setPlaceholderText("Search (typo-tolerant)")
         self.search_edit.textChanged.connect(self.refresh)
+        self.advanced_check = QtWidgets.QCheckBox("Advanced")
+        self.advanced_check.setToolTip(
+            "Treat the search as a filter expression, e.g. cost>100 and recurrence=monthly.\n"
+            "Operators: = != > >= < <= ~ (contains) !~, combined with and/or/not and parentheses."
+        )
+        self.advanced_check.toggled.connect(self._toggle_advanced)
+        self.compact_check = _compact_check(self.main)
         self.filter_combo = QtWidgets.QComboBox()
-        self.filter_combo.addItems(["All", "High (>4)", "Low (<2.5)"])
+        self.filter_combo.addItems(["All", "High (>4)", "Low (<2.5)", "Unscored"])
         self.filter_combo.currentIndexChanged.connect(self.refresh)
+        self.recurrence_combo = QtWidgets.QComboBox()
+        self.recurrence_combo.addItems(["Any recurrence", "Recurring"] + RECURRENCE_OPTIONS)
+        self.recurrence_combo.currentIndexChanged.connect(self.refresh)
         clear_btn = QtWidgets.QPushButton("Clear Filters")
         clear_btn.clicked.connect(self._clear_filters)
         self._action_buttons: Dict[str, QtWidgets.QPushButton] = {"Clear Filters": clear_btn}
@@ -645,22 +1702,31 @@ class PurchasesWidget(QtWidgets.QWidget):
             ("Delete", self.delete_item),
             ("Import", self.import_data),
             ("Export", self.export_data),
+            ("Export View", self.export_view),
+            ("Paste Rows", self.paste_rows),
+            ("Find Duplicates", self.find_duplicates),
+            ("Score Unscored", self.score_unscored),
             ("Refresh", self.refresh),
+            ("Columns", self.configure_columns),
         ]:
             btn = QtWidgets.QPushButton(text)
             btn.clicked.connect(handler)
             controls.addWidget(btn)
             self._action_buttons[text] = btn
+        self.actions_btn = _actions_menu_button(self._action_buttons)
+        controls.addWidget(self.actions_btn)
 
         controls.addStretch()
         controls.addWidget(QtWidgets.QLabel("Filter"))
         controls.addWidget(self.filter_combo)
+        controls.addWidget(self.recurrence_combo)
         controls.addWidget(self.search_edit)
+        controls.addWidget(self.advanced_check)
         controls.addWidget(clear_btn)
+        controls.addWidget(self.compact_check)
         layout.addLayout(controls)
 
-        self.table = QtWidgets.QTableWidget(0, 6)
-        self.table.setHorizontalHeaderLabels(["Product", "Date", "Cost", "Urgency", "Want", "Overall"])
+        self.table = QtWidgets.QTableWidget(0, len(self.main.item_columns))
         self.table.horizontalHeader().setStretchLastSection(True)
         self.table.setAlternatingRowColors(True)
         self.table.setSelectionMode(QtWidgets.QAbstractItemView.SingleSelection)
@@ -684,6 +1750,16 @@ class PurchasesWidget(QtWidgets.QWidget):
         layout.addLayout(summary)
         self.update_icons()
 
+    def set_compact(self, compact: bool) -> None:
+        for label, btn in self._action_buttons.items():
+            if label != "Clear Filters":
+                btn.setVisible(not compact)
+        self.actions_btn.setVisible(compact)
+
+    def set_compact_mode(self, enabled: bool) -> None:
+        _apply_compact_spacing(self, self.compact_check, enabled)
+        self.refresh()
+
     def update_icons(self) -> None:
         icon_map = {
             "Add Item": "add",
@@ -692,7 +1768,12 @@ class PurchasesWidget(QtWidgets.QWidget):
             "Delete": "delete",
             "Import": "import",
             "Export": "export",
+            "Export View": "export",
+            "Paste Rows": "import",
+            "Find Duplicates": "view",
+            "Score Unscored": "refresh",
             "Refresh": "refresh",
+            "Columns": "view",
             "Clear Filters": "clear",
         }
         for label, icon_name in icon_map.items():
@@ -701,51 +1782,111 @@ class PurchasesWidget(QtWidgets.QWidget):
                 btn.setIcon(self.main.get_icon(icon_name))
                 btn.setIconSize(QtCore.QSize(16, 16))
 
+    def _toggle_advanced(self, checked: bool) -> None:
+        self.search_edit.setPlaceholderText(
+            "Filter, e.g. cost>100 and recurrence=monthly" if checked else "Search (typo-tolerant)"
+        )
+        self.refresh()
+
+    def _search_predicate(self) -> Callable[[ItemRecord], bool]:
+        text = self.search_edit.text().strip()
+        if self.advanced_check.isChecked():
+            # An expression that does not parse yet (mid-typing) shows nothing rather than everything.
+            try:
+                matches = parse_filter(text)
+            except FilterError as exc:
+                self.search_edit.setToolTip(f"Filter error: {exc}")
+                return lambda item: False
+            self.search_edit.setToolTip("")
+            return matches
+        self.search_edit.setToolTip("")
+        query = text.lower()
+
+        def fuzzy(item: ItemRecord) -> bool:
+            haystack = " ".join(
+                [item.product, item.description, item.location, item.reference, item.justification]
+            ).lower()
+            return not query or query in haystack or item_match_score(item, query) >= FUZZY_THRESHOLD
+
+        return fuzzy
+
     def _filtered_items(self) -> List[ItemRecord]:
-        query = self.search_edit.text().strip().lower()
+        matches = self._search_predicate()
         mode = self.filter_combo.currentText()
+        recurrence = self.recurrence_combo.currentText()
         filtered = []
         for item in self.main.items:
-            haystack = " ".join(
-                [item.product, item.description, item.location, item.reference, item.justification]
-            ).lower()
-            if query and query not in haystack:
+            if not matches(item):
                 continue
-            if mode.startswith("High") and (item.overall_score or 0) <= 4:
+            if recurrence == "Recurring" and not item.is_recurring():
                 continue
-            if mode.startswith("Low") and (item.overall_score or 0) >= 2.5:
+            if recurrence in RECURRENCE_OPTIONS and item.recurrence_kind() != recurrence:
+                continue
+            # Score bands only cover scored items; unscored ones have their own filter.
+            if mode == "Unscored":
+                if item.overall_score is not None:
+                    continue
+            elif mode != "All" and item.overall_score is None:
+                continue
+            if mode.startswith("High") and item.overall_score <= 4:
+                continue
+            if mode.startswith("Low") and item.overall_score >= 2.5:
                 continue
             filtered.append(item)
         return filtered
 
     def refresh(self) -> None:
+        restore_scroll = _keep_scroll_position(self.table)
         items = self._filtered_items()
+        columns = self.main.item_columns
         self.table.setSortingEnabled(False)
+        self.table.setColumnCount(len(columns))
+        self.table.setHorizontalHeaderLabels([ITEM_COLUMNS[key][0] for key in columns])
+        # Compact mode hides secondary columns rather than dropping them, so sorting keeps its indices.
+        compact = self.compact_check.isChecked() and any(key in COMPACT_ITEM_COLUMNS for key in columns)
+        for col, key in enumerate(columns):
+            self.table.setColumnHidden(col, compact and key not in COMPACT_ITEM_COLUMNS)
         self.table.setRowCount(len(items))
         total = 0.0
         score_sum = 0.0
         scored = 0
         for row, item in enumerate(items):
-            values = [
-                item.product,
-                item.date.strftime(self.main.date_fmt),
-                f"{self.main.currency_symbol}{item.cost:.2f}",
-                str(item.urgency),
-                str(item.want),
-                f"{(item.overall_score or 0):.2f}",
-            ]
-            for col, val in enumerate(values):
-                self.table.setItem(row, col, QtWidgets.QTableWidgetItem(val))
+            for col, key in enumerate(columns):
+                cell = QtWidgets.QTableWidgetItem(self._cell_text(item, key))
+                if key == "overall" and item.overall_score is not None:
+                    cell.setForeground(QtGui.QColor(score_color(self.main.theme, item.overall_score)))
+                self.table.setItem(row, col, cell)
             total += item.cost
             if item.overall_score is not None:
                 scored += 1
                 score_sum += item.overall_score
         sort_order = QtCore.Qt.AscendingOrder if self.main.item_sort_ascending else QtCore.Qt.DescendingOrder
-        self.table.horizontalHeader().setSortIndicator(self.main.item_sort_column, sort_order)
+        sort_index = columns.index(self.main.item_sort_column) if self.main.item_sort_column in columns else -1
+        self.table.horizontalHeader().setSortIndicator(sort_index, sort_order)
         avg = score_sum / scored if scored else 0.0
         self.total_label.setText(f"Total: {self.main.currency_symbol}{total:.2f}")
         self.avg_label.setText(f"Average: {avg:.2f}")
-        self.count_label.setText(f"Items: {len(items)}")
+        error = self.search_edit.toolTip()
+        self.count_label.setText(error if error else f"Items: {len(items)}")
+        restore_scroll()
+
+    def _cell_text(self, item: ItemRecord, key: str) -> str:
+        if key == "date":
+            return item.date.strftime(self.main.date_fmt)
+        if key == "cost":
+            return f"{self.main.currency_symbol}{item.cost:.2f}"
+        if key == "overall":
+            return format_score(item.overall_score)
+        if key in ("expected_uses", "effort"):
+            return str(getattr(item, key) or "")
+        if key == "target_date":
+            return item.target_date.strftime("%Y-%m-%d") if item.target_date else ""
+        return " ".join(str(getattr(item, key)).splitlines())
+
+    def configure_columns(self) -> None:
+        dialog = ColumnsDialog(self, self.main.item_columns)
+        if dialog.exec() == QtWidgets.QDialog.Accepted:
+            self.main.set_item_columns(dialog.selected_columns())
 
     def _selected_item(self) -> Optional[ItemRecord]:
         rows = self.table.selectionModel().selectedRows()
@@ -760,6 +1901,43 @@ class PurchasesWidget(QtWidgets.QWidget):
     def add_item(self) -> None:
         self.main.add_or_edit_item()
 
+    def _update_quick_hint(self, text: str) -> None:
+        if not text.strip():
+            self.quick_hint.clear()
+            return
+        try:
+            preview = "Enter adds: " + parse_quick_add(text).describe(self.main.currency_symbol)
+            color = ""
+        except ValueError as exc:
+            preview, color = str(exc), QUICK_ADD_ERROR_COLOR
+        self.quick_hint.setText(preview)
+        self.quick_hint.setStyleSheet(f"color: {color};" if color else "")
+
+    def quick_add(self) -> None:
+        try:
+            parsed = parse_quick_add(self.quick_edit.text())
+        except ValueError:
+            # The hint already shows what is wrong; Enter on a bad line does nothing.
+            return
+        if self.main.config_manager.cost_exceeds_warning(parsed.cost):
+            confirm = QtWidgets.QMessageBox.question(
+                self,
+                "Unusually high cost",
+                f"{self.main.currency_symbol}{parsed.cost:,.2f} is above your warning threshold. Add anyway?",
+                QtWidgets.QMessageBox.Yes | QtWidgets.QMessageBox.No,
+                QtWidgets.QMessageBox.No,
+            )
+            if confirm != QtWidgets.QMessageBox.Yes:
+                return
+        record = parsed.to_record(self.main.config_manager.item_defaults())
+        record.location = self.main.config_manager.remember_location(record.location)
+        record.overall_score = score_item(record, self.main.weights).overall
+        self.main.items.append(record)
+        self.main._sort_items()
+        self.main.save_items()
+        self.quick_edit.clear()
+        self.main.notify(f"Added '{record.product}' (score {format_score(record.overall_score)}).")
+
     def edit_item(self) -> None:
         record = self._selected_item()
         if record:
@@ -776,7 +1954,62 @@ class PurchasesWidget(QtWidgets.QWidget):
             return
         if QtWidgets.QMessageBox.question(self, "Delete", f"Delete '{record.product}'?") == QtWidgets.QMessageBox.Yes:
             self.main.items = [i for i in self.main.items if i.id != record.id]
-            self.main.save_items(trigger_backup=self.main.settings["ui"].get("autosave", True))
+            for item in self.main.items:
+                if item.depends_on == record.id:
+                    item.depends_on = ""
+                    item.touch()
+            self.main.save_items()
+            self.main.notify(f"Deleted '{record.product}'.")
+
+    def score_unscored(self) -> None:
+        unscored = [item for item in self.main.items if item.overall_score is None]
+        if not unscored:
+            self.main.notify("Every item already has a score.")
+            return
+        for item in unscored:
+            item.overall_score = score_item(item, self.main.weights).overall
+        self.main._sort_items()
+        self.main.save_items()
+        self.main.notify(f"Scored {len(unscored)} item(s).")
+
+    def find_duplicates(self) -> None:
+        groups = duplicate_groups(self.main.items)
+        if not groups:
+            self.main.notify("No duplicate items found.")
+            return
+        symbol = self.main.currency_symbol
+        backed_up = False
+        merged = 0
+        for group in groups:
+            lines = [
+                f"{item.date.strftime(self.main.date_fmt)}  {item.product}  {symbol}{item.cost:,.2f}" for item in group
+            ]
+            choice = QtWidgets.QMessageBox.question(
+                self,
+                "Find Duplicates",
+                f"Merge these {len(group)} items into the oldest one?\n\n"
+                + "\n".join(lines)
+                + "\n\nLinked money moves to the kept item; justifications and notes are combined.",
+                QtWidgets.QMessageBox.Yes | QtWidgets.QMessageBox.No | QtWidgets.QMessageBox.Cancel,
+            )
+            if choice == QtWidgets.QMessageBox.Cancel:
+                break
+            if choice != QtWidgets.QMessageBox.Yes:
+                continue
+            if not backed_up and not self.main.safe_mode:
+                # Snapshot the files as they are before the first merge touches them.
+                self.main.flush_pending_saves(wait=True)
+                self.main._backup_files(
+                    [path for path in (self.main.items_path, self.main.money_path) if os.path.exists(path)]
+                )
+                backed_up = True
+            keep, _ = merge_group(group, self.main.items, self.main.money)
+            keep.overall_score = score_item(keep, self.main.weights).overall
+            merged += len(group) - 1
+        if merged:
+            self.main.save_items()
+            self.main.save_money()
+            self.main.notify(f"Consolidated {merged} duplicate item(s).")
 
     def import_data(self) -> None:
         choice, ok = QtWidgets.QInputDialog.getItem(
@@ -815,13 +2048,12 @@ class PurchasesWidget(QtWidgets.QWidget):
             self._export_bundle()
 
     def _import_items_csv(self) -> None:
-        path, _ = QtWidgets.QFileDialog.getOpenFileName(self, "Select items CSV", filter="CSV Files (*.csv)")
+        path, _ = QtWidgets.QFileDialog.getOpenFileName(self, "Select items CSV", filter="CSV Files (*.csv *.csv.gz)")
         if not path:
             return
-        try:
-            imported = read_items(path)
-        except Exception as exc:
-            QtWidgets.QMessageBox.critical(self, "Import failed", str(exc))
+        report = self.main.run_busy("Reading items...", lambda progress: validate_import(path, ItemRecord))
+        imported = _review_import(self, report, "items")
+        if imported is None:
             return
         choice = QtWidgets.QMessageBox.question(
             self,
@@ -832,30 +2064,53 @@ class PurchasesWidget(QtWidgets.QWidget):
         if choice == QtWidgets.QMessageBox.Yes:
             self.main.items = imported
         else:
-            self.main.items = _merge_by_id(self.main.items, imported)
+            merged = _merge_import(self, self.main.items, imported, "items")
+            if merged is None:
+                return
+            self.main.items = merged
         self.main._sort_items()
-        self.main.save_items(trigger_backup=self.main.settings["ui"].get("autosave", True))
-        QtWidgets.QMessageBox.information(self, "Import", "Items imported.")
+        self.main.save_items()
+        self.main.notify(f"Imported {len(imported)} items.")
 
     def _export_items_csv(self) -> None:
         path, _ = QtWidgets.QFileDialog.getSaveFileName(self, "Save items CSV", filter="CSV Files (*.csv)")
         if not path:
             return
         try:
-            write_items(path, self._filtered_items())
+            write_items(path, self.main.items)
         except Exception as exc:
             QtWidgets.QMessageBox.critical(self, "Export failed", str(exc))
         else:
             QtWidgets.QMessageBox.information(self, "Export", "Items exported.")
 
+    def export_view(self) -> None:
+        _export_view(self, self._filtered_items(), write_items, "items")
+
+    def paste_rows(self) -> None:
+        symbol = self.main.currency_symbol
+        pasted = _paste_rows(self, ItemRecord, "items", lambda item: f"{item.product}  {symbol}{item.cost:,.2f}")
+        if pasted is None:
+            return
+        for item in pasted:
+            item.overall_score = score_item(item, self.main.weights).overall
+        merged = _merge_import(self, self.main.items, pasted, "items")
+        if merged is None:
+            return
+        self.main.items = merged
+        self.main._sort_items()
+        self.main.save_items()
+        self.main.notify(f"Pasted {len(pasted)} items.")
+
     def _import_money_csv(self) -> None:
-        path, _ = QtWidgets.QFileDialog.getOpenFileName(self, "Select money CSV", filter="CSV Files (*.csv)")
+        path, _ = QtWidgets.QFileDialog.getOpenFileName(self, "Select money CSV", filter="CSV Files (*.csv *.csv.gz)")
         if not path:
             return
-        try:
-            imported = read_money(path)
-        except Exception as exc:
-            QtWidgets.QMessageBox.critical(self, "Import failed", str(exc))
+        item_ids = [item.id for item in self.main.items]
+        report = self.main.run_busy(
+            "Reading money entries...", lambda progress: validate_import(path, MoneyRecord, item_ids)
+        )
+        imported = _review_import(self, report, "money entries")
+        if imported is None:
             return
         choice = QtWidgets.QMessageBox.question(
             self,
@@ -866,10 +2121,13 @@ class PurchasesWidget(QtWidgets.QWidget):
         if choice == QtWidgets.QMessageBox.Yes:
             self.main.money = imported
         else:
-            self.main.money = _merge_by_id(self.main.money, imported)
+            merged = _merge_import(self, self.main.money, imported, "money entries")
+            if merged is None:
+                return
+            self.main.money = merged
         self.main._sort_money()
-        self.main.save_money(trigger_backup=self.main.settings["ui"].get("autosave", True))
-        QtWidgets.QMessageBox.information(self, "Import", "Money entries imported.")
+        self.main.save_money()
+        self.main.notify(f"Imported {len(imported)} money entries.")
 
     def _export_money_csv(self) -> None:
         path, _ = QtWidgets.QFileDialog.getSaveFileName(self, "Save money CSV", filter="CSV Files (*.csv)")
@@ -882,59 +2140,18 @@ class PurchasesWidget(QtWidgets.QWidget):
             QtWidgets.QMessageBox.critical(self, "Export failed", str(exc))
 
     def _import_bundle(self) -> None:
-        path, _ = QtWidgets.QFileDialog.getOpenFileName(self, "Select bundle", filter="Bundle Files (*.json)")
-        if not path:
-            return
-        try:
-            items, money, metadata = read_bundle(path)
-        except Exception as exc:
-            QtWidgets.QMessageBox.critical(self, "Import failed", f"Could not read bundle:\n{exc}")
-            return
-        details = metadata.get("generated_at", "unknown time") if isinstance(metadata, dict) else "unknown time"
-        choice = QtWidgets.QMessageBox.question(
-            self,
-            "Import Bundle",
-            (
-                f"Bundle contains {len(items)} items and {len(money)} money entries "
-                f"(created {details}).\n\n"
-                "Yes = overwrite current items and money with bundle contents.\n"
-                "No = append/merge; incoming records replace matching IDs and new IDs are added."
-            ),
-            QtWidgets.QMessageBox.Yes | QtWidgets.QMessageBox.No,
-        )
-        if choice == QtWidgets.QMessageBox.Yes:
-            self.main.items = items
-            self.main.money = money
-        else:
-            self.main.items = _merge_by_id(self.main.items, items)
-            self.main.money = _merge_by_id(self.main.money, money)
-        self.main._sort_items()
-        self.main._sort_money()
-        autosave = self.main.settings["ui"].get("autosave", True)
-        self.main.save_items(trigger_backup=autosave)
-        self.main.save_money(trigger_backup=autosave)
-        QtWidgets.QMessageBox.information(self, "Import", "Bundle imported.")
+        _import_bundle(self, self.main)
 
     def _export_bundle(self) -> None:
-        path, _ = QtWidgets.QFileDialog.getSaveFileName(self, "Save bundle", filter="Bundle Files (*.json)")
-        if not path:
-            return
-        try:
-            write_bundle(path, self.main.items, self.main.money)
-        except Exception as exc:
-            QtWidgets.QMessageBox.critical(self, "Export failed", f"Could not write bundle:\n{exc}")
-        else:
-            QtWidgets.QMessageBox.information(
-                self,
-                "Export",
-                f"Bundle exported with {len(self.main.items)} items and {len(self.main.money)} money entries.",
-            )
+        _export_bundle(self, self.main)
 
     def _clear_filters(self) -> None:
         self.search_edit.clear()
         self.filter_combo.setCurrentIndex(0)
+        self.recurrence_combo.setCurrentIndex(0)
 
-    def _handle_sort(self, column: int) -> None:
+    def _handle_sort(self, index: int) -> None:
+        column = self.main.item_columns[index]
         if column == self.main.item_sort_column:
             self.main.item_sort_ascending = not self.main.item_sort_ascending
         else:
@@ -963,6 +2180,7 @@ class MoneyWidget(QtWidgets.QWidget):
         self.type_filter = QtWidgets.QComboBox()
         self.type_filter.addItems(["All", "Income", "Expense"])
         self.type_filter.currentIndexChanged.connect(self.refresh)
+        self.compact_check = _compact_check(self.main)
         clear_btn = QtWidgets.QPushButton("Clear Filters")
         clear_btn.clicked.connect(self._clear_filters)
         self._action_buttons: Dict[str, QtWidgets.QPushButton] = {"Clear Filters": clear_btn}
@@ -971,24 +2189,33 @@ class MoneyWidget(QtWidgets.QWidget):
             ("Add Entry", self.add_entry),
             ("Edit", self.edit_entry),
             ("Delete", self.delete_entry),
+            ("Split", self.split_entry),
+            ("Templates", self.manage_templates),
             ("Import", self.import_data),
             ("Export", self.export_data),
+            ("Export View", self.export_view),
+            ("Paste Rows", self.paste_rows),
             ("Refresh", self.refresh),
         ]:
             btn = QtWidgets.QPushButton(text)
             btn.clicked.connect(handler)
             controls.addWidget(btn)
             self._action_buttons[text] = btn
+        self.actions_btn = _actions_menu_button(self._action_buttons)
+        controls.addWidget(self.actions_btn)
 
         controls.addStretch()
         controls.addWidget(QtWidgets.QLabel("Type"))
         controls.addWidget(self.type_filter)
         controls.addWidget(self.search_edit)
         controls.addWidget(clear_btn)
+        controls.addWidget(self.compact_check)
         layout.addLayout(controls)
 
-        self.table = QtWidgets.QTableWidget(0, 5)
-        self.table.setHorizontalHeaderLabels(["Date", "Type", "Source/Destination", "Amount", "Linked Item"])
+        self.table = QtWidgets.QTableWidget(0, 9)
+        self.table.setHorizontalHeaderLabels(
+            ["Date", "Type", "Source/Destination", "Amount", "Linked Item", "Status", "Category", "Balance", "Cleared"]
+        )
         self.table.horizontalHeader().setStretchLastSection(True)
         self.table.setAlternatingRowColors(True)
         self.table.setSelectionMode(QtWidgets.QAbstractItemView.SingleSelection)
@@ -1006,13 +2233,27 @@ class MoneyWidget(QtWidgets.QWidget):
         self.income_label = QtWidgets.QLabel("Income: 0")
         self.expense_label = QtWidgets.QLabel("Expenses: 0")
         self.balance_label = QtWidgets.QLabel("Balance: 0")
-        for lbl in (self.income_label, self.expense_label, self.balance_label):
+        self.cleared_label = QtWidgets.QLabel()
+        self.cleared_label.setToolTip(
+            "Balance of the entries marked cleared, to compare with a bank statement, against all entries to date."
+        )
+        for lbl in (self.income_label, self.expense_label, self.balance_label, self.cleared_label):
             summary.addWidget(lbl)
         summary.addStretch()
         layout.addLayout(summary)
 
-        breakdown_group = QtWidgets.QGroupBox("Category breakdowns (Source/Destination)")
-        breakdown_layout = QtWidgets.QHBoxLayout(breakdown_group)
+        breakdown_group = QtWidgets.QGroupBox("Breakdowns")
+        breakdown_outer = QtWidgets.QVBoxLayout(breakdown_group)
+        group_row = QtWidgets.QHBoxLayout()
+        group_row.addWidget(QtWidgets.QLabel("Group by"))
+        self.breakdown_key = QtWidgets.QComboBox()
+        self.breakdown_key.addItems(["Category", "Source/Destination"])
+        self.breakdown_key.currentIndexChanged.connect(self.refresh)
+        group_row.addWidget(self.breakdown_key)
+        group_row.addStretch()
+        breakdown_outer.addLayout(group_row)
+        breakdown_layout = QtWidgets.QHBoxLayout()
+        breakdown_outer.addLayout(breakdown_layout)
         expense_layout = QtWidgets.QVBoxLayout()
         expense_layout.addWidget(QtWidgets.QLabel("Expenses"))
         self.expense_breakdown_table = self._build_breakdown_table()
@@ -1026,13 +2267,28 @@ class MoneyWidget(QtWidgets.QWidget):
         layout.addWidget(breakdown_group)
         self.update_icons()
 
+    def set_compact(self, compact: bool) -> None:
+        for label, btn in self._action_buttons.items():
+            if label != "Clear Filters":
+                btn.setVisible(not compact)
+        self.actions_btn.setVisible(compact)
+
+    def set_compact_mode(self, enabled: bool) -> None:
+        _apply_compact_spacing(self, self.compact_check, enabled)
+        for col in COMPACT_HIDDEN_MONEY_COLUMNS:
+            self.table.setColumnHidden(col, enabled)
+
     def update_icons(self) -> None:
         icon_map = {
             "Add Entry": "add",
             "Edit": "edit",
             "Delete": "delete",
+            "Split": "edit",
+            "Templates": "edit",
             "Import": "import",
             "Export": "export",
+            "Export View": "export",
+            "Paste Rows": "import",
             "Refresh": "refresh",
             "Clear Filters": "clear",
         }
@@ -1063,6 +2319,7 @@ class MoneyWidget(QtWidgets.QWidget):
                     entry.entry_type,
                     entry.source_or_destination,
                     entry.notes,
+                    entry.category,
                     entry.linked_item_id,
                     id_to_product.get(entry.linked_item_id, ""),
                 ]
@@ -1078,40 +2335,69 @@ class MoneyWidget(QtWidgets.QWidget):
 
     def refresh(self) -> None:
         id_to_product = {item.id: item.product for item in self.main.items}
+        item_ids = set(id_to_product)
+        now = datetime.now()
         entries = self._filtered_entries()
+        restore_scroll = _keep_scroll_position(self.table)
         self.table.setSortingEnabled(False)
         self.table.setRowCount(len(entries))
         income = 0.0
         expense = 0.0
         expense_totals: Dict[str, float] = {}
         income_totals: Dict[str, float] = {}
+        by_category = self.breakdown_key.currentText() == "Category"
+        running = 0.0
+        balances: Dict[int, float] = {}
+        for entry in sorted(entries, key=lambda m: (m.date, m.id)):
+            running += entry.signed_amount()
+            balances[id(entry)] = running
         for row, entry in enumerate(entries):
+            key = entry.category_label() if by_category else entry.source_or_destination.strip() or UNCATEGORIZED
             if entry.entry_type.lower() == "income":
                 income += entry.amount
-                key = entry.source_or_destination.strip() or "Uncategorized"
                 income_totals[key] = income_totals.get(key, 0.0) + entry.amount
             elif entry.entry_type.lower() == "expense":
                 expense += entry.amount
-                key = entry.source_or_destination.strip() or "Uncategorized"
                 expense_totals[key] = expense_totals.get(key, 0.0) + entry.amount
             linked_display = id_to_product.get(entry.linked_item_id, entry.linked_item_id)
+            status = _money_status(entry, item_ids, now)
             values = [
                 entry.date.strftime(self.main.date_fmt),
                 entry.entry_type.title(),
                 entry.source_or_destination,
                 f"{self.main.currency_symbol}{entry.amount:.2f}",
                 linked_display,
+                status,
+                entry.category,
+                f"{self.main.currency_symbol}{balances[id(entry)]:.2f}",
+                "✓" if entry.cleared else "",
             ]
+            kind = entry.entry_type.strip().lower()
+            type_color = self.main.theme.get(kind) if kind in ("income", "expense") else None
             for col, val in enumerate(values):
-                self.table.setItem(row, col, QtWidgets.QTableWidgetItem(val))
+                cell = QtWidgets.QTableWidgetItem(val)
+                if type_color:
+                    cell.setForeground(QtGui.QColor(str(type_color)))
+                self.table.setItem(row, col, cell)
+            if balances[id(entry)] < -0.005:
+                self.table.item(row, 7).setForeground(QtGui.QColor(str(self.main.theme["negative"])))
+            status_color = MONEY_STATUS_COLORS.get(status)
+            if status_color:
+                self.table.item(row, 5).setForeground(QtGui.QColor(str(self.main.theme[status_color])))
         sort_order = QtCore.Qt.AscendingOrder if self.main.money_sort_ascending else QtCore.Qt.DescendingOrder
         self.table.horizontalHeader().setSortIndicator(self.main.money_sort_column, sort_order)
-        balance = income - expense
+        balance = sum(entry.signed_amount() for entry in entries)
         self.income_label.setText(f"Income: {self.main.currency_symbol}{income:.2f}")
         self.expense_label.setText(f"Expenses: {self.main.currency_symbol}{expense:.2f}")
         self.balance_label.setText(f"Balance: {self.main.currency_symbol}{balance:.2f}")
+        cleared, actual, uncleared = reconciliation(self.main.money, now)
+        self.cleared_label.setText(
+            f"Cleared: {self.main.currency_symbol}{cleared:.2f} of {self.main.currency_symbol}{actual:.2f}"
+            f" ({uncleared} uncleared)"
+        )
         self._populate_breakdown(self.expense_breakdown_table, expense_totals, expense)
         self._populate_breakdown(self.income_breakdown_table, income_totals, income)
+        restore_scroll()
 
     def _populate_breakdown(
         self, table: QtWidgets.QTableWidget, totals: Dict[str, float], total_amount: float
@@ -1152,7 +2438,22 @@ class MoneyWidget(QtWidgets.QWidget):
             return
         if QtWidgets.QMessageBox.question(self, "Delete", "Delete this entry?") == QtWidgets.QMessageBox.Yes:
             self.main.money = [m for m in self.main.money if m.id != record.id]
-            self.main.save_money(trigger_backup=self.main.settings["ui"].get("autosave", True))
+            self.main.save_money()
+            self.main.notify("Money entry deleted.")
+
+    def manage_templates(self) -> None:
+        TemplatesDialog(self.main).exec()
+
+    def split_entry(self) -> None:
+        record = self._selected_entry()
+        if not record:
+            return
+        dialog = SplitDialog(self.main, record)
+        if dialog.exec() == QtWidgets.QDialog.Accepted:
+            self.main.money = replace_with_split(self.main.money, record, dialog.result_parts)
+            self.main._sort_money()
+            self.main.save_money()
+            self.main.notify(f"Split into {len(dialog.result_parts)} entries.")
 
     def import_data(self) -> None:
         choice, ok = QtWidgets.QInputDialog.getItem(
@@ -1191,13 +2492,15 @@ class MoneyWidget(QtWidgets.QWidget):
             self._export_bundle()
 
     def _import_money_csv(self) -> None:
-        path, _ = QtWidgets.QFileDialog.getOpenFileName(self, "Select money CSV", filter="CSV Files (*.csv)")
+        path, _ = QtWidgets.QFileDialog.getOpenFileName(self, "Select money CSV", filter="CSV Files (*.csv *.csv.gz)")
         if not path:
             return
-        try:
-            imported = read_money(path)
-        except Exception as exc:
-            QtWidgets.QMessageBox.critical(self, "Import failed", str(exc))
+        item_ids = [item.id for item in self.main.items]
+        report = self.main.run_busy(
+            "Reading money entries...", lambda progress: validate_import(path, MoneyRecord, item_ids)
+        )
+        imported = _review_import(self, report, "money entries")
+        if imported is None:
             return
         choice = QtWidgets.QMessageBox.question(
             self,
@@ -1208,29 +2511,52 @@ class MoneyWidget(QtWidgets.QWidget):
         if choice == QtWidgets.QMessageBox.Yes:
             self.main.money = imported
         else:
-            self.main.money = _merge_by_id(self.main.money, imported)
+            merged = _merge_import(self, self.main.money, imported, "money entries")
+            if merged is None:
+                return
+            self.main.money = merged
         self.main._sort_money()
-        self.main.save_money(trigger_backup=self.main.settings["ui"].get("autosave", True))
-        QtWidgets.QMessageBox.information(self, "Import", "Money entries imported.")
+        self.main.save_money()
+        self.main.notify(f"Imported {len(imported)} money entries.")
 
     def _export_money_csv(self) -> None:
         path, _ = QtWidgets.QFileDialog.getSaveFileName(self, "Save money CSV", filter="CSV Files (*.csv)")
         if not path:
             return
         try:
-            write_money(path, self._filtered_entries())
+            write_money(path, self.main.money)
             QtWidgets.QMessageBox.information(self, "Export", "Money entries exported.")
         except Exception as exc:
             QtWidgets.QMessageBox.critical(self, "Export failed", str(exc))
 
+    def export_view(self) -> None:
+        _export_view(self, self._filtered_entries(), write_money, "money entries")
+
+    def paste_rows(self) -> None:
+        symbol = self.main.currency_symbol
+
+        def describe(entry: MoneyRecord) -> str:
+            when = entry.date.strftime(self.main.date_fmt)
+            return f"{when}  {entry.entry_type}  {entry.source_or_destination}  {symbol}{entry.amount:,.2f}"
+
+        pasted = _paste_rows(self, MoneyRecord, "money entries", describe, [item.id for item in self.main.items])
+        if pasted is None:
+            return
+        merged = _merge_import(self, self.main.money, pasted, "money entries")
+        if merged is None:
+            return
+        self.main.money = merged
+        self.main._sort_money()
+        self.main.save_money()
+        self.main.notify(f"Pasted {len(pasted)} money entries.")
+
     def _import_items_csv(self) -> None:
-        path, _ = QtWidgets.QFileDialog.getOpenFileName(self, "Select items CSV", filter="CSV Files (*.csv)")
+        path, _ = QtWidgets.QFileDialog.getOpenFileName(self, "Select items CSV", filter="CSV Files (*.csv *.csv.gz)")
         if not path:
             return
-        try:
-            imported = read_items(path)
-        except Exception as exc:
-            QtWidgets.QMessageBox.critical(self, "Import failed", str(exc))
+        report = self.main.run_busy("Reading items...", lambda progress: validate_import(path, ItemRecord))
+        imported = _review_import(self, report, "items")
+        if imported is None:
             return
         choice = QtWidgets.QMessageBox.question(
             self,
@@ -1241,10 +2567,13 @@ class MoneyWidget(QtWidgets.QWidget):
         if choice == QtWidgets.QMessageBox.Yes:
             self.main.items = imported
         else:
-            self.main.items = _merge_by_id(self.main.items, imported)
+            merged = _merge_import(self, self.main.items, imported, "items")
+            if merged is None:
+                return
+            self.main.items = merged
         self.main._sort_items()
-        self.main.save_items(trigger_backup=self.main.settings["ui"].get("autosave", True))
-        QtWidgets.QMessageBox.information(self, "Import", "Items imported.")
+        self.main.save_items()
+        self.main.notify(f"Imported {len(imported)} items.")
 
     def _export_items_csv(self) -> None:
         path, _ = QtWidgets.QFileDialog.getSaveFileName(self, "Save items CSV", filter="CSV Files (*.csv)")
@@ -1257,53 +2586,10 @@ class MoneyWidget(QtWidgets.QWidget):
             QtWidgets.QMessageBox.critical(self, "Export failed", str(exc))
 
     def _import_bundle(self) -> None:
-        path, _ = QtWidgets.QFileDialog.getOpenFileName(self, "Select bundle", filter="Bundle Files (*.json)")
-        if not path:
-            return
-        try:
-            items, money, metadata = read_bundle(path)
-        except Exception as exc:
-            QtWidgets.QMessageBox.critical(self, "Import failed", f"Could not read bundle:\n{exc}")
-            return
-        details = metadata.get("generated_at", "unknown time") if isinstance(metadata, dict) else "unknown time"
-        choice = QtWidgets.QMessageBox.question(
-            self,
-            "Import Bundle",
-            (
-                f"Bundle contains {len(items)} items and {len(money)} money entries "
-                f"(created {details}).\n\n"
-                "Yes = overwrite current items and money with bundle contents.\n"
-                "No = append/merge; incoming records replace matching IDs and new IDs are added."
-            ),
-            QtWidgets.QMessageBox.Yes | QtWidgets.QMessageBox.No,
-        )
-        if choice == QtWidgets.QMessageBox.Yes:
-            self.main.items = items
-            self.main.money = money
-        else:
-            self.main.items = _merge_by_id(self.main.items, items)
-            self.main.money = _merge_by_id(self.main.money, money)
-        self.main._sort_items()
-        self.main._sort_money()
-        autosave = self.main.settings["ui"].get("autosave", True)
-        self.main.save_items(trigger_backup=autosave)
-        self.main.save_money(trigger_backup=autosave)
-        QtWidgets.QMessageBox.information(self, "Import", "Bundle imported.")
+        _import_bundle(self, self.main)
 
     def _export_bundle(self) -> None:
-        path, _ = QtWidgets.QFileDialog.getSaveFileName(self, "Save bundle", filter="Bundle Files (*.json)")
-        if not path:
-            return
-        try:
-            write_bundle(path, self.main.items, self.main.money)
-        except Exception as exc:
-            QtWidgets.QMessageBox.critical(self, "Export failed", f"Could not write bundle:\n{exc}")
-        else:
-            QtWidgets.QMessageBox.information(
-                self,
-                "Export",
-                f"Bundle exported with {len(self.main.items)} items and {len(self.main.money)} money entries.",
-            )
+        _export_bundle(self, self.main)
 
     def _clear_filters(self) -> None:
         self.search_edit.clear()
@@ -1332,7 +2618,7 @@ class ThemeWidget(QtWidgets.QWidget):
         self.theme_combo = QtWidgets.QComboBox()
         theme_names = sorted(self.main.config_manager.themes.keys())
         self.theme_combo.addItems(theme_names)
-        current_theme = self.main.settings.get("themes", {}).get("default", "light")
+        current_theme = self.main.config_manager.active_theme_name()
         if current_theme in theme_names:
             self.theme_combo.setCurrentText(current_theme)
         self.theme_combo.currentTextChanged.connect(self._set_theme)
@@ -1347,6 +2633,217 @@ class ThemeWidget(QtWidgets.QWidget):
         self.main.apply_theme(name)
 
 
+WEIGHT_FIELDS = [
+    ("Date", "date"),
+    ("Cost", "cost"),
+    ("Urgency", "urgency"),
+    ("Value", "value"),
+    ("Want", "want"),
+    ("Price vs Similar", "price_comp"),
+    ("Effect", "effect"),
+    ("Cost per use", "cost_per_use"),
+    ("Target date", "target_date"),
+    ("Effort (inverted)", "effort"),
+]
+# Opt-in dimensions default to a zero weight so they do not change scores until enabled.
+_OPT_IN_WEIGHTS = ("cost_per_use", "target_date", "effort")
+
+
+class WeightsWidget(QtWidgets.QWidget):
+    def __init__(self, main: MainWindow) -> None:
+        super().__init__()
+        self.main = main
+        self._build_ui()
+        self._load(self.main.weights)
+
+    def _build_ui(self) -> None:
+        layout = QtWidgets.QHBoxLayout(self)
+        left = QtWidgets.QVBoxLayout()
+
+        weights_group = QtWidgets.QGroupBox("Weights")
+        weights_form = QtWidgets.QFormLayout(weights_group)
+        weights_form.setLabelAlignment(QtCore.Qt.AlignLeft)
+        self.weight_spins: Dict[str, QtWidgets.QDoubleSpinBox] = {}
+        for label, key in WEIGHT_FIELDS:
+            spin = QtWidgets.QDoubleSpinBox()
+            spin.setRange(0.0, 10.0)
+            spin.setSingleStep(0.1)
+            spin.setSuffix("×")
+            spin.valueChanged.connect(self._update_preview)
+            weights_form.addRow(label, spin)
+            self.weight_spins[key] = spin
+        left.addWidget(weights_group)
+
+        age_group = QtWidgets.QGroupBox("Age thresholds")
+        age_form = QtWidgets.QFormLayout(age_group)
+        self.recent_days = QtWidgets.QSpinBox()
+        self.recent_days.setRange(0, 3650)
+        self.recent_days.setSuffix(" days")
+        self.recent_days.valueChanged.connect(self._update_preview)
+        self.mid_days = QtWidgets.QSpinBox()
+        self.mid_days.setRange(0, 3650)
+        self.mid_days.setSuffix(" days")
+        self.mid_days.valueChanged.connect(self._update_preview)
+        age_form.addRow("Recent (scores 1)", self.recent_days)
+        age_form.addRow("Mid (scores 3)", self.mid_days)
+        self.target_ramp_days = QtWidgets.QSpinBox()
+        self.target_ramp_days.setRange(1, 3650)
+        self.target_ramp_days.setSuffix(" days")
+        self.target_ramp_days.setToolTip("Target-date scores climb from 1 to 5 over this many days before the target.")
+        self.target_ramp_days.valueChanged.connect(self._update_preview)
+        age_form.addRow("Target ramp", self.target_ramp_days)
+        left.addWidget(age_group)
+        left.addStretch()
+
+        right = QtWidgets.QVBoxLayout()
+        bands_group = QtWidgets.QGroupBox("Cost bands")
+        bands_layout = QtWidgets.QVBoxLayout(bands_group)
+        self.bands_table = QtWidgets.QTableWidget(0, 2)
+        self.bands_table.setHorizontalHeaderLabels(["Max cost (blank = no limit)", "Score"])
+        self.bands_table.horizontalHeader().setSectionResizeMode(QtWidgets.QHeaderView.Stretch)
+        self.bands_table.verticalHeader().setVisible(False)
+        self.bands_table.itemChanged.connect(self._update_preview)
+        bands_layout.addWidget(self.bands_table)
+        band_buttons = QtWidgets.QHBoxLayout()
+        add_band_btn = QtWidgets.QPushButton("Add band")
+        add_band_btn.clicked.connect(self._add_band)
+        remove_band_btn = QtWidgets.QPushButton("Remove band")
+        remove_band_btn.clicked.connect(self._remove_band)
+        band_buttons.addWidget(add_band_btn)
+        band_buttons.addWidget(remove_band_btn)
+        band_buttons.addStretch()
+        bands_layout.addLayout(band_buttons)
+        right.addWidget(bands_group)
+
+        preview_group = QtWidgets.QGroupBox("Preview")
+        preview_form = QtWidgets.QFormLayout(preview_group)
+        self.preview_combo = QtWidgets.QComboBox()
+        self.preview_combo.currentIndexChanged.connect(self._update_preview)
+        self.preview_label = QtWidgets.QLabel()
+        preview_form.addRow("Item", self.preview_combo)
+        preview_form.addRow("Score", self.preview_label)
+        right.addWidget(preview_group)
+
+        buttons = QtWidgets.QHBoxLayout()
+        buttons.addStretch()
+        revert_btn = QtWidgets.QPushButton("Revert")
+        revert_btn.clicked.connect(lambda: self._load(self.main.weights))
+        save_btn = QtWidgets.QPushButton("Save and rescore")
+        save_btn.clicked.connect(self._save)
+        buttons.addWidget(revert_btn)
+        buttons.addWidget(save_btn)
+        right.addLayout(buttons)
+
+        layout.addLayout(left, 1)
+        layout.addLayout(right, 2)
+
+    def showEvent(self, event: QtGui.QShowEvent) -> None:
+        self._refresh_preview_items()
+        super().showEvent(event)
+
+    def _load(self, weights: Dict[str, Any]) -> None:
+        values = weights.get("weights", {})
+        for key, spin in self.weight_spins.items():
+            spin.blockSignals(True)
+            spin.setValue(float(values.get(key, 0.0 if key in _OPT_IN_WEIGHTS else 1.0)))
+            spin.blockSignals(False)
+        date_cfg = weights.get("date_scoring", {})
+        self.recent_days.blockSignals(True)
+        self.mid_days.blockSignals(True)
+        self.target_ramp_days.blockSignals(True)
+        self.recent_days.setValue(int(date_cfg.get("recent_days", 7)))
+        self.mid_days.setValue(int(date_cfg.get("mid_days", 30)))
+        self.target_ramp_days.setValue(int(date_cfg.get("target_ramp_days", 30)))
+        self.recent_days.blockSignals(False)
+        self.mid_days.blockSignals(False)
+        self.target_ramp_days.blockSignals(False)
+        self.bands_table.blockSignals(True)
+        self.bands_table.setRowCount(0)
+        for band in weights.get("cost_bands", []):
+            max_val = band.get("max")
+            self._append_band_row("" if max_val is None else f"{float(max_val):g}", f"{float(band.get('score', 1)):g}")
+        self.bands_table.blockSignals(False)
+        self._update_preview()
+
+    def _append_band_row(self, max_text: str, score_text: str) -> None:
+        row = self.bands_table.rowCount()
+        self.bands_table.insertRow(row)
+        self.bands_table.setItem(row, 0, QtWidgets.QTableWidgetItem(max_text))
+        self.bands_table.setItem(row, 1, QtWidgets.QTableWidgetItem(score_text))
+
+    def _add_band(self) -> None:
+        self._append_band_row("", "1")
+        self._update_preview()
+
+    def _remove_band(self) -> None:
+        row = self.bands_table.currentRow()
+        if row < 0:
+            row = self.bands_table.rowCount() - 1
+        if row >= 0:
+            self.bands_table.removeRow(row)
+            self._update_preview()
+
+    def _collect(self) -> Dict[str, Any]:
+        weights = deepcopy(self.main.weights)
+        weights["weights"] = {key: spin.value() for key, spin in self.weight_spins.items()}
+        weights["date_scoring"] = {
+            "recent_days": self.recent_days.value(),
+            "mid_days": self.mid_days.value(),
+            "target_ramp_days": self.target_ramp_days.value(),
+        }
+        bands = []
+        for row in range(self.bands_table.rowCount()):
+            max_item = self.bands_table.item(row, 0)
+            score_item_cell = self.bands_table.item(row, 1)
+            max_text = max_item.text().strip() if max_item else ""
+            score_text = score_item_cell.text().strip() if score_item_cell else ""
+            try:
+                max_val = float(max_text) if max_text else None
+                score = float(score_text)
+            except ValueError:
+                raise ValueError(f"Cost band {row + 1} needs numeric values.") from None
+            bands.append({"max": max_val, "score": score})
+        weights["cost_bands"] = bands
+        return weights
+
+    def _refresh_preview_items(self) -> None:
+        current = self.preview_combo.currentData()
+        self.preview_combo.blockSignals(True)
+        self.preview_combo.clear()
+        for item in self.main.items:
+            self.preview_combo.addItem(item.product or item.id, item.id)
+        idx = self.preview_combo.findData(current)
+        self.preview_combo.setCurrentIndex(idx if idx >= 0 else 0)
+        self.preview_combo.blockSignals(False)
+        self._update_preview()
+
+    def _update_preview(self, *_args: object) -> None:
+        item_id = self.preview_combo.currentData()
+        record = next((item for item in self.main.items if item.id == item_id), None)
+        if record is None:
+            self.preview_label.setText("Add an item to preview scores.")
+            return
+        try:
+            new_score = score_item(record, self._collect()).overall
+        except ValueError as exc:
+            self.preview_label.setText(str(exc))
+            return
+        current = score_item(record, self.main.weights).overall
+        self.preview_label.setText(f"{current:.2f} now → {new_score:.2f} with these weights")
+
+    def _save(self) -> None:
+        try:
+            weights = self._collect()
+            self.main.apply_weights(weights)
+        except (ValueError, OSError) as exc:
+            QtWidgets.QMessageBox.warning(self, "Weights not saved", str(exc))
+            return
+        # Show the bands in the order they were saved (sorted by maximum).
+        self._load(self.main.weights)
+        self._update_preview()
+        QtWidgets.QMessageBox.information(self, "Weights", "Weights saved and all items rescored.")
+
+
 class SettingsWidget(QtWidgets.QWidget):
     def __init__(self, main: MainWindow) -> None:
         super().__init__()
@@ -1359,8 +2856,62 @@ class SettingsWidget(QtWidgets.QWidget):
         self.autosave_check = QtWidgets.QCheckBox("Enable autosave")
         self.autosave_check.setChecked(self.main.settings["ui"].get("autosave", True))
         self.autosave_check.stateChanged.connect(self._toggle_autosave)
-        layout.addRow("Autosave", self.autosave_check)
-
+        self.autosave_interval = QtWidgets.QSpinBox()
+        self.autosave_interval.setRange(1, 600)
+        self.autosave_interval.setSuffix(" s")
+        self.autosave_interval.setValue(int(self.main.settings["ui"].get("autosave_interval_seconds", 5)))
+        self.autosave_interval.valueChanged.connect(self._set_autosave_interval)
+        autosave_row = QtWidgets.QHBoxLayout()
+        autosave_row.addWidget(self.autosave_check)
+        autosave_row.addWidget(QtWidgets.QLabel("Write at most every"))
+        autosave_row.addWidget(self.autosave_interval)
+        autosave_row.addStretch()
+        layout.addRow("Autosave", autosave_row)
+        autosave_hint = QtWidgets.QLabel("With autosave off, changes are written only when you press Ctrl+S or Save now.")
+        autosave_hint.setWordWrap(True)
+        layout.addRow("", autosave_hint)
+        self.font_scale_spin = QtWidgets.QDoubleSpinBox()
+        self.font_scale_spin.setRange(0.5, 3.0)
+        self.font_scale_spin.setSingleStep(0.1)
+        self.font_scale_spin.setDecimals(1)
+        self.font_scale_spin.setSuffix("x")
+        self.font_scale_spin.setValue(float(self.main.settings["ui"].get("font_scale", 1.0) or 1.0))
+        self.font_scale_spin.valueChanged.connect(self._set_font_scale)
+        self.row_height_spin = QtWidgets.QSpinBox()
+        self.row_height_spin.setRange(0, 120)
+        self.row_height_spin.setSuffix(" px")
+        self.row_height_spin.setSpecialValueText("Fit text")
+        self.row_height_spin.setValue(int(self.main.settings["ui"].get("row_height", 0) or 0))
+        self.row_height_spin.valueChanged.connect(self._set_row_height)
+        display_row = QtWidgets.QHBoxLayout()
+        display_row.addWidget(QtWidgets.QLabel("Font size"))
+        display_row.addWidget(self.font_scale_spin)
+        display_row.addWidget(QtWidgets.QLabel("Grid row height"))
+        display_row.addWidget(self.row_height_spin)
+        display_row.addStretch()
+        layout.addRow("Display", display_row)
+        self.save_status_check = QtWidgets.QCheckBox("Show save status and last-saved time in the status bar")
+        self.save_status_check.setChecked(self.main.settings["ui"].get("show_save_status", True))
+        self.save_status_check.stateChanged.connect(self._toggle_save_status)
+        layout.addRow("", self.save_status_check)
+
+        self.remember_sort_check = QtWidgets.QCheckBox("Remember each grid's sort order between sessions")
+        self.remember_sort_check.setChecked(self.main.settings["ui"].get("remember_sort", True))
+        self.remember_sort_check.stateChanged.connect(self._toggle_remember_sort)
+        layout.addRow("Sorting", self.remember_sort_check)
+
+        self.backup_check = QtWidgets.QCheckBox("Create timestamped backups when saving")
+        self.backup_check.setChecked(self.main.settings["backup"].get("enabled", True))
+        self.backup_check.stateChanged.connect(self._toggle_backups)
+        layout.addRow("Backups", self.backup_check)
+        self.startup_backup_check = QtWidgets.QCheckBox("Also back up on startup (at most once a day)")
+        self.startup_backup_check.setChecked(self.main.settings["backup"].get("on_startup", False))
+        self.startup_backup_check.stateChanged.connect(self._toggle_startup_backup)
+        layout.addRow("", self.startup_backup_check)
+        layout.addRow("", self._build_backup_group())
+
+        save_btn = QtWidgets.QPushButton("Save now")
+        save_btn.clicked.connect(self.main.save_now)
         backup_btn = QtWidgets.QPushButton("Backup now")
         backup_btn.clicked.connect(self._backup_now)
         open_btn = QtWidgets.QPushButton("Open data folder")
@@ -1368,34 +2919,130 @@ class SettingsWidget(QtWidgets.QWidget):
         open_cfg_btn = QtWidgets.QPushButton("Open config folder")
         open_cfg_btn.clicked.connect(self._open_config_dir)
         btn_row = QtWidgets.QHBoxLayout()
+        btn_row.addWidget(save_btn)
         btn_row.addWidget(backup_btn)
         btn_row.addWidget(open_btn)
         btn_row.addWidget(open_cfg_btn)
         layout.addRow("Data", btn_row)
 
-        self._add_path_row(layout, "Items file", self.main.items_path)
-        self._add_path_row(layout, "Money file", self.main.money_path)
-        self._add_path_row(layout, "Backups", self.main.backup_dir)
+        self.items_path_entry = self._add_path_row(layout, "Items file", self.main.items_path)
+        self.money_path_entry = self._add_path_row(layout, "Money file", self.main.money_path)
+        self.backup_dir_entry = self._add_path_row(layout, "Backups", self.main.backup_dir)
         self._add_path_row(layout, "Config (settings.json)", self.main.config_manager.settings_path)
         self._add_path_row(layout, "Weights (weights.txt)", self.main.config_manager.weights_path)
         self._add_path_row(layout, "Themes (themes.json)", self.main.config_manager.themes_path)
 
         info_label = QtWidgets.QLabel(
-            "Weights are read-only at runtime. Edit weights.txt in the config folder and restart to apply changes."
+            "Edit weights in the Weights tab; saving there rewrites weights.txt and rescores every item."
         )
         info_label.setWordWrap(True)
         layout.addRow("Weights", info_label)
 
+    def _build_backup_group(self) -> QtWidgets.QGroupBox:
+        group = QtWidgets.QGroupBox("Backup retention")
+        g_layout = QtWidgets.QFormLayout(group)
+        g_layout.setLabelAlignment(QtCore.Qt.AlignLeft)
+        policy = self.main.settings["backup"]
+        self.keep_recent_spin = QtWidgets.QSpinBox()
+        self.keep_recent_spin.setRange(0, 1000)
+        self.keep_recent_spin.setValue(int(policy.get("keep_recent", 3)))
+        self.keep_recent_spin.setToolTip("Newest backups that are always kept.")
+        self.keep_recent_spin.valueChanged.connect(lambda value: self._set_retention("keep_recent", value))
+        self.keep_historical_spin = QtWidgets.QSpinBox()
+        self.keep_historical_spin.setRange(0, 1000)
+        self.keep_historical_spin.setValue(int(policy.get("keep_historical", 3)))
+        self.keep_historical_spin.setToolTip("Older backups kept, spread evenly across the remaining history.")
+        self.keep_historical_spin.valueChanged.connect(lambda value: self._set_retention("keep_historical", value))
+        g_layout.addRow("Keep recent", self.keep_recent_spin)
+        g_layout.addRow("Keep historical", self.keep_historical_spin)
+        self.items_backup_label = QtWidgets.QLabel()
+        self.money_backup_label = QtWidgets.QLabel()
+        g_layout.addRow("Items backups", self.items_backup_label)
+        g_layout.addRow("Money backups", self.money_backup_label)
+        clean_btn = QtWidgets.QPushButton("Clean now")
+        clean_btn.setToolTip("Delete backups that fall outside the retention policy.")
+        clean_btn.clicked.connect(self._clean_backups)
+        g_layout.addRow("", clean_btn)
+        self._refresh_backup_usage()
+        return group
+
+    def _refresh_backup_usage(self) -> None:
+        for path, label in [
+            (self.main.items_path, self.items_backup_label),
+            (self.main.money_path, self.money_backup_label),
+        ]:
+            count, size = backup_usage(path, self.main.backup_dir)
+            label.setText(f"{count} file{'s' if count != 1 else ''}, {size / 1024:,.1f} KB")
+
+    def _set_retention(self, key: str, value: int) -> None:
+        self.main.settings["backup"][key] = value
+        self.main.config_manager.save_settings()
+
+    def _clean_backups(self) -> None:
+        self.main.flush_pending_saves(wait=True)
+        removed = []
+        for path in [self.main.items_path, self.main.money_path]:
+            removed += enforce_retention(os.path.basename(path), self.main.backup_dir, self.main.settings["backup"])
+        self._refresh_backup_usage()
+        QtWidgets.QMessageBox.information(
+            self, "Backups", f"Removed {len(removed)} backup{'s' if len(removed) != 1 else ''}."
+        )
+
     def _toggle_autosave(self, state: int) -> None:
         self.main.settings["ui"]["autosave"] = bool(state)
         self.main.config_manager.save_settings()
+        self.main._schedule_autosave()
+
+    def _toggle_save_status(self, state: int) -> None:
+        self.main.settings["ui"]["show_save_status"] = bool(state)
+        self.main.statusBar().setVisible(bool(state))
+        self.main.config_manager.save_settings()
+
+    def _toggle_remember_sort(self, state: int) -> None:
+        self.main.settings["ui"]["remember_sort"] = bool(state)
+        if state:
+            self.main._persist_sort_state("items_sort", self.main.item_sort_column, self.main.item_sort_ascending)
+            self.main._persist_sort_state("money_sort", self.main.money_sort_column, self.main.money_sort_ascending)
+        else:
+            self.main.config_manager.save_settings()
+
+    def _toggle_startup_backup(self, state: int) -> None:
+        self.main.settings["backup"]["on_startup"] = bool(state)
+        self.main.config_manager.save_settings()
+
+    def _toggle_backups(self, state: int) -> None:
+        self.main.settings["backup"]["enabled"] = bool(state)
+        self.main.config_manager.save_settings()
+
+    def _set_font_scale(self, scale: float) -> None:
+        self.main.settings["ui"]["font_scale"] = round(scale, 2)
+        self.main.config_manager.save_settings()
+        self.main.apply_accessibility()
+
+    def _set_row_height(self, height: int) -> None:
+        self.main.settings["ui"]["row_height"] = height
+        self.main.config_manager.save_settings()
+        self.main.apply_accessibility()
+
+    def _set_autosave_interval(self, seconds: int) -> None:
+        self.main.settings["ui"]["autosave_interval_seconds"] = seconds
+        self.main.config_manager.save_settings()
+        self.main.set_autosave_interval(seconds)
 
     def _backup_now(self) -> None:
+        self.main.flush_pending_saves(wait=True)
+        if not self.main.settings["backup"].get("enabled", True):
+            QtWidgets.QMessageBox.information(self, "Backup", "Backups are disabled in settings.")
+            return
+        def back_up(progress: Callable[[int], None]) -> None:
+            for path in dict.fromkeys((self.main.items_path, self.main.money_path)):
+                create_backup(path, self.main.backup_dir, self.main.settings["backup"])
+
         try:
-            create_backup(self.main.items_path, self.main.backup_dir, self.main.settings["backup"])
-            create_backup(self.main.money_path, self.main.backup_dir, self.main.settings["backup"])
+            self.main.run_busy("Creating backups...", back_up)
+            self._refresh_backup_usage()
             QtWidgets.QMessageBox.information(self, "Backup", "Backups created.")
-        except FileNotFoundError as exc:
+        except BackupSourceMissingError as exc:
             QtWidgets.QMessageBox.critical(self, "Backup failed", str(exc))
 
     def _open_data_dir(self) -> None:
@@ -1427,51 +3074,72 @@ class SettingsWidget(QtWidgets.QWidget):
         else:
             subprocess.Popen(["xdg-open", target])
 
-    def _add_path_row(self, layout: QtWidgets.QFormLayout, label: str, path: str) -> None:
+    def _add_path_row(self, layout: QtWidgets.QFormLayout, label: str, path: str) -> QtWidgets.QLineEdit:
         row = QtWidgets.QHBoxLayout()
         entry = QtWidgets.QLineEdit(path)
         entry.setReadOnly(True)
         copy_btn = QtWidgets.QPushButton("Copy")
-        copy_btn.clicked.connect(lambda: QtWidgets.QApplication.clipboard().setText(path))
+        copy_btn.clicked.connect(lambda: QtWidgets.QApplication.clipboard().setText(entry.text()))
         row.addWidget(entry)
         row.addWidget(copy_btn)
         layout.addRow(label, row)
+        return entry
 
-    def _add_weights_group(self, layout: QtWidgets.QFormLayout) -> None:
-        group = QtWidgets.QGroupBox("Weights (admin)")
-        g_layout = QtWidgets.QFormLayout(group)
-        g_layout.setLabelAlignment(QtCore.Qt.AlignLeft)
-        self.weight_spins = {}
-        labels = [
-            ("Date", "date"),
-            ("Cost", "cost"),
-            ("Urgency", "urgency"),
-            ("Value", "value"),
-            ("Want", "want"),
-            ("Price vs Similar", "price_comp"),
-            ("Effect", "effect"),
-        ]
-        weights = self.main.weights.get("weights", {})
-        for label, key in labels:
-            spin = QtWidgets.QDoubleSpinBox()
-            spin.setRange(0.0, 10.0)
-            spin.setSingleStep(0.1)
-            spin.setValue(float(weights.get(key, 1.0)))
-            spin.setSuffix("×")
-            g_layout.addRow(f"{label} weight", spin)
-            self.weight_spins[key] = spin
-        save_btn = QtWidgets.QPushButton("Save weights")
-        save_btn.clicked.connect(self._save_weights)
-        g_layout.addRow(save_btn)
-        layout.addRow(group)
-
-    def _save_weights(self) -> None:
-        weights_cfg = self.main.weights
-        weights_cfg.setdefault("weights", {})
-        for key, spin in self.weight_spins.items():
-            weights_cfg["weights"][key] = spin.value()
-        self.main.apply_weights(weights_cfg)
-        QtWidgets.QMessageBox.information(self, "Weights", "Weights saved and applied.")
+    def refresh_paths(self) -> None:
+        """Show the open book's data and backup locations, e.g. after switching books."""
+        self.items_path_entry.setText(self.main.items_path)
+        self.money_path_entry.setText(self.main.money_path)
+        self.backup_dir_entry.setText(self.main.backup_dir)
+        self._refresh_backup_usage()
+
+
+class ColumnsDialog(QtWidgets.QDialog):
+    def __init__(self, parent: QtWidgets.QWidget, current: List[str]) -> None:
+        super().__init__(parent)
+        self.setWindowTitle("Grid Columns")
+        layout = QtWidgets.QVBoxLayout(self)
+        hint = QtWidgets.QLabel("Tick the columns to show and drag to reorder them.")
+        hint.setWordWrap(True)
+        layout.addWidget(hint)
+        self.list = QtWidgets.QListWidget()
+        self.list.setDragDropMode(QtWidgets.QAbstractItemView.InternalMove)
+        ordered = list(current) + [key for key in ITEM_COLUMNS if key not in current]
+        for key in ordered:
+            entry = QtWidgets.QListWidgetItem(ITEM_COLUMNS[key][0])
+            entry.setData(QtCore.Qt.UserRole, key)
+            entry.setFlags(entry.flags() | QtCore.Qt.ItemIsUserCheckable)
+            entry.setCheckState(QtCore.Qt.Checked if key in current else QtCore.Qt.Unchecked)
+            self.list.addItem(entry)
+        layout.addWidget(self.list)
+        buttons = QtWidgets.QDialogButtonBox(
+            QtWidgets.QDialogButtonBox.Ok | QtWidgets.QDialogButtonBox.Cancel | QtWidgets.QDialogButtonBox.RestoreDefaults
+        )
+        buttons.accepted.connect(self._accept)
+        buttons.rejected.connect(self.reject)
+        restore = buttons.button(QtWidgets.QDialogButtonBox.RestoreDefaults)
+        if restore:
+            restore.clicked.connect(self._restore_defaults)
+        layout.addWidget(buttons)
+
+    def selected_columns(self) -> List[str]:
+        selected = []
+        for row in range(self.list.count()):
+            entry = self.list.item(row)
+            if entry.checkState() == QtCore.Qt.Checked:
+                selected.append(entry.data(QtCore.Qt.UserRole))
+        return selected
+
+    def _restore_defaults(self) -> None:
+        for row in range(self.list.count()):
+            entry = self.list.item(row)
+            key = entry.data(QtCore.Qt.UserRole)
+            entry.setCheckState(QtCore.Qt.Checked if key in DEFAULT_ITEM_COLUMNS else QtCore.Qt.Unchecked)
+
+    def _accept(self) -> None:
+        if not self.selected_columns():
+            QtWidgets.QMessageBox.warning(self, "Columns", "Select at least one column.")
+            return
+        self.accept()
 
 
 class ItemDialog(QtWidgets.QDialog):
@@ -1481,9 +3149,42 @@ class ItemDialog(QtWidgets.QDialog):
         self.result_record: Optional[ItemRecord] = None
         self.existing = existing
         self.setWindowTitle("Item" if not existing else "Edit Item")
+        self.setSizeGripEnabled(True)
         self._build_ui()
         if existing:
             self._load(existing)
+        else:
+            for key, value in main.config_manager.item_defaults().items():
+                getattr(self, key).setValue(value)
+        self._initial_state = self._form_state()
+
+    def _form_state(self) -> tuple:
+        return (
+            self.date_edit.dateTime().toString("yyyy-MM-dd HH:mm"),
+            self.product.text(),
+            self.description.toPlainText(),
+            self.location_combo.currentText(),
+            self.reference.text(),
+            self.cost.value(),
+            self.urgency.value(),
+            self.value.value(),
+            self.want.value(),
+            self.price_comp.value(),
+            self.effect.value(),
+            self.effort.value(),
+            self.image_path.text(),
+            self.decision_combo.currentData(),
+            self.expected_uses.value(),
+            self.target_date.date().toString("yyyy-MM-dd"),
+            self.depends_combo.currentData(),
+            self.justification.toPlainText(),
+            self.recurrence.currentText(),
+        )
+
+    def reject(self) -> None:
+        if self._form_state() != self._initial_state and not _confirm_discard(self):
+            return
+        super().reject()
 
     def _build_ui(self) -> None:
         layout = QtWidgets.QFormLayout(self)
@@ -1496,14 +3197,12 @@ class ItemDialog(QtWidgets.QDialog):
         layout.addRow("Date", self.date_edit)
 
         self.product = QtWidgets.QLineEdit()
-        self.description = QtWidgets.QLineEdit()
+        self.description = self._multiline_edit()
         self.location_combo = QtWidgets.QComboBox()
-        self.location_options = ["Online", "Brick and Mortar", "Second-hand", "Gift", "Other"]
-        self.location_combo.addItems(self.location_options)
-        self.location_other = QtWidgets.QLineEdit()
-        self.location_other.setPlaceholderText("Specify location")
-        self.location_other.setEnabled(False)
-        self.location_combo.currentTextChanged.connect(self._location_changed)
+        self.location_combo.setEditable(True)
+        self.location_combo.setInsertPolicy(QtWidgets.QComboBox.NoInsert)
+        self.location_combo.addItems(self.main.config_manager.locations())
+        self.location_combo.lineEdit().setPlaceholderText("Pick or type a location")
         self.reference = QtWidgets.QLineEdit()
         self.cost = QtWidgets.QDoubleSpinBox()
         self.cost.setMaximum(1_000_000)
@@ -1514,26 +3213,53 @@ class ItemDialog(QtWidgets.QDialog):
         self.value.setRange(1, 5)
         self.want = QtWidgets.QSpinBox()
         self.want.setRange(1, 5)
-        self.want.setValue(3)
         self.price_comp = QtWidgets.QSpinBox()
         self.price_comp.setRange(1, 5)
         self.effect = QtWidgets.QSpinBox()
         self.effect.setRange(1, 5)
-        self.justification = QtWidgets.QLineEdit()
+        self.effort = QtWidgets.QSpinBox()
+        self.effort.setRange(0, 5)
+        self.effort.setSpecialValueText("Not set")
+        self.effort.setToolTip("Effort to buy or set up, 1 (little) to 5 (lots); lowers the score when weighted.")
+        self.image_path = QtWidgets.QLineEdit()
+        self.image_path.setPlaceholderText("Optional picture of the item")
+        self.image_path.textChanged.connect(lambda text: _show_item_image(self.image_preview, text.strip()))
+        self.image_browse = QtWidgets.QPushButton("Browse...")
+        self.image_browse.clicked.connect(self._browse_image)
+        self.image_preview = _item_image_label()
+        _show_item_image(self.image_preview, "")
+        self.decision_combo = QtWidgets.QComboBox()
+        self.decision_combo.addItem("Undecided", "")
+        for decision in DECISIONS:
+            self.decision_combo.addItem(decision.title(), decision)
+        self.decision_combo.setToolTip("What you decided, whatever the score says; see `report decisions`.")
+        self.expected_uses = QtWidgets.QSpinBox()
+        self.expected_uses.setRange(0, 1_000_000)
+        self.expected_uses.setSpecialValueText("Not set")
+        self.expected_uses.setToolTip("How many times you expect to use it; used to compute cost per use.")
+        self.target_date = QtWidgets.QDateEdit()
+        self.target_date.setCalendarPopup(True)
+        self.target_date.setDisplayFormat("yyyy-MM-dd")
+        # The minimum date stands in for "no target", shown as "Not set".
+        self.target_date.setMinimumDate(QtCore.QDate(2000, 1, 1))
+        self.target_date.setSpecialValueText("Not set")
+        self.target_date.setDate(self.target_date.minimumDate())
+        self.target_date.setToolTip("Buy-by or watch-until date; scoring ramps up as it nears when weighted.")
+        self.depends_combo = QtWidgets.QComboBox()
+        self.depends_combo.addItem("(none)", "")
+        own_id = self.existing.id if self.existing else ""
+        for item in self.main.items:
+            if item.id != own_id:
+                self.depends_combo.addItem(f"{item.product} ({item.id})", item.id)
+        self.depends_combo.setToolTip("Another item that should be bought before this one.")
+        self.justification = self._multiline_edit()
         self.recurrence = QtWidgets.QComboBox()
         self.recurrence.setFocusPolicy(QtCore.Qt.StrongFocus)
-        self.recurrence.addItems(["none", "once", "weekly", "biweekly", "monthly", "quarterly", "yearly"])
+        self.recurrence.addItems(RECURRENCE_OPTIONS)
 
         layout.addRow("Product", self.product)
         layout.addRow("Description", self.description)
-        location_row = QtWidgets.QHBoxLayout()
-        location_row.setContentsMargins(0, 0, 0, 0)
-        location_row.setSpacing(6)
-        location_row.addWidget(self.location_combo)
-        location_row.addWidget(self.location_other)
-        location_container = QtWidgets.QWidget()
-        location_container.setLayout(location_row)
-        layout.addRow("Location", location_container)
+        layout.addRow("Location", self.location_combo)
         layout.addRow("Reference", self.reference)
         layout.addRow("Cost", self.cost)
         layout.addRow("Urgency", self.urgency)
@@ -1541,6 +3267,16 @@ class ItemDialog(QtWidgets.QDialog):
         layout.addRow("Want", self.want)
         layout.addRow("Price vs Similar", self.price_comp)
         layout.addRow("Effect", self.effect)
+        layout.addRow("Effort", self.effort)
+        image_row = QtWidgets.QHBoxLayout()
+        image_row.addWidget(self.image_path)
+        image_row.addWidget(self.image_browse)
+        layout.addRow("Image", image_row)
+        layout.addRow("", self.image_preview)
+        layout.addRow("Decision", self.decision_combo)
+        layout.addRow("Expected uses", self.expected_uses)
+        layout.addRow("Target date", self.target_date)
+        layout.addRow("Depends on", self.depends_combo)
         layout.addRow("Justification", self.justification)
         layout.addRow("Recurrence", self.recurrence)
 
@@ -1551,19 +3287,35 @@ class ItemDialog(QtWidgets.QDialog):
 
         self._set_tab_order(buttons)
 
+    def _multiline_edit(self) -> QtWidgets.QPlainTextEdit:
+        edit = QtWidgets.QPlainTextEdit()
+        edit.setTabChangesFocus(True)
+        edit.setLineWrapMode(QtWidgets.QPlainTextEdit.WidgetWidth)
+        edit.setWordWrapMode(QtGui.QTextOption.WrapAtWordBoundaryOrAnywhere)
+        line_height = edit.fontMetrics().lineSpacing()
+        edit.setMinimumHeight(line_height * 4 + 12)
+        edit.setSizePolicy(QtWidgets.QSizePolicy.Expanding, QtWidgets.QSizePolicy.Expanding)
+        return edit
+
     def _set_tab_order(self, buttons: QtWidgets.QDialogButtonBox) -> None:
         self.setTabOrder(self.date_edit, self.product)
         self.setTabOrder(self.product, self.description)
         self.setTabOrder(self.description, self.location_combo)
-        self.setTabOrder(self.location_combo, self.location_other)
-        self.setTabOrder(self.location_other, self.reference)
+        self.setTabOrder(self.location_combo, self.reference)
         self.setTabOrder(self.reference, self.cost)
         self.setTabOrder(self.cost, self.urgency)
         self.setTabOrder(self.urgency, self.value)
         self.setTabOrder(self.value, self.want)
         self.setTabOrder(self.want, self.price_comp)
         self.setTabOrder(self.price_comp, self.effect)
-        self.setTabOrder(self.effect, self.justification)
+        self.setTabOrder(self.effect, self.effort)
+        self.setTabOrder(self.effort, self.image_path)
+        self.setTabOrder(self.image_path, self.image_browse)
+        self.setTabOrder(self.image_browse, self.decision_combo)
+        self.setTabOrder(self.decision_combo, self.expected_uses)
+        self.setTabOrder(self.expected_uses, self.target_date)
+        self.setTabOrder(self.target_date, self.depends_combo)
+        self.setTabOrder(self.depends_combo, self.justification)
         self.setTabOrder(self.justification, self.recurrence)
         save_btn = buttons.button(QtWidgets.QDialogButtonBox.Save)
         cancel_btn = buttons.button(QtWidgets.QDialogButtonBox.Cancel)
@@ -1577,18 +3329,8 @@ class ItemDialog(QtWidgets.QDialog):
     def _load(self, item: ItemRecord) -> None:
         self.date_edit.setDateTime(QtCore.QDateTime.fromString(item.date.strftime("%Y-%m-%d %H:%M"), "yyyy-MM-dd HH:mm"))
         self.product.setText(item.product)
-        self.description.setText(item.description)
-        if item.location in self.location_options:
-            idx = self.location_combo.findText(item.location)
-            if idx >= 0:
-                self.location_combo.setCurrentIndex(idx)
-            self.location_other.clear()
-        else:
-            other_idx = self.location_combo.findText("Other")
-            if other_idx >= 0:
-                self.location_combo.setCurrentIndex(other_idx)
-            self.location_other.setText(item.location)
-            self.location_other.setEnabled(True)
+        self.description.setPlainText(item.description)
+        self.location_combo.setCurrentText(item.location)
         self.reference.setText(item.reference)
         self.cost.setValue(item.cost)
         self.urgency.setValue(item.urgency)
@@ -1596,7 +3338,16 @@ class ItemDialog(QtWidgets.QDialog):
         self.want.setValue(item.want)
         self.price_comp.setValue(item.price_comp)
         self.effect.setValue(item.effect)
-        self.justification.setText(item.justification)
+        self.effort.setValue(item.effort or 0)
+        self.image_path.setText(item.image_path)
+        self.decision_combo.setCurrentIndex(max(0, self.decision_combo.findData(item.decision)))
+        self.expected_uses.setValue(item.expected_uses or 0)
+        if item.target_date:
+            self.target_date.setDate(QtCore.QDate(item.target_date.year, item.target_date.month, item.target_date.day))
+        idx = self.depends_combo.findData(item.depends_on)
+        if idx >= 0:
+            self.depends_combo.setCurrentIndex(idx)
+        self.justification.setPlainText(item.justification)
         if item.recurrence:
             idx = self.recurrence.findText(item.recurrence)
             if idx >= 0:
@@ -1608,32 +3359,66 @@ class ItemDialog(QtWidgets.QDialog):
         except Exception:
             QtWidgets.QMessageBox.warning(self, "Invalid", "Invalid date.")
             return
-        location_choice = self.location_combo.currentText()
-        if location_choice == "Other":
-            location_value = self.location_other.text().strip() or "Other"
-        else:
-            location_value = location_choice
+        cost = float(self.cost.value())
+        if self.main.config_manager.cost_exceeds_warning(cost):
+            confirm = QtWidgets.QMessageBox.question(
+                self,
+                "Unusually high cost",
+                f"{self.main.currency_symbol}{cost:,.2f} is above your warning threshold. Save anyway?",
+                QtWidgets.QMessageBox.Yes | QtWidgets.QMessageBox.No,
+                QtWidgets.QMessageBox.No,
+            )
+            if confirm != QtWidgets.QMessageBox.Yes:
+                self.cost.setFocus()
+                return
+        depends_on = self.depends_combo.currentData() or ""
+        own_id = self.existing.id if self.existing else ""
+        problem = dependency_error(self.main.items, own_id, depends_on) if own_id else None
+        if problem:
+            QtWidgets.QMessageBox.warning(self, "Invalid prerequisite", problem)
+            self.depends_combo.setFocus()
+            return
+        location_value = self.main.config_manager.remember_location(self.location_combo.currentText())
         record = ItemRecord(
-            id=self.existing.id if self.existing else str(QtCore.QUuid.createUuid()).strip("{}"),
+            id=self.existing.id if self.existing else new_id(),
             date=date,
             product=self.product.text(),
-            description=self.description.text(),
+            description=self.description.toPlainText(),
             location=location_value,
             reference=self.reference.text(),
-            cost=float(self.cost.value()),
+            cost=cost,
             urgency=int(self.urgency.value()),
             value=int(self.value.value()),
             want=int(self.want.value()),
             price_comp=int(self.price_comp.value()),
             effect=int(self.effect.value()),
-            justification=self.justification.text(),
+            justification=self.justification.toPlainText(),
             recurrence=self.recurrence.currentText(),
+            history=list(self.existing.history) if self.existing else [],
+            expected_uses=int(self.expected_uses.value()) or None,
+            target_date=self._target_date_value(),
+            depends_on=depends_on,
+            effort=int(self.effort.value()) or None,
+            image_path=self.image_path.text().strip(),
+            decision=self.decision_combo.currentData() or "",
+            created_at=self.existing.created_at if self.existing else None,
         )
+        record.touch()
         self.result_record = record
         self.accept()
 
-    def _location_changed(self, text: str) -> None:
-        self.location_other.setEnabled(text == "Other")
+    def _browse_image(self) -> None:
+        current = self.image_path.text().strip()
+        path, _ = QtWidgets.QFileDialog.getOpenFileName(
+            self, "Select item image", os.path.dirname(current) if current else "", IMAGE_FILE_FILTER
+        )
+        if path:
+            self.image_path.setText(path)
+
+    def _target_date_value(self) -> Optional[datetime]:
+        if self.target_date.date() == self.target_date.minimumDate():
+            return None
+        return datetime.combine(self.target_date.date().toPython(), datetime.min.time())
 
 
 class MoneyDialog(QtWidgets.QDialog):
@@ -1643,10 +3428,30 @@ class MoneyDialog(QtWidgets.QDialog):
         self.items = items
         self.result_record: Optional[MoneyRecord] = None
         self.existing = existing
+        # Set while the chosen template leaves the amount to be typed in.
+        self._amount_required_by = ""
         self.setWindowTitle("Money Entry" if not existing else "Edit Money Entry")
         self._build_ui()
         if existing:
             self._load(existing)
+        self._initial_state = self._form_state()
+
+    def _form_state(self) -> tuple:
+        return (
+            self.date_edit.dateTime().toString("yyyy-MM-dd HH:mm"),
+            self.type_box.currentText(),
+            self.source.text(),
+            self.amount.value(),
+            self.notes.text(),
+            self.category_combo.currentText(),
+            self.link_combo.currentData(),
+            self.cleared_check.isChecked(),
+        )
+
+    def reject(self) -> None:
+        if self._form_state() != self._initial_state and not _confirm_discard(self):
+            return
+        super().reject()
 
     def _build_ui(self) -> None:
         layout = QtWidgets.QFormLayout(self)
@@ -1656,6 +3461,14 @@ class MoneyDialog(QtWidgets.QDialog):
         self.date_edit = QtWidgets.QDateTimeEdit(QtCore.QDateTime.currentDateTime())
         self.date_edit.setCalendarPopup(True)
         self.date_edit.setDisplayFormat("yyyy-MM-dd HH:mm")
+        templates = self.main.config_manager.money_templates()
+        if templates and not self.existing:
+            self.template_combo = QtWidgets.QComboBox()
+            self.template_combo.addItem("(none)", None)
+            for name, template in templates.items():
+                self.template_combo.addItem(name, template)
+            self.template_combo.currentIndexChanged.connect(self._apply_template)
+            layout.addRow("Template", self.template_combo)
         layout.addRow("Date", self.date_edit)
 
         self.type_box = QtWidgets.QComboBox()
@@ -1665,6 +3478,12 @@ class MoneyDialog(QtWidgets.QDialog):
         self.amount.setMaximum(10_000_000)
         self.amount.setPrefix(self.main.currency_symbol)
         self.notes = QtWidgets.QLineEdit()
+        self.category_combo = QtWidgets.QComboBox()
+        self.category_combo.setEditable(True)
+        self.category_combo.setInsertPolicy(QtWidgets.QComboBox.NoInsert)
+        self.category_combo.addItem("")
+        self.category_combo.addItems(self.main.config_manager.money_categories())
+        self.category_combo.lineEdit().setPlaceholderText(UNCATEGORIZED)
         self.link_combo = QtWidgets.QComboBox()
         self.link_combo.addItem("", "")
         for item in self.items:
@@ -1674,22 +3493,78 @@ class MoneyDialog(QtWidgets.QDialog):
         layout.addRow("Source/Destination", self.source)
         layout.addRow("Amount", self.amount)
         layout.addRow("Notes", self.notes)
+        layout.addRow("Category", self.category_combo)
         layout.addRow("Linked Item", self.link_combo)
+        self.cleared_check = QtWidgets.QCheckBox("Seen on a bank statement")
+        layout.addRow("Cleared", self.cleared_check)
 
         buttons = QtWidgets.QDialogButtonBox(QtWidgets.QDialogButtonBox.Save | QtWidgets.QDialogButtonBox.Cancel)
+        save_template_btn = buttons.addButton("Save as Template...", QtWidgets.QDialogButtonBox.ActionRole)
+        save_template_btn.clicked.connect(self._save_as_template)
         buttons.accepted.connect(self._save)
         buttons.rejected.connect(self.reject)
         layout.addRow(buttons)
 
+    def _apply_template(self) -> None:
+        template = self.template_combo.currentData() or {}
+        self.type_box.setCurrentText(template.get("entry_type", "expense"))
+        self.source.setText(template.get("source", ""))
+        self.amount.setValue(template.get("amount", 0.0))
+        self.notes.setText(template.get("notes", ""))
+        self.category_combo.setCurrentText(template.get("category", ""))
+        self.link_combo.setCurrentIndex(max(self.link_combo.findData(template.get("link", "")), 0))
+        self._amount_required_by = self.template_combo.currentText() if template and "amount" not in template else ""
+        # Shown in place of 0 so the missing amount stands out.
+        self.amount.setSpecialValueText("Enter amount" if self._amount_required_by else "")
+        if self._amount_required_by:
+            self.amount.setFocus()
+
+    def _save_as_template(self) -> None:
+        name, ok = QtWidgets.QInputDialog.getText(self, "Save as Template", "Template name:")
+        name = " ".join(name.split())
+        if not ok or not name:
+            return
+        config = self.main.config_manager
+        if name in config.money_templates() and (
+            QtWidgets.QMessageBox.question(self, "Save as Template", f"Replace the template '{name}'?")
+            != QtWidgets.QMessageBox.Yes
+        ):
+            return
+        template = {
+            "source": self.source.text().strip(),
+            "entry_type": self.type_box.currentText(),
+            "notes": self.notes.text(),
+            "category": " ".join(self.category_combo.currentText().split()),
+            "link": self.link_combo.currentData() or "",
+        }
+        if (
+            QtWidgets.QMessageBox.question(
+                self,
+                "Save as Template",
+                f"Include the amount {self.main.currency_symbol}{self.amount.value():,.2f}?\n\n"
+                "Choose No to type the amount each time the template is used.",
+            )
+            == QtWidgets.QMessageBox.Yes
+        ):
+            template["amount"] = float(self.amount.value())
+        try:
+            config.save_money_template(name, {key: value for key, value in template.items() if value != ""})
+        except ValueError as exc:
+            QtWidgets.QMessageBox.warning(self, "Save as Template", str(exc))
+            return
+        self.main.notify(f"Saved template '{name}'.")
+
     def _load(self, entry: MoneyRecord) -> None:
         self.date_edit.setDateTime(QtCore.QDateTime.fromString(entry.date.strftime("%Y-%m-%d %H:%M"), "yyyy-MM-dd HH:mm"))
         self.type_box.setCurrentText(entry.entry_type)
         self.source.setText(entry.source_or_destination)
         self.amount.setValue(entry.amount)
         self.notes.setText(entry.notes)
+        self.category_combo.setCurrentText(entry.category)
         idx = self.link_combo.findData(entry.linked_item_id)
         if idx >= 0:
             self.link_combo.setCurrentIndex(idx)
+        self.cleared_check.setChecked(entry.cleared)
 
     def _save(self) -> None:
         try:
@@ -1697,19 +3572,132 @@ class MoneyDialog(QtWidgets.QDialog):
         except Exception:
             QtWidgets.QMessageBox.warning(self, "Invalid", "Invalid date.")
             return
+        if self._amount_required_by and not self.amount.value():
+            QtWidgets.QMessageBox.warning(
+                self, "Amount needed", f"The template '{self._amount_required_by}' has no amount; enter one."
+            )
+            self.amount.setFocus()
+            return
         record = MoneyRecord(
-            id=self.existing.id if self.existing else str(QtCore.QUuid.createUuid()).strip("{}"),
+            id=self.existing.id if self.existing else new_id(),
             date=date,
             entry_type=self.type_box.currentText(),
             source_or_destination=self.source.text(),
             amount=float(self.amount.value()),
             notes=self.notes.text(),
             linked_item_id=self.link_combo.currentData() or "",
+            category=" ".join(self.category_combo.currentText().split()),
+            split_group=self.existing.split_group if self.existing else "",
+            cleared=self.cleared_check.isChecked(),
+            created_at=self.existing.created_at if self.existing else None,
         )
+        record.touch()
         self.result_record = record
         self.accept()
 
 
+class TemplatesDialog(QtWidgets.QDialog):
+    """List and delete the saved money entry templates; new ones come from Save as Template in an entry."""
+
+    def __init__(self, main: MainWindow) -> None:
+        super().__init__(main)
+        self.main = main
+        self.setWindowTitle("Money Templates")
+        layout = QtWidgets.QVBoxLayout(self)
+        hint = QtWidgets.QLabel("Save templates with Save as Template... in an entry, then pick one when adding.")
+        hint.setWordWrap(True)
+        layout.addWidget(hint)
+        self.list = QtWidgets.QListWidget()
+        layout.addWidget(self.list)
+        buttons = QtWidgets.QDialogButtonBox(QtWidgets.QDialogButtonBox.Close)
+        delete_btn = buttons.addButton("Delete", QtWidgets.QDialogButtonBox.ActionRole)
+        delete_btn.clicked.connect(self._delete)
+        buttons.rejected.connect(self.reject)
+        layout.addWidget(buttons)
+        self._refresh()
+
+    def _refresh(self) -> None:
+        self.list.clear()
+        for name, template in self.main.config_manager.money_templates().items():
+            entry = QtWidgets.QListWidgetItem(f"{name}: {describe_money_template(template, self.main.currency_symbol)}")
+            entry.setData(QtCore.Qt.UserRole, name)
+            self.list.addItem(entry)
+
+    def _delete(self) -> None:
+        entry = self.list.currentItem()
+        if entry is None:
+            return
+        name = entry.data(QtCore.Qt.UserRole)
+        choice = QtWidgets.QMessageBox.question(self, "Delete Template", f"Delete the template '{name}'?")
+        if choice == QtWidgets.QMessageBox.Yes:
+            self.main.config_manager.delete_money_template(name)
+            self._refresh()
+
+
+class SplitDialog(QtWidgets.QDialog):
+    """Divide one money entry into parts with their own amounts and categories."""
+
+    def __init__(self, main: MainWindow, entry: MoneyRecord) -> None:
+        super().__init__(main)
+        self.main = main
+        self.entry = entry
+        self.result_parts: List[MoneyRecord] = []
+        self.setWindowTitle("Split Entry")
+        layout = QtWidgets.QVBoxLayout(self)
+        symbol = main.currency_symbol
+        layout.addWidget(
+            QtWidgets.QLabel(f"{entry.source_or_destination}: {symbol}{entry.amount:,.2f} ({entry.entry_type})")
+        )
+        self.parts_layout = QtWidgets.QFormLayout()
+        layout.addLayout(self.parts_layout)
+        self.remaining_label = QtWidgets.QLabel()
+        self.rows: List[tuple] = []
+        half = round(entry.amount / 2, 2)
+        self._add_part(half)
+        self._add_part(round(entry.amount - half, 2))
+        add_btn = QtWidgets.QPushButton("Add part")
+        add_btn.clicked.connect(lambda: self._add_part(0.0))
+        layout.addWidget(add_btn)
+        layout.addWidget(self.remaining_label)
+        buttons = QtWidgets.QDialogButtonBox(QtWidgets.QDialogButtonBox.Ok | QtWidgets.QDialogButtonBox.Cancel)
+        buttons.accepted.connect(self._accept)
+        buttons.rejected.connect(self.reject)
+        layout.addWidget(buttons)
+        self._update_remaining()
+
+    def _add_part(self, amount: float) -> None:
+        spin = QtWidgets.QDoubleSpinBox()
+        spin.setMaximum(10_000_000)
+        spin.setPrefix(self.main.currency_symbol)
+        spin.setValue(amount)
+        spin.valueChanged.connect(self._update_remaining)
+        category = QtWidgets.QComboBox()
+        category.setEditable(True)
+        category.addItem("")
+        category.addItems(self.main.config_manager.money_categories())
+        category.setCurrentText(self.entry.category)
+        row = QtWidgets.QHBoxLayout()
+        row.addWidget(spin)
+        row.addWidget(category)
+        self.rows.append((spin, category))
+        self.parts_layout.addRow(f"Part {len(self.rows)}", row)
+        self._update_remaining()
+
+    def _update_remaining(self, *_args: object) -> None:
+        remaining = self.entry.amount - sum(spin.value() for spin, _ in self.rows)
+        self.remaining_label.setText(f"Left to assign: {self.main.currency_symbol}{remaining:,.2f}")
+
+    def _accept(self) -> None:
+        amounts = [spin.value() for spin, _ in self.rows if spin.value() > 0]
+        categories = [" ".join(combo.currentText().split()) for spin, combo in self.rows if spin.value() > 0]
+        try:
+            self.result_parts = split_entry(self.entry, amounts, categories)
+        except ValueError as exc:
+            QtWidgets.QMessageBox.warning(self, "Split Entry", str(exc))
+            return
+        self.accept()
+
+
 if __name__ == "__main__":
     launch()
 